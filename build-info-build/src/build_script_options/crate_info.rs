@@ -75,6 +75,26 @@ impl crate::BuildScriptOptions {
 pub(crate) struct Manifest {
 	pub crate_info: CrateInfo,
 	pub workspace_root: String,
+	/// The metadata for the target platform
+	pub metadata: Metadata,
+	/// The enabled features, as passed to `cargo metadata --features`
+	pub features: String,
+}
+
+/// Runs `cargo metadata` for the crate being built, resolving platform-specific dependencies for `platform`.
+pub(crate) fn metadata(platform: &str, features: Option<&str>) -> Metadata {
+	let mut args = vec!["--filter-platform".to_string(), platform.to_string()];
+	if let Some(features) = features {
+		args.push("--features".to_string());
+		args.push(features.to_string());
+	}
+	MetadataCommand::new()
+		.cargo_path(std::env::var_os("CARGO").unwrap())
+		.manifest_path(super::cargo_toml())
+		.features(CargoOpt::NoDefaultFeatures)
+		.other_options(args)
+		.exec()
+		.unwrap()
 }
 
 pub(crate) fn read_manifest(
@@ -83,8 +103,6 @@ pub(crate) fn read_manifest(
 	collect_build_dependencies: DependencyDepth,
 	collect_dev_dependencies: DependencyDepth,
 ) -> Manifest {
-	let mut args = vec!["--filter-platform".to_string(), target_platform.to_string()];
-
 	// Cargo does not provide a proper list of enabled features, so we collect metadata once to find all possible
 	// features, convert them to the equivalent `CARGO_FEATURE_` representation, check for collisions, and then rerun
 	// the command with the appropriate feature flags selected.
@@ -92,13 +110,7 @@ pub(crate) fn read_manifest(
 	// We still expect this to fail for dependency flags that are enabled by hand (e.g.,
 	// `cargo run --features=serde/derive`), but so far there is no workaround for that.
 
-	let meta = MetadataCommand::new()
-		.cargo_path(std::env::var_os("CARGO").unwrap())
-		.manifest_path(super::cargo_toml())
-		.features(CargoOpt::NoDefaultFeatures)
-		.other_options(args.clone())
-		.exec()
-		.unwrap();
+	let meta = metadata(target_platform, None);
 
 	let root = &meta[meta.resolve.as_ref().unwrap().root.as_ref().unwrap()];
 	let mut map = HashMap::new();
@@ -129,16 +141,8 @@ pub(crate) fn read_manifest(
 		}
 		feature_list += feature;
 	}
-	args.push("--features".to_string());
-	args.push(feature_list);
 
-	let meta = MetadataCommand::new()
-		.cargo_path(std::env::var_os("CARGO").unwrap())
-		.manifest_path(super::cargo_toml())
-		.features(CargoOpt::NoDefaultFeatures)
-		.other_options(args)
-		.exec()
-		.unwrap();
+	let meta = metadata(target_platform, Some(&feature_list));
 	let crate_info = make_crate_info(
 		&meta,
 		collect_runtime_dependencies,
@@ -158,7 +162,9 @@ pub(crate) fn read_manifest(
 
 	Manifest {
 		crate_info,
-		workspace_root: meta.workspace_root.into(),
+		workspace_root: meta.workspace_root.to_string(),
+		metadata: meta,
+		features: feature_list,
 	}
 }
 
@@ -197,6 +203,7 @@ fn to_crate_info(
 	let version = Version::parse(&pkg.version.to_string()).unwrap();
 	let authors = pkg.authors.clone();
 	let license = pkg.license.clone();
+	let source = pkg.source.as_ref().map(|source| source.repr.clone());
 	let available_features = pkg.features.keys().cloned().collect();
 	let enabled_features = node.features.iter().map(|name| name.to_string()).collect::<Vec<_>>();
 	let dependencies = if collect_runtime_dependencies.do_collect(depth) || collect_build_dependencies.do_collect(depth) {
@@ -233,6 +240,7 @@ fn to_crate_info(
 		version,
 		authors,
 		license,
+		source,
 		enabled_features,
 		available_features,
 		dependencies,
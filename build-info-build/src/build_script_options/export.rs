@@ -0,0 +1,149 @@
+use std::path::PathBuf;
+
+use build_info_common::BuildInfo;
+use serde_yaml_ng::with::singleton_map_recursive;
+
+/// File formats into which the build information can be exported
+#[derive(Clone, Copy, Eq, PartialEq, Debug)]
+pub(crate) enum Format {
+	Json,
+	Toml,
+	Yaml,
+	#[cfg(feature = "sbom")]
+	CycloneDx,
+	#[cfg(feature = "sbom")]
+	SpdxJson,
+	#[cfg(feature = "sbom")]
+	SpdxTagValue,
+}
+
+impl crate::BuildScriptOptions {
+	/// Writes the build information to a JSON file.
+	///
+	/// Relative paths are resolved against `OUT_DIR`, or against the target directory if
+	/// [`export_to_target_dir`](Self::export_to_target_dir) is set. The file follows the schema in
+	/// `schema/build-info.schema.json`, which only changes together with the version of `build-info-common`.
+	pub fn write_json(mut self, path: impl Into<PathBuf>) -> Self {
+		self.exports.push((Format::Json, path.into()));
+		self
+	}
+
+	/// Writes the build information to a TOML file.
+	///
+	/// The layout is the same as for [`write_json`](Self::write_json), except that `None` values are omitted, since TOML
+	/// cannot represent them.
+	pub fn write_toml(mut self, path: impl Into<PathBuf>) -> Self {
+		self.exports.push((Format::Toml, path.into()));
+		self
+	}
+
+	/// Writes the build information to a YAML file.
+	///
+	/// The layout is the same as for [`write_json`](Self::write_json).
+	pub fn write_yaml(mut self, path: impl Into<PathBuf>) -> Self {
+		self.exports.push((Format::Yaml, path.into()));
+		self
+	}
+
+	/// Resolves relative paths given to `write_json`, `write_toml` and `write_yaml` against the directory of the current
+	/// profile (e.g., `target/debug`) instead of `OUT_DIR`, so that the files can be found next to the built binaries.
+	pub fn export_to_target_dir(mut self, export_to_target_dir: bool) -> Self {
+		self.export_to_target_dir = export_to_target_dir;
+		self
+	}
+}
+
+pub(crate) fn export(build_info: &BuildInfo, exports: &[(Format, PathBuf)], to_target_dir: bool, workspace_root: &str) {
+	if exports.is_empty() {
+		return;
+	}
+
+	let out_dir =
+		PathBuf::from(std::env::var_os("OUT_DIR").expect("Expected environment variable `OUT_DIR` to be set by cargo"));
+	let base = if to_target_dir {
+		// `OUT_DIR` is `target/<profile>/build/<package>-<hash>/out`
+		out_dir
+			.ancestors()
+			.nth(3)
+			.expect("Could not determine the target directory from `OUT_DIR`")
+			.to_path_buf()
+	} else {
+		out_dir
+	};
+
+	for (format, path) in exports {
+		let path = base.join(path);
+		if let Some(parent) = path.parent() {
+			std::fs::create_dir_all(parent).unwrap_or_else(|err| panic!("Could not create {}: {err}", parent.display()));
+		}
+		std::fs::write(&path, serialize(build_info, *format, workspace_root))
+			.unwrap_or_else(|err| panic!("Could not write {}: {err}", path.display()));
+	}
+}
+
+#[cfg_attr(not(feature = "sbom"), allow(unused_variables))]
+fn serialize(build_info: &BuildInfo, format: Format, workspace_root: &str) -> String {
+	match format {
+		Format::Json => serde_json::to_string_pretty(build_info).unwrap() + "\n",
+		Format::Toml => toml::to_string_pretty(build_info).expect("Could not serialize BuildInfo as TOML"),
+		Format::Yaml => {
+			// Enums are written as maps with a single key, as in JSON and TOML, instead of using YAML tags
+			let mut yaml = Vec::new();
+			singleton_map_recursive::serialize(build_info, &mut serde_yaml_ng::Serializer::new(&mut yaml))
+				.expect("Could not serialize BuildInfo as YAML");
+			String::from_utf8(yaml).unwrap()
+		}
+		#[cfg(feature = "sbom")]
+		Format::CycloneDx => super::sbom::cyclonedx::cyclonedx(build_info, workspace_root),
+		#[cfg(feature = "sbom")]
+		Format::SpdxJson => super::sbom::spdx::json(build_info, workspace_root),
+		#[cfg(feature = "sbom")]
+		Format::SpdxTagValue => super::sbom::spdx::tag_value(build_info, workspace_root),
+	}
+}
+
+#[cfg(test)]
+mod test {
+	use build_info_common::VersionControl;
+	use build_info_fixtures::build_info;
+	use pretty_assertions::assert_eq;
+
+	use super::*;
+
+	#[test]
+	fn formats() {
+		let mut build_info = build_info();
+		build_info.crate_info.authors = vec!["Jane \"JD\" Doe".to_string()];
+		build_info.crate_info.enabled_features = vec!["key: value".to_string(), "#".to_string()];
+		assert!(matches!(build_info.version_control, Some(VersionControl::Git(_))));
+
+		let json = serialize(&build_info, Format::Json, "");
+		assert_eq!(serde_json::from_str::<BuildInfo>(&json).unwrap(), build_info);
+		let toml = serialize(&build_info, Format::Toml, "");
+		assert_eq!(toml::from_str::<BuildInfo>(&toml).unwrap(), build_info);
+		let yaml = serialize(&build_info, Format::Yaml, "");
+		let deserializer = serde_yaml_ng::Deserializer::from_str(&yaml);
+		assert_eq!(
+			singleton_map_recursive::deserialize::<BuildInfo, _>(deserializer).unwrap(),
+			build_info
+		);
+
+		// YAML has the same layout as JSON
+		let yaml: serde_json::Value = serde_yaml_ng::from_str(&yaml).unwrap();
+		assert_eq!(yaml, serde_json::from_str::<serde_json::Value>(&json).unwrap());
+	}
+
+	fn schema_path() -> PathBuf {
+		PathBuf::from(env!("CARGO_MANIFEST_DIR")).join("schema/build-info.schema.json")
+	}
+
+	/// Run with `BUILD_INFO_UPDATE_SCHEMA=1` to regenerate the schema after changing the types in `build-info-common`.
+	#[test]
+	fn schema() {
+		let schema = serde_json::to_string_pretty(&schemars::schema_for!(BuildInfo)).unwrap() + "\n";
+		if std::env::var_os("BUILD_INFO_UPDATE_SCHEMA").is_some() {
+			std::fs::write(schema_path(), &schema).unwrap();
+		}
+		assert_eq!(std::fs::read_to_string(schema_path()).unwrap(), schema);
+	}
+}
@@ -0,0 +1,73 @@
+#[cfg(feature = "sbom")]
+use spdx::expression::{ExprNode, ExpressionReq, Operator};
+use spdx::{Expression, ParseError, ParseMode};
+
+/// Accepts licenses in the forms accepted by `cargo`, e.g., with `/` instead of `OR`, and treats identifiers that are
+/// not on the SPDX license list as `LicenseRef-`s
+pub(crate) const MODE: ParseMode = ParseMode {
+	allow_unknown: true,
+	..ParseMode::LAX
+};
+
+/// Parses `license` as an SPDX license expression.
+pub(crate) fn parse(license: &str) -> Result<Expression, ParseError> {
+	Expression::parse_mode(license, MODE)
+}
+
+/// Writes `expression` in its canonical form, in which each license term is written by `term`.
+#[cfg(feature = "sbom")]
+pub(crate) fn render(expression: &Expression, mut term: impl FnMut(&ExpressionReq) -> String) -> String {
+	// The expression is stored in postfix order, so each operand is on the stack, along with its top-level operator
+	let mut stack: Vec<(String, Option<Operator>)> = Vec::new();
+	for node in expression.iter() {
+		match node {
+			ExprNode::Req(req) => stack.push((term(req), None)),
+			ExprNode::Op(op) => {
+				let (rhs, lhs) = (stack.pop().unwrap(), stack.pop().unwrap());
+				let operand = |(operand, operator): (String, Option<Operator>)| match (op, operator) {
+					(Operator::And, Some(Operator::Or)) => format!("({operand})"),
+					_ => operand,
+				};
+				let keyword = match op {
+					Operator::And => "AND",
+					Operator::Or => "OR",
+				};
+				stack.push((format!("{} {keyword} {}", operand(lhs), operand(rhs)), Some(*op)));
+			}
+		}
+	}
+	stack.pop().unwrap().0
+}
+
+#[cfg(test)]
+mod test {
+	use super::*;
+
+	#[test]
+	fn parse_cargo_licenses() {
+		for license in [
+			"MIT/Apache-2.0",
+			"GPL-2.0+ WITH Classpath-exception-2.0",
+			"Custom AND LicenseRef-Proprietary",
+		] {
+			assert!(parse(license).is_ok(), "{license:?} should be accepted");
+		}
+		for license in ["", "MIT OR", "MIT)", "Custom License", "Custom!"] {
+			assert!(parse(license).is_err(), "{license:?} should be rejected");
+		}
+	}
+
+	#[test]
+	#[cfg(feature = "sbom")]
+	fn canonical_form() {
+		let canonical = |license: &str| render(&parse(license).unwrap(), |term| term.req.to_string());
+		assert_eq!(canonical("MIT/Apache-2.0"), "MIT OR Apache-2.0");
+		assert_eq!(canonical("(MIT OR ISC) AND Zlib"), "(MIT OR ISC) AND Zlib");
+		assert_eq!(canonical("MIT OR (ISC AND Zlib)"), "MIT OR ISC AND Zlib");
+		assert_eq!(canonical("MIT AND (ISC AND Zlib)"), "MIT AND ISC AND Zlib");
+		assert_eq!(
+			canonical("Custom AND Apache-2.0 WITH LLVM-exception"),
+			"LicenseRef-Custom AND Apache-2.0 WITH LLVM-exception"
+		);
+	}
+}
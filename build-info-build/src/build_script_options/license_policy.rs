@@ -0,0 +1,354 @@
+use std::{collections::HashMap, fmt};
+
+use cargo_metadata::{DependencyKind, Metadata, Node, Package, PackageId};
+use spdx::{LicenseItem, LicenseReq, Licensee};
+
+use super::license;
+
+/// How a [`LicensePolicy`] treats licenses with copyleft obligations, such as the GPL, LGPL or MPL.
+#[derive(Clone, Copy, Eq, PartialEq, Debug, Default)]
+pub enum Copyleft {
+	/// Copyleft licenses are treated like any other license.
+	#[default]
+	Allow,
+	/// Copyleft licenses are only allowed for crates that are not part of the final binary, i.e., build dependencies,
+	/// proc-macros and their dependencies.
+	BuildOnly,
+	/// Copyleft licenses are not allowed.
+	Deny,
+}
+
+/// Restricts the licenses of the dependencies of the crate.
+///
+/// The license of each dependency is parsed as an SPDX expression, of which at least one alternative (e.g., `MIT` for
+/// `MIT OR GPL-3.0`) must consist only of allowed licenses. Crates from the workspace or other local paths are not
+/// checked.
+///
+/// The allowed and denied licenses are single license terms, such as `MIT` or `Apache-2.0 WITH LLVM-exception`, which
+/// are matched against each license term of the expression. An entry matches a term if it satisfies the term, i.e., if
+/// it is the same license (or a later version, if the term allows later versions, e.g., `Apache-2.0` matches
+/// `Apache-1.1+`) with the same exception. An entry without an exception also matches the terms of its license with
+/// any exception (e.g., `GPL-2.0-only` matches `GPL-2.0-only WITH Classpath-exception-2.0`). Licenses that are not on
+/// the SPDX license list are given as `LicenseRef-<name>`, which also matches `<name>` in the license of a dependency.
+/// Licenses with copyleft obligations are those that the SPDX license list marks as such.
+///
+/// ```rust,no_run
+/// # use build_info_build::{Copyleft, LicensePolicy};
+/// build_info_build::build_script().license_policy(
+/// 	LicensePolicy::new()
+/// 		.allow(["MIT", "Apache-2.0", "BSD-3-Clause", "Unicode-3.0"])
+/// 		.copyleft(Copyleft::BuildOnly)
+/// 		.fail_on_violation(true),
+/// );
+/// ```
+#[derive(Clone, Eq, PartialEq, Debug, Default)]
+pub struct LicensePolicy {
+	allow: Vec<Licensee>,
+	deny: Vec<Licensee>,
+	copyleft: Copyleft,
+	fail_on_violation: bool,
+}
+
+impl LicensePolicy {
+	/// Creates a policy that allows all valid license expressions, but reports crates without a license.
+	pub fn new() -> Self {
+		Self::default()
+	}
+
+	/// Allows the given licenses. Once any licenses are allowed, all other licenses are denied.
+	///
+	/// Panics if one of the licenses is not a single license term.
+	pub fn allow(mut self, licenses: impl IntoIterator<Item = impl Into<String>>) -> Self {
+		self.allow.extend(licenses.into_iter().map(licensee));
+		self
+	}
+
+	/// Denies the given licenses, even if they are also allowed.
+	///
+	/// Panics if one of the licenses is not a single license term.
+	pub fn deny(mut self, licenses: impl IntoIterator<Item = impl Into<String>>) -> Self {
+		self.deny.extend(licenses.into_iter().map(licensee));
+		self
+	}
+
+	/// Sets how licenses with copyleft obligations are treated (default: [`Copyleft::Allow`]).
+	pub fn copyleft(mut self, copyleft: Copyleft) -> Self {
+		self.copyleft = copyleft;
+		self
+	}
+
+	/// Fails the build if the policy is violated, instead of only emitting warnings (default: `false`).
+	pub fn fail_on_violation(mut self, fail_on_violation: bool) -> Self {
+		self.fail_on_violation = fail_on_violation;
+		self
+	}
+
+	/// Checks whether the license term `license` is accepted for a dependency with the given usage.
+	fn accepts(&self, license: &LicenseReq, usage: Usage) -> bool {
+		let without_exception = LicenseReq {
+			license: license.license.clone(),
+			addition: None,
+		};
+		let listed = |licensees: &[Licensee]| {
+			licensees
+				.iter()
+				.any(|licensee| licensee.satisfies(license) || licensee.satisfies(&without_exception))
+		};
+		let copyleft = matches!(license.license, LicenseItem::Spdx { id, .. } if id.is_copyleft());
+		let copyleft_allowed = match self.copyleft {
+			Copyleft::Allow => true,
+			Copyleft::BuildOnly => usage == Usage::Build,
+			Copyleft::Deny => false,
+		};
+		!listed(&self.deny) && (self.allow.is_empty() || listed(&self.allow)) && (copyleft_allowed || !copyleft)
+	}
+
+	/// Describes why `dependency` violates the policy, if it does.
+	fn check(&self, dependency: &Dependency) -> Option<String> {
+		let Some(license) = &dependency.license else {
+			return Some(format!("{dependency} does not declare a license"));
+		};
+		match license::parse(license) {
+			Ok(expression) if expression.evaluate(|license| self.accepts(license, dependency.usage)) => None,
+			Ok(_) => Some(format!(
+				"{dependency} is licensed under `{license}`, which is not allowed"
+			)),
+			Err(err) => Some(format!(
+				"{dependency} has an invalid license `{license}`: {}",
+				err.reason
+			)),
+		}
+	}
+
+	/// Emits a warning for each violation, or fails the build if `fail_on_violation` is set.
+	pub(crate) fn enforce(&self, dependencies: &[Dependency]) {
+		let violations: Vec<String> = dependencies
+			.iter()
+			.filter_map(|dependency| self.check(dependency))
+			.collect();
+		if violations.is_empty() {
+			return;
+		}
+
+		if self.fail_on_violation {
+			panic!("The license policy was violated:\n{}", violations.join("\n"));
+		}
+		for violation in violations {
+			println!("cargo:warning=License policy: {violation}");
+		}
+	}
+}
+
+/// Parses an entry of an allow or deny list.
+fn licensee(license: impl Into<String>) -> Licensee {
+	let license = license.into();
+	Licensee::parse_mode(&license, license::MODE)
+		.unwrap_or_else(|err| panic!("Invalid license `{license}` in the license policy: {}", err.reason))
+}
+
+impl crate::BuildScriptOptions {
+	/// Checks the licenses of all runtime and build dependencies against `policy` whenever the build script runs,
+	/// independently of whether dependencies are collected.
+	pub fn license_policy(mut self, policy: LicensePolicy) -> Self {
+		self.license_policy = Some(policy);
+		self
+	}
+}
+
+/// Whether a dependency ends up in the final binary
+#[derive(Clone, Copy, Eq, PartialEq, Ord, PartialOrd, Debug)]
+pub(crate) enum Usage {
+	Build,
+	Runtime,
+}
+
+#[derive(Clone, Eq, PartialEq, Debug)]
+pub(crate) struct Dependency {
+	name: String,
+	version: String,
+	license: Option<String>,
+	usage: Usage,
+}
+
+impl fmt::Display for Dependency {
+	fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+		let usage = match self.usage {
+			Usage::Build => "build",
+			Usage::Runtime => "runtime",
+		};
+		write!(f, "{} v{} ({usage} dependency)", self.name, self.version)
+	}
+}
+
+/// Lists all dependencies that were not built from local sources, sorted by name and version.
+///
+/// Crates that are reachable from the root crate through normal dependencies are runtime dependencies, unless they
+/// are proc-macros. Everything else, i.e., build dependencies, proc-macros and all of their dependencies, is only used
+/// at build time.
+///
+/// The dependencies of runtime dependencies are resolved for the target platform using `target_meta`, while build
+/// dependencies and their dependencies are compiled for the host and are therefore resolved using `host_meta`.
+pub(crate) fn dependencies(target_meta: &Metadata, host_meta: &Metadata) -> Vec<Dependency> {
+	fn nodes(meta: &Metadata) -> HashMap<&PackageId, &Node> {
+		let resolve = meta.resolve.as_ref().unwrap();
+		resolve.nodes.iter().map(|node| (&node.id, node)).collect()
+	}
+
+	/// The edges of type `kinds` from `id` in the dependency graph of the platform described by `nodes`
+	fn edges<'a>(
+		nodes: &HashMap<&PackageId, &'a Node>,
+		id: &PackageId,
+		kinds: &[DependencyKind],
+	) -> Vec<(&'a PackageId, DependencyKind)> {
+		// A crate might not be part of the dependency graph of the host, e.g., if it is only used by the target
+		let deps = nodes.get(id).map_or(&[][..], |node| &node.deps[..]);
+		deps
+			.iter()
+			.flat_map(|dep| dep.dep_kinds.iter().map(move |kind| (&dep.pkg, kind.kind)))
+			.filter(|(_, kind)| kinds.contains(kind))
+			.collect()
+	}
+
+	let (target_nodes, host_nodes) = (nodes(target_meta), nodes(host_meta));
+	let packages: HashMap<&PackageId, &Package> = host_meta
+		.packages
+		.iter()
+		.chain(&target_meta.packages)
+		.map(|package| (&package.id, package))
+		.collect();
+
+	let mut usages = HashMap::new();
+	let mut queue = vec![(
+		target_meta.resolve.as_ref().unwrap().root.as_ref().unwrap(),
+		Usage::Runtime,
+	)];
+	while let Some((id, usage)) = queue.pop() {
+		let dependencies = match usage {
+			Usage::Runtime => {
+				let mut dependencies = edges(&target_nodes, id, &[DependencyKind::Normal]);
+				dependencies.extend(edges(&host_nodes, id, &[DependencyKind::Build]));
+				dependencies
+			}
+			Usage::Build => edges(&host_nodes, id, &[DependencyKind::Normal, DependencyKind::Build]),
+		};
+		for (dep, kind) in dependencies {
+			let is_proc_macro = packages[dep].targets.iter().any(|target| target.is_proc_macro());
+			let dep_usage = match kind {
+				DependencyKind::Normal if usage == Usage::Runtime && !is_proc_macro => Usage::Runtime,
+				_ => Usage::Build,
+			};
+			// Runtime usage takes precedence, in which case the dependencies need to be visited again
+			if usages.get(dep).is_some_and(|known| *known >= dep_usage) {
+				continue;
+			}
+			usages.insert(dep, dep_usage);
+			queue.push((dep, dep_usage));
+		}
+	}
+
+	let mut dependencies: Vec<Dependency> = usages
+		.into_iter()
+		.map(|(id, usage)| (packages[id], usage))
+		.filter(|(package, _)| package.source.is_some())
+		.map(|(package, usage)| Dependency {
+			name: package.name.to_string(),
+			version: package.version.to_string(),
+			license: package.license.clone(),
+			usage,
+		})
+		.collect();
+	dependencies.sort_by(|lhs, rhs| (&lhs.name, &lhs.version).cmp(&(&rhs.name, &rhs.version)));
+	dependencies
+}
+
+#[cfg(test)]
+mod test {
+	use pretty_assertions::assert_eq;
+
+	use super::*;
+
+	fn dependency(license: Option<&str>, usage: Usage) -> Dependency {
+		Dependency {
+			name: "dep".to_string(),
+			version: "1.0.0".to_string(),
+			license: license.map(str::to_string),
+			usage,
+		}
+	}
+
+	fn check(policy: &LicensePolicy, license: Option<&str>, usage: Usage) -> Option<String> {
+		policy.check(&dependency(license, usage))
+	}
+
+	#[test]
+	fn allow_and_deny() {
+		let policy = LicensePolicy::new().allow(["MIT", "Apache-2.0"]).deny(["Apache-2.0"]);
+		assert_eq!(check(&policy, Some("MIT"), Usage::Runtime), None);
+		assert_eq!(check(&policy, Some("mit/Apache-2.0"), Usage::Runtime), None);
+		assert_eq!(
+			check(&policy, Some("Apache-2.0"), Usage::Build),
+			Some("dep v1.0.0 (build dependency) is licensed under `Apache-2.0`, which is not allowed".to_string())
+		);
+		assert!(check(&policy, Some("MIT AND ISC"), Usage::Runtime).is_some());
+		assert_eq!(
+			check(&policy, None, Usage::Runtime),
+			Some("dep v1.0.0 (runtime dependency) does not declare a license".to_string())
+		);
+		assert_eq!(
+			check(&policy, Some("MIT or"), Usage::Runtime),
+			Some(
+				"dep v1.0.0 (runtime dependency) has an invalid license `MIT or`: expected one of `<license>`, `(` here"
+					.to_string()
+			)
+		);
+
+		let policy = LicensePolicy::new();
+		assert_eq!(check(&policy, Some("GPL-3.0-only AND Custom"), Usage::Runtime), None);
+	}
+
+	#[test]
+	#[should_panic(expected = "Invalid license `MIT OR Apache-2.0` in the license policy")]
+	fn invalid_entry() {
+		LicensePolicy::new().allow(["MIT OR Apache-2.0"]);
+	}
+
+	#[test]
+	fn terms() {
+		let policy = LicensePolicy::new().deny(["GPL-2.0-or-later", "Apache-2.0 WITH LLVM-exception"]);
+		assert!(check(&policy, Some("GPL-2.0+"), Usage::Runtime).is_some());
+		assert!(check(&policy, Some("GPL-2.0+ WITH Classpath-exception-2.0"), Usage::Runtime).is_some());
+		assert_eq!(check(&policy, Some("GPL-2.0"), Usage::Runtime), None);
+		assert!(check(&policy, Some("Apache-2.0 WITH LLVM-exception"), Usage::Runtime).is_some());
+		assert_eq!(check(&policy, Some("Apache-2.0"), Usage::Runtime), None);
+
+		let policy = LicensePolicy::new().allow(["GPL-2.0", "Apache-2.0 WITH LLVM-exception", "LicenseRef-Custom"]);
+		assert_eq!(
+			check(&policy, Some("GPL-2.0 WITH Classpath-exception-2.0"), Usage::Runtime),
+			None
+		);
+		assert_eq!(
+			check(&policy, Some("Apache-2.0 WITH LLVM-exception"), Usage::Runtime),
+			None
+		);
+		assert_eq!(
+			check(&policy, Some("Apache-1.1+ WITH LLVM-exception"), Usage::Runtime),
+			None
+		);
+		assert_eq!(check(&policy, Some("Custom"), Usage::Runtime), None);
+		assert!(check(&policy, Some("Apache-2.0"), Usage::Runtime).is_some());
+		assert!(check(&policy, Some("GPL-2.0+"), Usage::Runtime).is_some());
+	}
+
+	#[test]
+	fn copyleft() {
+		let build_only = LicensePolicy::new().copyleft(Copyleft::BuildOnly);
+		assert_eq!(check(&build_only, Some("GPL-3.0-or-later"), Usage::Build), None);
+		assert!(check(&build_only, Some("GPL-3.0-or-later"), Usage::Runtime).is_some());
+		assert!(check(&build_only, Some("MIT AND LGPL-2.1+"), Usage::Runtime).is_some());
+		assert_eq!(check(&build_only, Some("MPL-2.0 OR MIT"), Usage::Runtime), None);
+
+		let deny = LicensePolicy::new().copyleft(Copyleft::Deny);
+		assert!(check(&deny, Some("MPL-2.0"), Usage::Build).is_some());
+		assert_eq!(check(&deny, Some("BSD-3-Clause"), Usage::Build), None);
+	}
+}
@@ -4,11 +4,19 @@ use std::path::{Path, PathBuf};
 use build_info_common::{OptimizationLevel, VersionedString};
 use chrono::{DateTime, Utc};
 
-pub use self::crate_info::DependencyDepth;
+pub use self::{
+	crate_info::DependencyDepth,
+	license_policy::{Copyleft, LicensePolicy},
+};
 use super::BuildInfo;
 
 mod compiler;
 mod crate_info;
+mod export;
+mod license;
+mod license_policy;
+#[cfg(feature = "sbom")]
+mod sbom;
 mod target;
 mod timestamp;
 mod version_control;
@@ -34,6 +42,19 @@ pub struct BuildScriptOptions {
 
 	/// Enable dev dependency collection
 	collect_dev_dependencies: DependencyDepth,
+
+	/// Files into which the build information is written
+	exports: Vec<(export::Format, PathBuf)>,
+
+	/// Resolve relative export paths against the target directory instead of `OUT_DIR`
+	export_to_target_dir: bool,
+
+	/// Make the SBOM available to `build_info!`
+	#[cfg(feature = "sbom")]
+	embed_sbom: bool,
+
+	/// Check the licenses of the dependencies
+	license_policy: Option<LicensePolicy>,
 }
 static BUILD_SCRIPT_RAN: AtomicBool = AtomicBool::new(false);
 
@@ -62,12 +83,23 @@ impl BuildScriptOptions {
 		let crate_info::Manifest {
 			crate_info,
 			workspace_root,
+			metadata,
+			features,
 		} = crate_info::read_manifest(
 			&target.triple,
 			self.collect_runtime_dependencies,
 			self.collect_build_dependencies,
 			self.collect_dev_dependencies,
 		);
+		if let Some(policy) = &self.license_policy {
+			// Build dependencies are compiled for the host, which may resolve to different crates when cross-compiling
+			let host_metadata =
+				(compiler.host_triple != target.triple).then(|| crate_info::metadata(&compiler.host_triple, Some(&features)));
+			policy.enforce(&license_policy::dependencies(
+				&metadata,
+				host_metadata.as_ref().unwrap_or(&metadata),
+			));
+		}
 		let version_control = version_control::get_info();
 
 		let timestamp = self.timestamp.unwrap_or_else(timestamp::get_timestamp);
@@ -91,6 +123,14 @@ impl BuildScriptOptions {
 		let serialized = serde_json::to_string(&versioned).unwrap();
 
 		println!("cargo:rustc-env=BUILD_INFO={serialized}");
+		let cfg = serde_json::to_string(&target::get_cfg()).unwrap();
+		println!("cargo:rustc-env=BUILD_INFO_CFG={cfg}");
+
+		export::export(&build_info, &self.exports, self.export_to_target_dir, &workspace_root);
+		#[cfg(feature = "sbom")]
+		if self.embed_sbom {
+			sbom::embed(&build_info, &workspace_root);
+		}
 
 		// Whenever any `cargo:rerun-if-changed` key is set, the default set is cleared.
 		// Since we will need to emit such keys to trigger rebuilds when the vcs repository changes state,
@@ -124,6 +164,11 @@ impl Default for BuildScriptOptions {
 			collect_runtime_dependencies: DependencyDepth::None,
 			collect_build_dependencies: DependencyDepth::None,
 			collect_dev_dependencies: DependencyDepth::None,
+			exports: Vec::new(),
+			export_to_target_dir: false,
+			#[cfg(feature = "sbom")]
+			embed_sbom: false,
+			license_policy: None,
 		}
 	}
 }
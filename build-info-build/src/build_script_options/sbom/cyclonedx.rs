@@ -0,0 +1,165 @@
+use build_info_common::{BuildInfo, CrateInfo};
+use serde_json::{Value, json};
+
+use super::{
+	super::license::{parse, render},
+	Checksums, Graph, checksum, checksums, purl,
+};
+
+/// Generates a CycloneDX 1.5 document in JSON format.
+pub(crate) fn cyclonedx(build_info: &BuildInfo, workspace_root: &str) -> String {
+	let document = document(build_info, &checksums(workspace_root));
+	serde_json::to_string_pretty(&document).unwrap() + "\n"
+}
+
+fn component(crate_info: &CrateInfo, kind: &str, checksums: &Checksums) -> Value {
+	let purl = purl(crate_info);
+	let mut component = json!({
+		"type": kind,
+		"bom-ref": purl,
+		"name": crate_info.name,
+		"version": crate_info.version.to_string(),
+		"purl": purl,
+	});
+	if !crate_info.authors.is_empty() {
+		component["author"] = json!(crate_info.authors.join(", "));
+	}
+	if let Some(license) = &crate_info.license {
+		// Licenses that are not valid SPDX expressions can only be given by name
+		component["licenses"] = match parse(license) {
+			Ok(expression) => json!([{ "expression": render(&expression, |term| term.req.to_string()) }]),
+			Err(_) => json!([{ "license": { "name": license } }]),
+		};
+	}
+	if let Some(checksum) = checksum(checksums, crate_info) {
+		component["hashes"] = json!([{ "alg": "SHA-256", "content": checksum }]);
+	}
+	if !crate_info.enabled_features.is_empty() {
+		component["properties"] = json!([{ "name": "cargo:features", "value": crate_info.enabled_features.join(",") }]);
+	}
+	component
+}
+
+fn document(build_info: &BuildInfo, checksums: &Checksums) -> Value {
+	let graph = Graph::new(&build_info.crate_info);
+	json!({
+		"bomFormat": "CycloneDX",
+		"specVersion": "1.5",
+		"version": 1,
+		"metadata": {
+			"timestamp": build_info.timestamp.to_rfc3339_opts(chrono::SecondsFormat::Secs, true),
+			"tools": {
+				"components": [{
+					"type": "library",
+					"name": "build-info-build",
+					"version": env!("CARGO_PKG_VERSION"),
+				}],
+			},
+			"component": component(&build_info.crate_info, "application", checksums),
+		},
+		"components": graph
+			.components
+			.values()
+			.map(|crate_info| component(crate_info, "library", checksums))
+			.collect::<Vec<_>>(),
+		"dependencies": graph
+			.dependencies
+			.into_iter()
+			.map(|(bom_ref, depends_on)| json!({ "ref": bom_ref, "dependsOn": depends_on }))
+			.collect::<Vec<_>>(),
+	})
+}
+
+#[cfg(test)]
+mod test {
+	use build_info_fixtures::dependency_tree;
+	use pretty_assertions::assert_eq;
+
+	use super::{
+		super::test::{CHECKSUM, checksums},
+		*,
+	};
+
+	/// Validates documents against the CycloneDX 1.5 schema, which refers to the schemas of the SPDX license list and of
+	/// JSON signatures
+	fn validator() -> jsonschema::Validator {
+		let schema: Value = serde_json::from_str(include_str!("../../../schema/cyclonedx-1.5.schema.json")).unwrap();
+		let licenses = spdx::identifiers::LICENSES.iter().map(|license| license.name);
+		let exceptions = spdx::identifiers::EXCEPTIONS.iter().map(|exception| exception.name);
+		let spdx = json!({
+			"$schema": "http://json-schema.org/draft-07/schema#",
+			"$id": "http://cyclonedx.org/schema/spdx.schema.json",
+			"enum": licenses.chain(exceptions).collect::<Vec<_>>(),
+		});
+		// The generated documents are not signed, so the signature schema is not needed
+		let jsf = json!({
+			"$schema": "http://json-schema.org/draft-07/schema#",
+			"$id": "http://cyclonedx.org/schema/jsf-0.82.schema.json",
+			"definitions": { "signature": {} },
+		});
+		jsonschema::options()
+			.with_resource(
+				"http://cyclonedx.org/schema/spdx.schema.json",
+				jsonschema::Resource::from_contents(spdx).unwrap(),
+			)
+			.with_resource(
+				"http://cyclonedx.org/schema/jsf-0.82.schema.json",
+				jsonschema::Resource::from_contents(jsf).unwrap(),
+			)
+			.build(&schema)
+			.unwrap()
+	}
+
+	#[test]
+	fn components_and_dependencies() {
+		let document = document(&dependency_tree(), &checksums());
+		let validator = validator();
+		let errors: Vec<String> = validator.iter_errors(&document).map(|err| err.to_string()).collect();
+		assert_eq!(errors, Vec::<String>::new());
+		let mut invalid = document.clone();
+		invalid["components"][0]["type"] = json!("crate");
+		assert!(!validator.is_valid(&invalid));
+
+		assert_eq!(document["specVersion"], "1.5");
+		assert_eq!(document["metadata"]["component"]["purl"], "pkg:cargo/sample@1.2.3");
+		assert_eq!(document["metadata"]["component"]["author"], "Jane Doe, John Doe");
+		assert_eq!(
+			document["components"],
+			json!([
+				{
+					"type": "library",
+					"bom-ref": "pkg:cargo/a@1.0.0",
+					"name": "a",
+					"version": "1.0.0",
+					"purl": "pkg:cargo/a@1.0.0",
+					"licenses": [{ "expression": "MIT" }],
+					"hashes": [{ "alg": "SHA-256", "content": CHECKSUM }],
+				},
+				{
+					"type": "library",
+					"bom-ref": "pkg:cargo/b@1.0.0",
+					"name": "b",
+					"version": "1.0.0",
+					"purl": "pkg:cargo/b@1.0.0",
+				},
+				{
+					"type": "library",
+					"bom-ref": "pkg:cargo/shared@1.0.0",
+					"name": "shared",
+					"version": "1.0.0",
+					"purl": "pkg:cargo/shared@1.0.0",
+					"licenses": [{ "expression": "MIT OR Apache-2.0" }],
+				},
+			])
+		);
+		assert_eq!(
+			document["dependencies"],
+			json!([
+				{ "ref": "pkg:cargo/a@1.0.0", "dependsOn": ["pkg:cargo/shared@1.0.0"] },
+				{ "ref": "pkg:cargo/b@1.0.0", "dependsOn": ["pkg:cargo/shared@1.0.0"] },
+				{ "ref": "pkg:cargo/sample@1.2.3", "dependsOn": ["pkg:cargo/a@1.0.0", "pkg:cargo/b@1.0.0"] },
+				{ "ref": "pkg:cargo/shared@1.0.0", "dependsOn": [] },
+			])
+		);
+	}
+}
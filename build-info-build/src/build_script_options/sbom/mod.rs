@@ -0,0 +1,260 @@
+use std::{
+	collections::{BTreeMap, BTreeSet, HashMap},
+	fmt::Write as _,
+	path::{Path, PathBuf},
+};
+
+use build_info_common::{BuildInfo, CrateInfo};
+
+use super::export::Format;
+
+pub(crate) mod cyclonedx;
+pub(crate) mod spdx;
+
+impl crate::BuildScriptOptions {
+	/// Writes a [CycloneDX](https://cyclonedx.org/) 1.5 software bill of materials (SBOM) in JSON format.
+	///
+	/// The SBOM lists the crate and its dependencies with their licenses and the checksums from `Cargo.lock`. Since it is
+	/// generated from the collected build information, dependencies must be collected (e.g., using
+	/// `collect_runtime_dependencies(DependencyDepth::Full)`) for them to show up. The package URLs of crates that are
+	/// not from crates.io name their registry or git repository. Relative paths are resolved as for
+	/// [`write_json`](Self::write_json).
+	pub fn write_sbom(mut self, path: impl Into<PathBuf>) -> Self {
+		self.exports.push((Format::CycloneDx, path.into()));
+		self
+	}
+
+	/// Writes an [SPDX](https://spdx.dev/) 2.3 document in JSON format.
+	///
+	/// The document contains the same packages as [`write_sbom`](Self::write_sbom), with their licenses parsed as SPDX
+	/// license expressions (licenses that cannot be parsed are reported as `NOASSERTION`). Licenses and exceptions that
+	/// are not on the SPDX license list are replaced by `LicenseRef-` identifiers, which are described in the
+	/// `hasExtractedLicensingInfos` of the document. The license of each package is only declared, since it is taken
+	/// from the manifest without analyzing the sources, so `licenseConcluded` is always `NOASSERTION`.
+	pub fn write_spdx_json(mut self, path: impl Into<PathBuf>) -> Self {
+		self.exports.push((Format::SpdxJson, path.into()));
+		self
+	}
+
+	/// Writes an [SPDX](https://spdx.dev/) 2.3 document in tag-value format.
+	///
+	/// The content is the same as for [`write_spdx_json`](Self::write_spdx_json).
+	pub fn write_spdx_tag_value(mut self, path: impl Into<PathBuf>) -> Self {
+		self.exports.push((Format::SpdxTagValue, path.into()));
+		self
+	}
+
+	/// Makes the CycloneDX SBOM available to the crate, where `build_info!(fn sbom_json = sbom)` generates a function
+	/// that returns it as a `&'static str`.
+	pub fn embed_sbom(mut self, embed_sbom: bool) -> Self {
+		self.embed_sbom = embed_sbom;
+		self
+	}
+}
+
+/// Writes the CycloneDX SBOM into `OUT_DIR` and tells `build_info!` where to find it.
+pub(crate) fn embed(build_info: &BuildInfo, workspace_root: &str) {
+	let out_dir =
+		PathBuf::from(std::env::var_os("OUT_DIR").expect("Expected environment variable `OUT_DIR` to be set by cargo"));
+	let path = out_dir.join("build-info.cdx.json");
+	std::fs::write(&path, cyclonedx::cyclonedx(build_info, workspace_root))
+		.unwrap_or_else(|err| panic!("Could not write {}: {err}", path.display()));
+	println!("cargo:rustc-env=BUILD_INFO_SBOM={}", path.to_str().unwrap());
+}
+
+/// SHA-256 checksums of packages by name, version and source
+type Checksums = HashMap<(String, String, String), String>;
+
+/// Reads the SHA-256 checksums of all packages from `Cargo.lock`, which only lists them for packages from registries.
+fn checksums(workspace_root: &str) -> Checksums {
+	let Ok(lockfile) = std::fs::read_to_string(Path::new(workspace_root).join("Cargo.lock")) else {
+		return HashMap::new();
+	};
+	parse_checksums(&lockfile)
+}
+
+fn parse_checksums(lockfile: &str) -> Checksums {
+	let lockfile: toml::Table = match lockfile.parse() {
+		Ok(lockfile) => lockfile,
+		Err(err) => {
+			println!(
+				"cargo:warning=The SBOM contains no checksums, because Cargo.lock could not be parsed: {}",
+				err.message()
+			);
+			return HashMap::new();
+		}
+	};
+	let Some(packages) = lockfile.get("package").and_then(toml::Value::as_array) else {
+		return HashMap::new();
+	};
+	packages
+		.iter()
+		.filter_map(|package| {
+			let field = |key| package.get(key).and_then(toml::Value::as_str).map(str::to_string);
+			Some((
+				(field("name")?, field("version")?, field("source")?),
+				field("checksum")?,
+			))
+		})
+		.collect()
+}
+
+fn checksum<'a>(checksums: &'a Checksums, crate_info: &CrateInfo) -> Option<&'a String> {
+	let source = crate_info.source.clone()?;
+	checksums.get(&(crate_info.name.clone(), crate_info.version.to_string(), source))
+}
+
+/// Percent-encodes all characters that may not appear verbatim in a component of a package URL.
+fn percent_encode(value: &str) -> String {
+	let mut encoded = String::with_capacity(value.len());
+	for byte in value.bytes() {
+		if byte.is_ascii_alphanumeric() || matches!(byte, b'-' | b'.' | b'_' | b'~' | b':' | b'/') {
+			encoded.push(byte as char);
+		} else {
+			write!(encoded, "%{byte:02X}").unwrap();
+		}
+	}
+	encoded
+}
+
+/// The package URL of a crate, which names the source of crates that are not from crates.io as `repository_url` (for
+/// registries) or `vcs_url` (for git repositories)
+fn purl(crate_info: &CrateInfo) -> String {
+	let purl = format!(
+		"pkg:cargo/{}@{}",
+		crate_info.name,
+		percent_encode(&crate_info.version.to_string())
+	);
+	let Some(source) = crate_info.source.as_deref() else {
+		return purl;
+	};
+	if let Some(repository) = source.strip_prefix("git+") {
+		// Git sources have the form `git+<url>?<reference>#<commit>`
+		let (url, commit) = repository.split_once('#').unwrap_or((repository, ""));
+		let url = url.split_once('?').map_or(url, |(url, _reference)| url);
+		let vcs_url = if commit.is_empty() {
+			format!("git+{url}")
+		} else {
+			format!("git+{url}@{commit}")
+		};
+		return format!("{purl}?vcs_url={}", percent_encode(&vcs_url));
+	}
+	match source
+		.strip_prefix("registry+")
+		.or_else(|| source.strip_prefix("sparse+"))
+	{
+		Some("https://github.com/rust-lang/crates.io-index" | "https://index.crates.io/") => purl,
+		Some(registry) => format!("{purl}?repository_url={}", percent_encode(registry)),
+		None => format!("{purl}?repository_url={}", percent_encode(source)),
+	}
+}
+
+/// The crates of the dependency tree, keyed by their purl
+struct Graph<'a> {
+	/// Each dependency, even if it can be reached through several paths in the dependency tree
+	components: BTreeMap<String, &'a CrateInfo>,
+	/// The direct dependencies of the root crate and of each component
+	dependencies: BTreeMap<String, BTreeSet<String>>,
+}
+
+impl<'a> Graph<'a> {
+	fn new(root: &'a CrateInfo) -> Self {
+		fn collect<'a>(crate_info: &'a CrateInfo, graph: &mut Graph<'a>) {
+			let depends_on = graph.dependencies.entry(purl(crate_info)).or_default();
+			depends_on.extend(crate_info.dependencies.iter().map(purl));
+			for dependency in &crate_info.dependencies {
+				graph.components.entry(purl(dependency)).or_insert(dependency);
+				collect(dependency, graph);
+			}
+		}
+
+		let mut graph = Graph {
+			components: BTreeMap::new(),
+			dependencies: BTreeMap::new(),
+		};
+		collect(root, &mut graph);
+		graph
+	}
+}
+
+#[cfg(test)]
+pub(crate) mod test {
+	use build_info_common::semver::Version;
+	use pretty_assertions::assert_eq;
+
+	use super::*;
+
+	/// The SHA-256 checksum of `a` 1.0.0 from crates.io
+	pub(crate) const CHECKSUM: &str = "0123456789abcdef0123456789abcdef0123456789abcdef0123456789abcdef";
+
+	pub(crate) fn checksums() -> Checksums {
+		HashMap::from([(
+			(
+				"a".to_string(),
+				"1.0.0".to_string(),
+				"registry+https://github.com/rust-lang/crates.io-index".to_string(),
+			),
+			CHECKSUM.to_string(),
+		)])
+	}
+
+	#[test]
+	fn purl_encoding() {
+		let mut crate_info = build_info_fixtures::crate_info("a", None, Vec::new());
+		assert_eq!(purl(&crate_info), "pkg:cargo/a@1.0.0");
+		crate_info.version = Version::parse("1.0.0-rc.1+build.5").unwrap();
+		assert_eq!(purl(&crate_info), "pkg:cargo/a@1.0.0-rc.1%2Bbuild.5");
+	}
+
+	#[test]
+	fn purl_sources() {
+		let purl_from = |source: Option<&str>| {
+			let mut crate_info = build_info_fixtures::crate_info("a", None, Vec::new());
+			crate_info.source = source.map(str::to_string);
+			purl(&crate_info)
+		};
+		assert_eq!(purl_from(None), "pkg:cargo/a@1.0.0");
+		assert_eq!(purl_from(Some("sparse+https://index.crates.io/")), "pkg:cargo/a@1.0.0");
+		assert_eq!(
+			purl_from(Some("sparse+https://example.com/index/")),
+			"pkg:cargo/a@1.0.0?repository_url=https://example.com/index/"
+		);
+		assert_eq!(
+			purl_from(Some("git+https://example.com/a.git?branch=fix#4f1bc2a")),
+			"pkg:cargo/a@1.0.0?vcs_url=git%2Bhttps://example.com/a.git%404f1bc2a"
+		);
+	}
+
+	#[test]
+	fn lockfile_checksums() {
+		let lockfile = r#"
+version = 4
+
+[[package]]
+name = "a"
+version = "1.0.0"
+source = "registry+https://github.com/rust-lang/crates.io-index"
+checksum = "1111111111111111111111111111111111111111111111111111111111111111"
+
+[[package]]
+name = "a"
+version = "1.0.0"
+source = "sparse+https://example.com/index/"
+checksum = "2222222222222222222222222222222222222222222222222222222222222222"
+
+[[package]]
+name = "local"
+version = "1.0.0"
+"#;
+		let checksums = parse_checksums(lockfile);
+		assert_eq!(checksums.len(), 2);
+		let mut crate_info = build_info_fixtures::crate_info("a", None, Vec::new());
+		assert_eq!(checksum(&checksums, &crate_info).unwrap(), &"1".repeat(64));
+		crate_info.source = Some("sparse+https://example.com/index/".to_string());
+		assert_eq!(checksum(&checksums, &crate_info).unwrap(), &"2".repeat(64));
+		crate_info.source = None;
+		assert_eq!(checksum(&checksums, &crate_info), None);
+
+		assert_eq!(parse_checksums("[[package]\nname ="), HashMap::new());
+	}
+}
@@ -0,0 +1,438 @@
+use std::{
+	collections::{BTreeMap, HashMap, HashSet},
+	fmt::Write as _,
+};
+
+use build_info_common::{BuildInfo, CrateInfo, VersionControl};
+use chrono::SecondsFormat;
+use serde_json::{Value, json};
+use spdx::{AdditionItem, LicenseItem, LicenseReq};
+
+use super::{
+	super::license::{parse, render},
+	Checksums, Graph, checksum, checksums, purl,
+};
+
+const NOASSERTION: &str = "NOASSERTION";
+
+/// The contents of an SPDX 2.3 document, which can be written in JSON or tag-value format
+struct Document {
+	name: String,
+	namespace: String,
+	created: String,
+	packages: Vec<Package>,
+	/// The names of the licenses that are not on the SPDX license list, by their `LicenseRef-` identifier
+	extracted_licenses: BTreeMap<String, String>,
+	/// `(element, relationship type, related element)`
+	relationships: Vec<(String, &'static str, String)>,
+}
+
+struct Package {
+	id: String,
+	name: String,
+	version: String,
+	license: String,
+	checksum: Option<String>,
+	purl: String,
+}
+
+/// Generates an SPDX 2.3 document in JSON format.
+pub(crate) fn json(build_info: &BuildInfo, workspace_root: &str) -> String {
+	let document = document(build_info, &checksums(workspace_root));
+	serde_json::to_string_pretty(&document.to_json()).unwrap() + "\n"
+}
+
+/// Generates an SPDX 2.3 document in tag-value format.
+pub(crate) fn tag_value(build_info: &BuildInfo, workspace_root: &str) -> String {
+	document(build_info, &checksums(workspace_root)).to_tag_value()
+}
+
+/// Assigns an SPDX identifier to each crate of the document, keyed by its purl. SPDX identifiers may only contain
+/// letters, numbers, `.` and `-`, so different crates (e.g., `a_b` and `a-b`, or the same crate from different sources)
+/// may map to the same identifier, which is then made unique by a numeric suffix.
+fn spdx_ids<'a>(crates: impl IntoIterator<Item = &'a CrateInfo>) -> HashMap<String, String> {
+	let mut taken = HashSet::new();
+	let mut ids = HashMap::new();
+	for crate_info in crates {
+		let id: String = format!("Package-{}-{}", crate_info.name, crate_info.version)
+			.chars()
+			.map(|c| if c.is_ascii_alphanumeric() || c == '.' { c } else { '-' })
+			.collect();
+		let id = (1..)
+			.map(|n| {
+				if n == 1 {
+					format!("SPDXRef-{id}")
+				} else {
+					format!("SPDXRef-{id}-{n}")
+				}
+			})
+			.find(|id| !taken.contains(id))
+			.unwrap();
+		taken.insert(id.clone());
+		ids.insert(purl(crate_info), id);
+	}
+	ids
+}
+
+/// Writes the license term `term`, replacing licenses that are not on the SPDX license list (including those with an
+/// unlisted exception) by a `LicenseRef-` identifier, whose name is recorded in `extracted_licenses`.
+fn listed_license(term: &LicenseReq, extracted_licenses: &mut BTreeMap<String, String>) -> String {
+	let listed_exception = matches!(term.addition, None | Some(AdditionItem::Spdx(_)));
+	let (id, name) = match &term.license {
+		LicenseItem::Spdx { .. } if listed_exception => return term.to_string(),
+		// Licenses from other documents are described there
+		LicenseItem::Other(reference) if reference.doc_ref.is_some() && listed_exception => return term.to_string(),
+		LicenseItem::Other(reference) if term.addition.is_none() => {
+			(format!("LicenseRef-{}", reference.lic_ref), reference.lic_ref.clone())
+		}
+		_ => {
+			let name = term.to_string();
+			let id: String = name
+				.chars()
+				.map(|c| if c.is_ascii_alphanumeric() || c == '.' { c } else { '-' })
+				.collect();
+			(
+				format!("LicenseRef-{}", id.strip_prefix("LicenseRef-").unwrap_or(&id)),
+				name,
+			)
+		}
+	};
+	extracted_licenses.insert(id.clone(), name);
+	id
+}
+
+fn package(
+	crate_info: &CrateInfo,
+	id: String,
+	checksums: &Checksums,
+	extracted_licenses: &mut BTreeMap<String, String>,
+) -> Package {
+	Package {
+		id,
+		name: crate_info.name.clone(),
+		version: crate_info.version.to_string(),
+		license: crate_info
+			.license
+			.as_deref()
+			.map(parse)
+			.and_then(Result::ok)
+			.map_or_else(
+				|| NOASSERTION.to_string(),
+				|expression| render(&expression, |term| listed_license(&term.req, extracted_licenses)),
+			),
+		checksum: checksum(checksums, crate_info).cloned(),
+		purl: purl(crate_info),
+	}
+}
+
+fn document(build_info: &BuildInfo, checksums: &Checksums) -> Document {
+	let root = &build_info.crate_info;
+	let graph = Graph::new(root);
+	let ids = spdx_ids(std::iter::once(root).chain(graph.components.values().copied()));
+
+	// The namespace has to be unique for each version of the document, so the commit is included if it is known
+	let revision = match &build_info.version_control {
+		Some(VersionControl::Git(git)) if git.dirty => format!("{}-dirty", git.commit_id),
+		Some(VersionControl::Git(git)) => git.commit_id.clone(),
+		None => build_info.timestamp.timestamp().to_string(),
+	};
+
+	let mut relationships = vec![("SPDXRef-DOCUMENT".to_string(), "DESCRIBES", ids[&purl(root)].clone())];
+	for (dependent, dependencies) in &graph.dependencies {
+		for dependency in dependencies {
+			relationships.push((ids[dependent].clone(), "DEPENDS_ON", ids[dependency].clone()));
+		}
+	}
+
+	let mut extracted_licenses = BTreeMap::new();
+	let packages = std::iter::once(root)
+		.chain(graph.components.values().copied())
+		.map(|crate_info| {
+			package(
+				crate_info,
+				ids[&purl(crate_info)].clone(),
+				checksums,
+				&mut extracted_licenses,
+			)
+		})
+		.collect();
+
+	Document {
+		name: format!("{}-{}", root.name, root.version),
+		namespace: format!("https://spdx.org/spdxdocs/{}-{}-{revision}", root.name, root.version),
+		created: build_info.timestamp.to_rfc3339_opts(SecondsFormat::Secs, true),
+		packages,
+		extracted_licenses,
+		relationships,
+	}
+}
+
+fn creator() -> String {
+	format!("Tool: build-info-build-{}", env!("CARGO_PKG_VERSION"))
+}
+
+/// The text of a license that is not on the SPDX license list, which is not known to build-info-build
+fn extracted_text(name: &str) -> String {
+	format!("The license `{name}` is declared in the package manifest, but its text is not available.")
+}
+
+impl Document {
+	fn to_json(&self) -> Value {
+		let packages: Vec<Value> = self
+			.packages
+			.iter()
+			.map(|package| {
+				let mut value = json!({
+					"SPDXID": package.id,
+					"name": package.name,
+					"versionInfo": package.version,
+					"downloadLocation": NOASSERTION,
+					"filesAnalyzed": false,
+					"licenseConcluded": NOASSERTION,
+					"licenseDeclared": package.license,
+					"copyrightText": NOASSERTION,
+					"externalRefs": [{
+						"referenceCategory": "PACKAGE-MANAGER",
+						"referenceType": "purl",
+						"referenceLocator": package.purl,
+					}],
+				});
+				if let Some(checksum) = &package.checksum {
+					value["checksums"] = json!([{ "algorithm": "SHA256", "checksumValue": checksum }]);
+				}
+				value
+			})
+			.collect();
+		let relationships: Vec<Value> = self
+			.relationships
+			.iter()
+			.map(|(element, kind, related)| {
+				json!({ "spdxElementId": element, "relationshipType": kind, "relatedSpdxElement": related })
+			})
+			.collect();
+
+		let mut document = json!({
+			"spdxVersion": "SPDX-2.3",
+			"dataLicense": "CC0-1.0",
+			"SPDXID": "SPDXRef-DOCUMENT",
+			"name": self.name,
+			"documentNamespace": self.namespace,
+			"creationInfo": {
+				"created": self.created,
+				"creators": [creator()],
+			},
+			"packages": packages,
+			"relationships": relationships,
+		});
+		if !self.extracted_licenses.is_empty() {
+			let extracted_licenses: Vec<Value> = self
+				.extracted_licenses
+				.iter()
+				.map(|(id, name)| json!({ "licenseId": id, "name": name, "extractedText": extracted_text(name) }))
+				.collect();
+			document["hasExtractedLicensingInfos"] = json!(extracted_licenses);
+		}
+		document
+	}
+
+	fn to_tag_value(&self) -> String {
+		let mut result = String::new();
+		let out = &mut result;
+		writeln!(out, "SPDXVersion: SPDX-2.3").unwrap();
+		writeln!(out, "DataLicense: CC0-1.0").unwrap();
+		writeln!(out, "SPDXID: SPDXRef-DOCUMENT").unwrap();
+		writeln!(out, "DocumentName: {}", self.name).unwrap();
+		writeln!(out, "DocumentNamespace: {}", self.namespace).unwrap();
+		writeln!(out, "Creator: {}", creator()).unwrap();
+		writeln!(out, "Created: {}", self.created).unwrap();
+
+		for package in &self.packages {
+			writeln!(out).unwrap();
+			writeln!(out, "PackageName: {}", package.name).unwrap();
+			writeln!(out, "SPDXID: {}", package.id).unwrap();
+			writeln!(out, "PackageVersion: {}", package.version).unwrap();
+			writeln!(out, "PackageDownloadLocation: {NOASSERTION}").unwrap();
+			writeln!(out, "FilesAnalyzed: false").unwrap();
+			if let Some(checksum) = &package.checksum {
+				writeln!(out, "PackageChecksum: SHA256: {checksum}").unwrap();
+			}
+			writeln!(out, "PackageLicenseConcluded: {NOASSERTION}").unwrap();
+			writeln!(out, "PackageLicenseDeclared: {}", package.license).unwrap();
+			writeln!(out, "PackageCopyrightText: {NOASSERTION}").unwrap();
+			writeln!(out, "ExternalRef: PACKAGE-MANAGER purl {}", package.purl).unwrap();
+		}
+
+		for (id, name) in &self.extracted_licenses {
+			writeln!(out).unwrap();
+			writeln!(out, "LicenseID: {id}").unwrap();
+			writeln!(out, "ExtractedText: <text>{}</text>", extracted_text(name)).unwrap();
+			writeln!(out, "LicenseName: {name}").unwrap();
+		}
+
+		writeln!(out).unwrap();
+		for (element, kind, related) in &self.relationships {
+			writeln!(out, "Relationship: {element} {kind} {related}").unwrap();
+		}
+		result
+	}
+}
+
+#[cfg(test)]
+mod test {
+	use build_info_fixtures::{crate_info, dependency_tree};
+	use pretty_assertions::assert_eq;
+
+	use super::{
+		super::test::{CHECKSUM, checksums},
+		*,
+	};
+
+	/// [`dependency_tree`] with an additional dependency `c`, whose license is not on the SPDX license list
+	fn unlisted_licenses() -> BuildInfo {
+		let mut build_info = dependency_tree();
+		build_info.crate_info.dependencies.push(crate_info(
+			"c",
+			Some("Custom AND (LicenseRef-Proprietary OR GPL-2.0+ WITH Custom-exception) AND Apache-2.0 WITH LLVM-exception"),
+			Vec::new(),
+		));
+		build_info
+	}
+
+	#[test]
+	fn json_matches_schema() {
+		let schema: Value = serde_json::from_str(include_str!("../../../schema/spdx-2.3.schema.json")).unwrap();
+		let validator = jsonschema::validator_for(&schema).unwrap();
+		let document = document(&unlisted_licenses(), &checksums()).to_json();
+		let errors: Vec<String> = validator.iter_errors(&document).map(|err| err.to_string()).collect();
+		assert_eq!(errors, Vec::<String>::new());
+		let mut invalid = document.clone();
+		invalid.as_object_mut().unwrap().remove("documentNamespace");
+		assert!(!validator.is_valid(&invalid));
+
+		assert_eq!(document["name"], "sample-1.2.3");
+		assert!(
+			document["documentNamespace"]
+				.as_str()
+				.unwrap()
+				.starts_with("https://spdx.org/spdxdocs/sample-1.2.3-")
+		);
+		for package in document["packages"].as_array().unwrap() {
+			let id = package["SPDXID"].as_str().unwrap();
+			assert!(
+				id.strip_prefix("SPDXRef-")
+					.unwrap()
+					.chars()
+					.all(|c| c.is_ascii_alphanumeric() || c == '.' || c == '-')
+			);
+		}
+	}
+
+	#[test]
+	fn packages_and_relationships() {
+		let document = document(&dependency_tree(), &checksums());
+		let licenses: Vec<(&str, &str)> = document
+			.packages
+			.iter()
+			.map(|package| (package.name.as_str(), package.license.as_str()))
+			.collect();
+		assert_eq!(
+			licenses,
+			[
+				("sample", NOASSERTION),
+				("a", "MIT"),
+				("b", NOASSERTION),
+				("shared", "MIT OR Apache-2.0")
+			]
+		);
+
+		let tag_value = document.to_tag_value();
+		assert!(tag_value.starts_with("SPDXVersion: SPDX-2.3\nDataLicense: CC0-1.0\nSPDXID: SPDXRef-DOCUMENT\n"));
+		assert!(tag_value.contains(&format!(
+			"\nPackageName: a\nSPDXID: SPDXRef-Package-a-1.0.0\nPackageVersion: 1.0.0\nPackageDownloadLocation: \
+			 NOASSERTION\nFilesAnalyzed: false\nPackageChecksum: SHA256: {CHECKSUM}\nPackageLicenseConcluded: \
+			 NOASSERTION\nPackageLicenseDeclared: MIT\n"
+		)));
+		let relationships: Vec<&str> = tag_value
+			.lines()
+			.filter_map(|line| line.strip_prefix("Relationship: "))
+			.collect();
+		assert_eq!(
+			relationships,
+			[
+				"SPDXRef-DOCUMENT DESCRIBES SPDXRef-Package-sample-1.2.3",
+				"SPDXRef-Package-a-1.0.0 DEPENDS_ON SPDXRef-Package-shared-1.0.0",
+				"SPDXRef-Package-b-1.0.0 DEPENDS_ON SPDXRef-Package-shared-1.0.0",
+				"SPDXRef-Package-sample-1.2.3 DEPENDS_ON SPDXRef-Package-a-1.0.0",
+				"SPDXRef-Package-sample-1.2.3 DEPENDS_ON SPDXRef-Package-b-1.0.0",
+			]
+		);
+	}
+
+	#[test]
+	fn unique_ids() {
+		let mut build_info = dependency_tree();
+		let mut forked = crate_info("shared", None, Vec::new());
+		forked.source = Some("git+https://example.com/shared.git#4f1bc2a".to_string());
+		build_info
+			.crate_info
+			.dependencies
+			.extend([crate_info("a_", None, Vec::new()), forked]);
+
+		let document = document(&build_info, &checksums());
+		let ids: Vec<(&str, &str)> = document
+			.packages
+			.iter()
+			.map(|package| (package.purl.as_str(), package.id.as_str()))
+			.collect();
+		assert_eq!(
+			ids,
+			[
+				("pkg:cargo/sample@1.2.3", "SPDXRef-Package-sample-1.2.3"),
+				("pkg:cargo/a@1.0.0", "SPDXRef-Package-a-1.0.0"),
+				("pkg:cargo/a_@1.0.0", "SPDXRef-Package-a--1.0.0"),
+				("pkg:cargo/b@1.0.0", "SPDXRef-Package-b-1.0.0"),
+				("pkg:cargo/shared@1.0.0", "SPDXRef-Package-shared-1.0.0"),
+				(
+					"pkg:cargo/shared@1.0.0?vcs_url=git%2Bhttps://example.com/shared.git%404f1bc2a",
+					"SPDXRef-Package-shared-1.0.0-2"
+				),
+			]
+		);
+		assert_eq!(document.packages[1].checksum.as_deref(), Some(CHECKSUM));
+		assert_eq!(document.packages[5].checksum, None);
+	}
+
+	#[test]
+	fn extracted_licenses() {
+		let document = document(&unlisted_licenses(), &checksums());
+		assert_eq!(
+			document
+				.packages
+				.iter()
+				.find(|package| package.name == "c")
+				.unwrap()
+				.license,
+			"LicenseRef-Custom AND (LicenseRef-Proprietary OR LicenseRef-GPL-2.0-or-later-WITH-AdditionRef-Custom-exception) \
+			 AND Apache-2.0 WITH LLVM-exception"
+		);
+
+		let json = document.to_json();
+		assert_eq!(
+			json["hasExtractedLicensingInfos"][0],
+			json!({
+				"licenseId": "LicenseRef-Custom",
+				"name": "Custom",
+				"extractedText": "The license `Custom` is declared in the package manifest, but its text is not available.",
+			})
+		);
+		assert_eq!(json["hasExtractedLicensingInfos"].as_array().unwrap().len(), 3);
+
+		let tag_value = document.to_tag_value();
+		assert!(tag_value.contains(
+			"\nLicenseID: LicenseRef-GPL-2.0-or-later-WITH-AdditionRef-Custom-exception\nExtractedText: <text>The license \
+			 `GPL-2.0-or-later WITH AdditionRef-Custom-exception` is declared in the package manifest, but its text is not \
+			 available.</text>\nLicenseName: GPL-2.0-or-later WITH AdditionRef-Custom-exception\n"
+		));
+		assert!(tag_value.contains("\nLicenseID: LicenseRef-Proprietary\n"));
+	}
+}
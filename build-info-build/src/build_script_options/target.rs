@@ -1,3 +1,5 @@
+use std::collections::BTreeMap;
+
 use build_info_common::{CpuInfo, Endianness, TargetInfo};
 
 pub(crate) fn get_info() -> TargetInfo {
@@ -19,11 +21,92 @@ pub(crate) fn get_info() -> TargetInfo {
 				},
 				Err(err) => panic!("Could not read `CARGO_CFG_TARGET_ENDIAN`: {err}"),
 			},
-			features: std::env::var("CARGO_CFG_TARGET_FEATURE")
-				.unwrap_or_default()
-				.split(',')
-				.map(|s| s.to_owned())
-				.collect(),
+			features: get_features(std::env::var("CARGO_CFG_TARGET_FEATURE").ok()),
 		},
 	}
 }
+
+/// Turns the comma-separated feature list from `CARGO_CFG_TARGET_FEATURE` into a sorted list without duplicates or
+/// empty entries.
+fn get_features(features: Option<String>) -> Vec<String> {
+	let mut features: Vec<String> = features
+		.unwrap_or_default()
+		.split(',')
+		.map(str::trim)
+		.filter(|feature| !feature.is_empty())
+		.map(|feature| feature.to_owned())
+		.collect();
+	features.sort();
+	features.dedup();
+	features
+}
+
+/// Collects the configuration options of the crate being built from the `CARGO_CFG_*` environment variables, so that
+/// the proc-macros, which do not see them, can evaluate `cfg!`.
+pub(crate) fn get_cfg() -> BTreeMap<String, Vec<String>> {
+	cfg_from_vars(
+		std::env::vars_os().filter_map(|(name, value)| Some((name.into_string().ok()?, value.into_string().ok()?))),
+	)
+}
+
+/// Maps, e.g., `CARGO_CFG_PANIC=unwind` to `panic: ["unwind"]` and `CARGO_CFG_DEBUG_ASSERTIONS=` to
+/// `debug_assertions: []`.
+fn cfg_from_vars(vars: impl Iterator<Item = (String, String)>) -> BTreeMap<String, Vec<String>> {
+	vars
+		.filter_map(|(name, value)| {
+			let name = name.strip_prefix("CARGO_CFG_")?.to_lowercase();
+			let values = value
+				.split(',')
+				.filter(|value| !value.is_empty())
+				.map(str::to_owned)
+				.collect();
+			Some((name, values))
+		})
+		.collect()
+}
+
+#[cfg(test)]
+mod test {
+	use pretty_assertions::assert_eq;
+
+	use super::*;
+
+	#[test]
+	fn no_features() {
+		assert_eq!(get_features(None), Vec::<String>::new());
+		assert_eq!(get_features(Some(String::new())), Vec::<String>::new());
+	}
+
+	#[test]
+	fn normalized_features() {
+		assert_eq!(
+			get_features(Some("sse2,fxsr,,sse,sse2".to_string())),
+			vec!["fxsr".to_string(), "sse".to_string(), "sse2".to_string()]
+		);
+	}
+
+	#[test]
+	fn cfg() {
+		let vars = [
+			("CARGO_CFG_DEBUG_ASSERTIONS", ""),
+			("CARGO_CFG_PANIC", "unwind"),
+			("CARGO_CFG_TARGET_HAS_ATOMIC", "16,32,64,8,ptr"),
+			("CARGO_PKG_NAME", "sample"),
+		];
+		assert_eq!(
+			cfg_from_vars(
+				vars
+					.into_iter()
+					.map(|(name, value)| (name.to_string(), value.to_string()))
+			),
+			BTreeMap::from([
+				("debug_assertions".to_string(), vec![]),
+				("panic".to_string(), vec!["unwind".to_string()]),
+				(
+					"target_has_atomic".to_string(),
+					["16", "32", "64", "8", "ptr"].map(str::to_string).to_vec()
+				),
+			])
+		);
+	}
+}
@@ -7,10 +7,41 @@ This crate is used to collect build info for consumption by the `build-info` cra
 build_info_build::build_script();
 ```
 
+# Exporting
+The build information can also be written to files, e.g., for deployment tooling:
+
+```rust,no_run
+build_info_build::build_script()
+	.write_json("build-info.json") // written to `OUT_DIR`
+	.write_toml("build-info.toml")
+	.write_yaml("build-info.yaml");
+```
+
+All three formats share the layout of the serialized `BuildInfo`, which is described by the JSON Schema in
+`schema/build-info.schema.json` and only changes together with the version of `build-info-common`. Use
+`export_to_target_dir(true)` to write the files into the directory of the current profile (e.g., `target/debug`)
+instead.
+
+# License policy
+The licenses of all dependencies can be checked against a `LicensePolicy`, which emits a warning for each dependency
+whose license is not allowed (or fails the build, if requested):
+
+```rust,no_run
+use build_info_build::{Copyleft, LicensePolicy};
+
+build_info_build::build_script().license_policy(
+	LicensePolicy::new()
+		.deny(["AGPL-3.0-only", "AGPL-3.0-or-later"])
+		.copyleft(Copyleft::BuildOnly) // e.g., GPL-licensed code generators are fine
+		.fail_on_violation(true),
+);
+```
+
 # Features
 The `build-info-build` crate has the following features:
 
 - `git` (enabled by default): Enables git support. A git repository will only be detected if this feature is available.
+- `sbom`: Enables `write_sbom`, which writes a CycloneDX 1.5 software bill of materials for the crate and its collected dependencies, and `embed_sbom`, which makes it available to `build_info::build_info!(fn sbom_json = sbom)`. The same information can be written as an SPDX 2.3 document using `write_spdx_json` and `write_spdx_tag_value`.
 */
 
 #![forbid(unsafe_code)]
@@ -21,7 +52,7 @@ pub use build_info_common::{
 };
 
 mod build_script_options;
-pub use build_script_options::{BuildScriptOptions, DependencyDepth};
+pub use build_script_options::{BuildScriptOptions, Copyleft, DependencyDepth, LicensePolicy};
 
 /// Call this function in your `build.rs` script to generate the data consumed by the `build_info` crate.
 /// Additional customization options are available by manipulating the return type.
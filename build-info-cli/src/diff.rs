@@ -0,0 +1,147 @@
+use std::collections::{BTreeMap, BTreeSet};
+
+use build_info_common::{BuildInfo, CrateInfo, VersionControl};
+
+/// A single difference between two builds
+#[derive(Clone, Eq, PartialEq, Debug)]
+pub struct Change {
+	pub what: String,
+	pub old: Option<String>,
+	pub new: Option<String>,
+}
+
+impl Change {
+	/// Formats the change as `what: old -> new`, optionally colored using ANSI escape sequences.
+	pub fn display(&self, color: bool) -> String {
+		let (red, green, reset) = if color {
+			("\x1b[31m", "\x1b[32m", "\x1b[0m")
+		} else {
+			("", "", "")
+		};
+		match (&self.old, &self.new) {
+			(Some(old), Some(new)) => format!("{}: {red}{old}{reset} -> {green}{new}{reset}", self.what),
+			(Some(old), None) => format!("{}: {red}removed ({old}){reset}", self.what),
+			(None, Some(new)) => format!("{}: {green}added ({new}){reset}", self.what),
+			(None, None) => self.what.clone(),
+		}
+	}
+}
+
+/// Compares two builds, reporting changes to the crate itself, its commit, the compiler and the dependency versions.
+pub fn diff(old: &BuildInfo, new: &BuildInfo) -> Vec<Change> {
+	let mut changes = Vec::new();
+	let mut compare = |what: &str, old: Option<String>, new: Option<String>| {
+		if old != new {
+			changes.push(Change {
+				what: what.to_string(),
+				old,
+				new,
+			});
+		}
+	};
+
+	compare(
+		"crate",
+		Some(old.crate_info.to_string()),
+		Some(new.crate_info.to_string()),
+	);
+	compare("profile", Some(old.profile.clone()), Some(new.profile.clone()));
+	compare("target", Some(old.target.to_string()), Some(new.target.to_string()));
+	compare("commit", commit(old), commit(new));
+	compare(
+		"compiler",
+		Some(old.compiler.to_string()),
+		Some(new.compiler.to_string()),
+	);
+
+	let old_dependencies = dependencies(&old.crate_info);
+	let new_dependencies = dependencies(&new.crate_info);
+	let names: BTreeSet<&str> = old_dependencies
+		.keys()
+		.chain(new_dependencies.keys())
+		.copied()
+		.collect();
+	for name in names {
+		let versions = |dependencies: &BTreeMap<&str, BTreeSet<String>>| {
+			dependencies
+				.get(name)
+				.map(|versions| versions.iter().cloned().collect::<Vec<_>>().join(", "))
+		};
+		compare(
+			&format!("dependency {name}"),
+			versions(&old_dependencies),
+			versions(&new_dependencies),
+		);
+	}
+
+	changes
+}
+
+fn commit(build_info: &BuildInfo) -> Option<String> {
+	build_info
+		.version_control
+		.as_ref()
+		.map(|version_control| match version_control {
+			VersionControl::Git(git) if git.dirty => format!("{} (dirty)", git.commit_id),
+			VersionControl::Git(git) => git.commit_id.clone(),
+		})
+}
+
+/// Collects the versions of all (transitive) dependencies by name, since a crate may be present in multiple versions.
+fn dependencies(crate_info: &CrateInfo) -> BTreeMap<&str, BTreeSet<String>> {
+	fn collect<'a>(crate_info: &'a CrateInfo, result: &mut BTreeMap<&'a str, BTreeSet<String>>) {
+		for dependency in &crate_info.dependencies {
+			result
+				.entry(&dependency.name)
+				.or_default()
+				.insert(dependency.version.to_string());
+			collect(dependency, result);
+		}
+	}
+
+	let mut result = BTreeMap::new();
+	collect(crate_info, &mut result);
+	result
+}
+
+#[cfg(test)]
+mod test {
+	use build_info_common::semver::Version;
+	use build_info_fixtures::build_info;
+	use pretty_assertions::assert_eq;
+
+	use super::*;
+
+	fn dependency(name: &str, version: &str) -> CrateInfo {
+		let mut crate_info = build_info().crate_info;
+		crate_info.name = name.to_string();
+		crate_info.version = Version::parse(version).unwrap();
+		crate_info
+	}
+
+	#[test]
+	fn changes() {
+		let mut old = build_info();
+		old.crate_info.dependencies = vec![dependency("a", "1.0.0"), dependency("b", "0.1.0")];
+		assert_eq!(diff(&old, &old), []);
+
+		let mut new = old.clone();
+		new.compiler.version = Version::new(1, 89, 0);
+		new.crate_info.dependencies = vec![dependency("a", "1.1.0"), dependency("c", "2.0.0")];
+		if let Some(VersionControl::Git(git)) = &mut new.version_control {
+			git.dirty = true;
+		}
+
+		let changes: Vec<String> = diff(&old, &new).iter().map(|change| change.display(false)).collect();
+		assert_eq!(
+			changes,
+			[
+				"commit: 0123456789abcdef -> 0123456789abcdef (dirty)",
+				"compiler: rustc 1.88.0 -> rustc 1.89.0",
+				"dependency a: 1.0.0 -> 1.1.0",
+				"dependency b: removed (0.1.0)",
+				"dependency c: added (2.0.0)",
+			]
+		);
+	}
+}
@@ -0,0 +1,8 @@
+/*!
+Library interface of the `build-info` command, for tools that process the build information embedded into binaries by
+`build_info::embed!()` themselves.
+*/
+
+#![forbid(unsafe_code)]
+
+pub mod provenance;
@@ -0,0 +1,239 @@
+/*!
+Reads the build information that `build_info::embed!()` placed into a binary.
+
+```text
+build-info inspect [--json | --format <TEMPLATE>] <BINARY>
+build-info diff <OLD> <NEW>
+build-info provenance [--builder-id <ID>] [--lockfile <PATH>] [--output <PATH>] <BINARY>
+```
+*/
+
+#![forbid(unsafe_code)]
+
+use std::{
+	io::{IsTerminal, stdout},
+	path::{Path, PathBuf},
+	process::ExitCode,
+};
+
+use anyhow::{Context, Result, bail};
+use build_info_cli::provenance;
+use build_info_common::{BuildInfo, CrateInfo};
+
+mod diff;
+mod template;
+
+const USAGE: &str = "\
+Usage:
+	build-info inspect [--json | --format <TEMPLATE>] <BINARY>
+	build-info diff <OLD> <NEW>
+	build-info provenance [--builder-id <ID>] [--lockfile <PATH>] [--output <PATH>] <BINARY>
+
+Commands:
+	inspect     Prints the build information embedded into BINARY
+	diff        Compares the build information embedded into two binaries
+	provenance  Writes an in-toto statement with SLSA provenance for BINARY to BINARY.intoto.json
+
+Options:
+	--json               Prints the build information as JSON
+	--format <TEMPLATE>  Formats the build information like `build_info::format!`, e.g.,
+	                     '\"{} {}\", $.crate_info.name, $.crate_info.version'
+	--builder-id <ID>    Identifies the builder (detected on GitHub Actions and GitLab CI)
+	--lockfile <PATH>    The Cargo.lock to take the dependencies from (searched next to BINARY by default)
+	--output <PATH>      Where to write the statement instead of next to BINARY";
+
+enum Output {
+	Text,
+	Json,
+	Template(String),
+}
+
+fn main() -> ExitCode {
+	match run(std::env::args().skip(1).collect()) {
+		Ok(code) => code,
+		Err(err) => {
+			eprintln!("Error: {err:#}");
+			ExitCode::from(2)
+		}
+	}
+}
+
+fn run(args: Vec<String>) -> Result<ExitCode> {
+	let mut args = args.into_iter();
+	match args.next().as_deref() {
+		Some("inspect") => {
+			let mut output = Output::Text;
+			let mut binary = None;
+			while let Some(arg) = args.next() {
+				match arg.as_str() {
+					"--json" => output = Output::Json,
+					"--format" => output = Output::Template(args.next().context("`--format` expects a template")?),
+					_ if arg.starts_with("--") => bail!("Unknown option `{arg}`\n\n{USAGE}"),
+					_ if binary.is_none() => binary = Some(arg),
+					_ => bail!("Unexpected argument `{arg}`\n\n{USAGE}"),
+				}
+			}
+			let binary = binary.with_context(|| format!("No binary given\n\n{USAGE}"))?;
+			inspect(Path::new(&binary), &output)?;
+			Ok(ExitCode::SUCCESS)
+		}
+		Some("diff") => {
+			let (Some(old), Some(new), None) = (args.next(), args.next(), args.next()) else {
+				bail!("`diff` expects exactly two binaries\n\n{USAGE}");
+			};
+			let changes = diff::diff(&read_single(Path::new(&old))?, &read_single(Path::new(&new))?);
+			let color = stdout().is_terminal();
+			for change in &changes {
+				println!("{}", change.display(color));
+			}
+			// Like `diff(1)`, the exit code signals whether there were any differences
+			Ok(if changes.is_empty() {
+				ExitCode::SUCCESS
+			} else {
+				ExitCode::from(1)
+			})
+		}
+		Some("provenance") => {
+			let (mut builder_id, mut lockfile, mut output, mut binary) = (None, None, None, None);
+			while let Some(arg) = args.next() {
+				let mut value = |option: &str| args.next().with_context(|| format!("`{option}` expects a value"));
+				match arg.as_str() {
+					"--builder-id" => builder_id = Some(value(&arg)?),
+					"--lockfile" => lockfile = Some(PathBuf::from(value(&arg)?)),
+					"--output" => output = Some(PathBuf::from(value(&arg)?)),
+					_ if arg.starts_with("--") => bail!("Unknown option `{arg}`\n\n{USAGE}"),
+					_ if binary.is_none() => binary = Some(PathBuf::from(arg)),
+					_ => bail!("Unexpected argument `{arg}`\n\n{USAGE}"),
+				}
+			}
+			let binary = binary.with_context(|| format!("No binary given\n\n{USAGE}"))?;
+			let output = output.unwrap_or_else(|| {
+				let mut path = binary.clone().into_os_string();
+				path.push(".intoto.json");
+				path.into()
+			});
+			provenance(&binary, builder_id, lockfile, &output)?;
+			Ok(ExitCode::SUCCESS)
+		}
+		Some("help" | "--help" | "-h") => {
+			println!("{USAGE}");
+			Ok(ExitCode::SUCCESS)
+		}
+		Some(command) => bail!("Unknown command `{command}`\n\n{USAGE}"),
+		None => bail!("No command given\n\n{USAGE}"),
+	}
+}
+
+/// Reads all build information embedded into `path`
+fn read(path: &Path) -> Result<Vec<BuildInfo>> {
+	let data = std::fs::read(path).with_context(|| format!("Could not read {}", path.display()))?;
+	build_info_common::embedded::read(&data)
+		.with_context(|| format!("Could not extract build information from {}", path.display()))
+}
+
+/// Reads the build information embedded into `path`, which must contain exactly one record
+fn read_single(path: &Path) -> Result<BuildInfo> {
+	let mut build_infos = read(path)?;
+	if build_infos.len() > 1 {
+		bail!(
+			"{} contains {} records of build information, but only binaries with a single record can be compared",
+			path.display(),
+			build_infos.len()
+		);
+	}
+	Ok(build_infos.remove(0))
+}
+
+fn inspect(path: &Path, output: &Output) -> Result<()> {
+	let build_infos = read(path)?;
+	match output {
+		Output::Text => {
+			for (i, build_info) in build_infos.iter().enumerate() {
+				if i > 0 {
+					println!();
+				}
+				print_text(build_info);
+			}
+		}
+		Output::Json => {
+			// A binary usually contains a single record, which is then printed as an object instead of an array
+			let json = match build_infos.as_slice() {
+				[build_info] => serde_json::to_string_pretty(build_info)?,
+				_ => serde_json::to_string_pretty(&build_infos)?,
+			};
+			println!("{json}");
+		}
+		Output::Template(template) => {
+			for build_info in &build_infos {
+				println!("{}", template::render(template, build_info)?);
+			}
+		}
+	}
+	Ok(())
+}
+
+fn provenance(binary: &Path, builder_id: Option<String>, lockfile: Option<PathBuf>, output: &Path) -> Result<()> {
+	let data = std::fs::read(binary).with_context(|| format!("Could not read {}", binary.display()))?;
+
+	let mut builder = provenance::Builder::from_env(|name| std::env::var(name).ok());
+	if let Some(id) = builder_id {
+		builder = Some(match builder {
+			Some(builder) => provenance::Builder { id, ..builder },
+			None => provenance::Builder {
+				id,
+				invocation_id: None,
+				repository: None,
+			},
+		});
+	}
+	let builder = builder.context("Could not detect the CI system, please pass `--builder-id`")?;
+
+	// The binary is usually found in `target/<profile>`, so its ancestors will contain the workspace root
+	let lockfile = match lockfile {
+		Some(lockfile) => lockfile,
+		None => std::path::absolute(binary)?
+			.ancestors()
+			.map(|dir| dir.join("Cargo.lock"))
+			.find(|lockfile| lockfile.is_file())
+			.context("Could not find Cargo.lock, please pass `--lockfile`")?,
+	};
+	let lockfile =
+		std::fs::read_to_string(&lockfile).with_context(|| format!("Could not read {}", lockfile.display()))?;
+
+	let subject = binary
+		.file_name()
+		.with_context(|| format!("{} is not a file", binary.display()))?
+		.to_string_lossy();
+	let statement = provenance::generate(&subject, &data, &lockfile, &builder)
+		.with_context(|| format!("Could not generate the provenance of {}", binary.display()))?;
+	std::fs::write(output, serde_json::to_string_pretty(&statement)? + "\n")
+		.with_context(|| format!("Could not write {}", output.display()))
+}
+
+fn print_text(build_info: &BuildInfo) {
+	println!("{} ({} build)", build_info.crate_info, build_info.profile);
+	println!("  built:        {}", build_info.timestamp);
+	println!("  optimization: {}", build_info.optimization_level);
+	println!("  target:       {}", build_info.target);
+	println!("  compiler:     {}", build_info.compiler);
+	if let Some(version_control) = &build_info.version_control {
+		println!("  commit:       {version_control}");
+	}
+	if let Some(license) = &build_info.crate_info.license {
+		println!("  license:      {license}");
+	}
+	if !build_info.crate_info.enabled_features.is_empty() {
+		println!("  features:     {}", build_info.crate_info.enabled_features.join(", "));
+	}
+	if !build_info.crate_info.dependencies.is_empty() {
+		println!("  dependencies:");
+		print_dependencies(&build_info.crate_info.dependencies, 2);
+	}
+}
+
+fn print_dependencies(dependencies: &[CrateInfo], depth: usize) {
+	for dependency in dependencies {
+		println!("{:indent$}{dependency}", "", indent = 2 * depth);
+		print_dependencies(&dependency.dependencies, depth + 1);
+	}
+}
@@ -0,0 +1,376 @@
+//! Generates [in-toto](https://in-toto.io/) statements with a [SLSA v1](https://slsa.dev/spec/v1.0/provenance)
+//! provenance predicate for binaries that contain embedded build information.
+//!
+//! The `externalParameters` of the build definition name the package, its version, profile, target and enabled
+//! features; the `internalParameters` describe the compiler and optimization level. The resolved dependencies are the
+//! source commit and the dependencies listed in the embedded build information that were downloaded from a registry or
+//! a git repository, with their checksums from `Cargo.lock`. Since dependencies are only embedded if the build script
+//! collected them, the statement notes when they are missing.
+
+use std::collections::BTreeSet;
+
+use anyhow::{Context, bail};
+use build_info_common::{BuildInfo, CrateInfo, VersionControl, chrono::SecondsFormat};
+use serde_json::{Value, json};
+use sha2::{Digest, Sha256};
+
+pub const BUILD_TYPE: &str = "https://github.com/danielschemmel/build-info/cargo@v1";
+
+/// The system that ran the build
+#[derive(Clone, Eq, PartialEq, Debug)]
+pub struct Builder {
+	/// Identifies the builder, i.e., the CI pipeline and its runners
+	pub id: String,
+	/// Identifies this particular run of the builder, e.g., the URL of a CI job
+	pub invocation_id: Option<String>,
+	/// The repository that the source was checked out from, as a `git+https://...` URI
+	pub repository: Option<String>,
+}
+
+impl Builder {
+	/// Detects GitHub Actions and GitLab CI from their environment variables, which are looked up using `var`.
+	pub fn from_env(var: impl Fn(&str) -> Option<String>) -> Option<Builder> {
+		let var = |name: &str| var(name).filter(|value| !value.is_empty());
+		if var("GITHUB_ACTIONS").as_deref() == Some("true") {
+			let server = var("GITHUB_SERVER_URL")?;
+			let repository = var("GITHUB_REPOSITORY")?;
+			Some(Builder {
+				// e.g., `https://github.com/owner/repo/.github/workflows/release.yml@refs/heads/main`
+				id: format!("{server}/{}", var("GITHUB_WORKFLOW_REF")?),
+				invocation_id: var("GITHUB_RUN_ID").map(|run| {
+					let attempt = var("GITHUB_RUN_ATTEMPT").unwrap_or_else(|| "1".to_string());
+					format!("{server}/{repository}/actions/runs/{run}/attempts/{attempt}")
+				}),
+				repository: Some(format!("git+{server}/{repository}")),
+			})
+		} else if var("GITLAB_CI").as_deref() == Some("true") {
+			Some(Builder {
+				id: format!(
+					"{}/{}/-/runners/{}",
+					var("CI_SERVER_URL")?,
+					var("CI_PROJECT_PATH")?,
+					var("CI_RUNNER_ID")?
+				),
+				invocation_id: var("CI_JOB_URL"),
+				repository: var("CI_PROJECT_URL").map(|url| format!("git+{url}")),
+			})
+		} else {
+			None
+		}
+	}
+}
+
+/// Lists the (transitive) dependencies of `crate_info` that were not built from local sources as SLSA resource
+/// descriptors, taking their checksums from `Cargo.lock`.
+pub fn locked_dependencies(lockfile: &str, crate_info: &CrateInfo) -> Result<Vec<Value>, toml::de::Error> {
+	fn collect(crate_info: &CrateInfo, crates: &mut BTreeSet<(String, String)>) {
+		for dependency in &crate_info.dependencies {
+			crates.insert((dependency.name.clone(), dependency.version.to_string()));
+			collect(dependency, crates);
+		}
+	}
+	let mut crates = BTreeSet::new();
+	collect(crate_info, &mut crates);
+
+	let lockfile: toml::Table = lockfile.parse()?;
+	let packages = lockfile.get("package").and_then(toml::Value::as_array);
+	let dependencies = packages.into_iter().flatten().filter_map(|package| {
+		let field = |key| package.get(key).and_then(toml::Value::as_str);
+		let (name, version) = (field("name")?, field("version")?);
+		if !crates.contains(&(name.to_string(), version.to_string())) {
+			return None;
+		}
+		// Packages from git repositories have no checksum, but their source is pinned to a commit
+		let git_commit = field("source")
+			.and_then(|source| source.strip_prefix("git+"))
+			.and_then(|source| source.rsplit_once('#'));
+		let digest = match (field("checksum"), git_commit) {
+			(Some(checksum), _) => json!({ "sha256": checksum }),
+			(None, Some((_, commit))) => json!({ "gitCommit": commit }),
+			(None, None) => return None,
+		};
+		Some(json!({ "name": name, "uri": format!("pkg:cargo/{name}@{version}"), "digest": digest }))
+	});
+	Ok(dependencies.collect())
+}
+
+/// Generates the statement for the binary `subject` with the contents `binary`, which contains exactly one record of
+/// embedded build information, using the checksums from the contents of `Cargo.lock`.
+pub fn generate(subject: &str, binary: &[u8], lockfile: &str, builder: &Builder) -> anyhow::Result<Value> {
+	let build_infos = build_info_common::embedded::read(binary).context("Could not extract build information")?;
+	if build_infos.len() > 1 {
+		bail!(
+			"{subject} contains {} records of build information, but provenance can only be generated for binaries with \
+			 a single record",
+			build_infos.len()
+		);
+	}
+	let build_info = &build_infos[0];
+	let digest: String = Sha256::digest(binary)
+		.iter()
+		.map(|byte| format!("{byte:02x}"))
+		.collect();
+	let dependencies =
+		locked_dependencies(lockfile, &build_info.crate_info).context("Could not read the dependencies from Cargo.lock")?;
+	Ok(statement(build_info, subject, &digest, dependencies, builder))
+}
+
+/// Generates the statement for the binary `subject` with the SHA-256 hash `digest`.
+pub fn statement(
+	build_info: &BuildInfo,
+	subject: &str,
+	digest: &str,
+	dependencies: Vec<Value>,
+	builder: &Builder,
+) -> Value {
+	let mut resolved_dependencies = Vec::new();
+	if let Some(VersionControl::Git(git)) = &build_info.version_control {
+		let mut source = json!({
+			"digest": { "gitCommit": git.commit_id },
+			"annotations": { "dirty": git.dirty },
+		});
+		if let Some(repository) = &builder.repository {
+			source["uri"] = json!(repository);
+		}
+		if let Some(branch) = &git.branch {
+			source["annotations"]["branch"] = json!(branch);
+		}
+		resolved_dependencies.push(source);
+	}
+	resolved_dependencies.extend(dependencies);
+
+	let mut metadata = json!({
+		"startedOn": build_info.timestamp.to_rfc3339_opts(SecondsFormat::Secs, true),
+	});
+	if let Some(invocation_id) = &builder.invocation_id {
+		metadata["invocationId"] = json!(invocation_id);
+	}
+
+	let crate_info = &build_info.crate_info;
+	let mut internal_parameters = json!({
+		"compiler": build_info.compiler,
+		"optimizationLevel": build_info.optimization_level,
+	});
+	if crate_info.dependencies.is_empty() {
+		internal_parameters["note"] = json!(
+			"The embedded build information lists no dependencies, so resolvedDependencies may be incomplete. Collect \
+			 them in the build script, e.g., using `collect_runtime_dependencies(DependencyDepth::Full)`."
+		);
+	}
+
+	json!({
+		"_type": "https://in-toto.io/Statement/v1",
+		"subject": [{ "name": subject, "digest": { "sha256": digest } }],
+		"predicateType": "https://slsa.dev/provenance/v1",
+		"predicate": {
+			"buildDefinition": {
+				"buildType": BUILD_TYPE,
+				"externalParameters": {
+					"package": crate_info.name,
+					"version": crate_info.version.to_string(),
+					"profile": build_info.profile,
+					"target": build_info.target.triple,
+					"features": crate_info.enabled_features,
+				},
+				"internalParameters": internal_parameters,
+				"resolvedDependencies": resolved_dependencies,
+			},
+			"runDetails": {
+				"builder": { "id": builder.id },
+				"metadata": metadata,
+			},
+		},
+	})
+}
+
+#[cfg(test)]
+mod test {
+	use std::collections::HashMap;
+
+	use build_info_fixtures::crate_info;
+	use pretty_assertions::assert_eq;
+
+	use super::*;
+
+	const LOCKFILE: &str = r#"
+version = 4
+
+[[package]]
+name = "sample"
+version = "1.2.3"
+dependencies = ["anyhow", "forked"]
+
+[[package]]
+name = "anyhow"
+version = "1.0.98"
+source = "registry+https://github.com/rust-lang/crates.io-index"
+checksum = "e16d2d3311acee920a9eb8d33b8cbc1787ce4a264e85f964c2404b969bdcd487"
+
+[[package]]
+name = "forked"
+version = "0.1.0"
+source = "git+https://example.com/forked.git?branch=fix#4f1bc2a"
+
+[[package]]
+name = "build-only"
+version = "2.0.0"
+source = "registry+https://github.com/rust-lang/crates.io-index"
+checksum = "0000000000000000000000000000000000000000000000000000000000000000"
+"#;
+
+	/// The build information of `sample`, which depends on `anyhow` and `forked`, but not on `build-only`
+	fn build_info() -> BuildInfo {
+		let mut build_info = build_info_fixtures::build_info();
+		let mut anyhow = crate_info("anyhow", Some("MIT OR Apache-2.0"), Vec::new());
+		anyhow.version = "1.0.98".parse().unwrap();
+		let mut forked = crate_info("forked", None, Vec::new());
+		forked.version = "0.1.0".parse().unwrap();
+		build_info.crate_info.dependencies = vec![anyhow, forked];
+		build_info
+	}
+
+	fn github() -> Builder {
+		let env = HashMap::from([
+			("GITHUB_ACTIONS", "true"),
+			("GITHUB_SERVER_URL", "https://github.com"),
+			("GITHUB_REPOSITORY", "owner/sample"),
+			(
+				"GITHUB_WORKFLOW_REF",
+				"owner/sample/.github/workflows/release.yml@refs/tags/v1.2.3",
+			),
+			("GITHUB_RUN_ID", "42"),
+			("GITHUB_RUN_ATTEMPT", "2"),
+		]);
+		Builder::from_env(|name| env.get(name).map(|value| value.to_string())).unwrap()
+	}
+
+	#[test]
+	fn builders() {
+		assert_eq!(
+			github(),
+			Builder {
+				id: "https://github.com/owner/sample/.github/workflows/release.yml@refs/tags/v1.2.3".to_string(),
+				invocation_id: Some("https://github.com/owner/sample/actions/runs/42/attempts/2".to_string()),
+				repository: Some("git+https://github.com/owner/sample".to_string()),
+			}
+		);
+
+		let env = HashMap::from([
+			("GITLAB_CI", "true"),
+			("CI_SERVER_URL", "https://gitlab.com"),
+			("CI_PROJECT_PATH", "group/sample"),
+			("CI_PROJECT_URL", "https://gitlab.com/group/sample"),
+			("CI_RUNNER_ID", "7"),
+			("CI_JOB_URL", "https://gitlab.com/group/sample/-/jobs/99"),
+		]);
+		assert_eq!(
+			Builder::from_env(|name| env.get(name).map(|value| value.to_string())),
+			Some(Builder {
+				id: "https://gitlab.com/group/sample/-/runners/7".to_string(),
+				invocation_id: Some("https://gitlab.com/group/sample/-/jobs/99".to_string()),
+				repository: Some("git+https://gitlab.com/group/sample".to_string()),
+			})
+		);
+
+		assert_eq!(Builder::from_env(|_| None), None);
+	}
+
+	#[test]
+	fn dependencies() {
+		assert_eq!(
+			locked_dependencies(LOCKFILE, &build_info().crate_info).unwrap(),
+			[
+				json!({
+					"name": "anyhow",
+					"uri": "pkg:cargo/anyhow@1.0.98",
+					"digest": { "sha256": "e16d2d3311acee920a9eb8d33b8cbc1787ce4a264e85f964c2404b969bdcd487" },
+				}),
+				json!({ "name": "forked", "uri": "pkg:cargo/forked@0.1.0", "digest": { "gitCommit": "4f1bc2a" } }),
+			]
+		);
+		assert_eq!(
+			locked_dependencies(LOCKFILE, &build_info_fixtures::build_info().crate_info).unwrap(),
+			Vec::<Value>::new()
+		);
+		assert!(locked_dependencies("[[package]", &build_info().crate_info).is_err());
+	}
+
+	#[test]
+	fn provenance() {
+		let build_info = build_info();
+		let statement = statement(
+			&build_info,
+			"sample",
+			"abcdef",
+			locked_dependencies(LOCKFILE, &build_info.crate_info).unwrap(),
+			&github(),
+		);
+
+		assert_eq!(statement["_type"], "https://in-toto.io/Statement/v1");
+		assert_eq!(
+			statement["subject"],
+			json!([{ "name": "sample", "digest": { "sha256": "abcdef" } }])
+		);
+		let definition = &statement["predicate"]["buildDefinition"];
+		assert_eq!(
+			definition["externalParameters"],
+			json!({
+				"package": "sample",
+				"version": "1.2.3",
+				"profile": "release",
+				"target": "x86_64-unknown-linux-gnu",
+				"features": [],
+			})
+		);
+		assert_eq!(definition["internalParameters"]["compiler"]["version"], "1.88.0");
+		assert_eq!(definition["internalParameters"].get("note"), None);
+		assert_eq!(
+			definition["resolvedDependencies"][0],
+			json!({
+				"uri": "git+https://github.com/owner/sample",
+				"digest": { "gitCommit": "0123456789abcdef" },
+				"annotations": { "dirty": false, "branch": "main" },
+			})
+		);
+		assert_eq!(definition["resolvedDependencies"].as_array().unwrap().len(), 3);
+		assert_eq!(
+			statement["predicate"]["runDetails"],
+			json!({
+				"builder": { "id": "https://github.com/owner/sample/.github/workflows/release.yml@refs/tags/v1.2.3" },
+				"metadata": {
+					"invocationId": "https://github.com/owner/sample/actions/runs/42/attempts/2",
+					"startedOn": "2020-05-28T20:29:40Z",
+				},
+			})
+		);
+	}
+
+	#[test]
+	fn missing_dependencies() {
+		let build_info = build_info_fixtures::build_info();
+		let statement = statement(&build_info, "sample", "abcdef", Vec::new(), &github());
+		let definition = &statement["predicate"]["buildDefinition"];
+		assert!(
+			definition["internalParameters"]["note"]
+				.as_str()
+				.unwrap()
+				.starts_with("The embedded build information lists no dependencies")
+		);
+		assert_eq!(definition["resolvedDependencies"].as_array().unwrap().len(), 1);
+	}
+
+	#[test]
+	fn multiple_records() {
+		let record = build_info_common::embedded::encode(&build_info());
+		let statement = generate("sample", &record, LOCKFILE, &github()).unwrap();
+		assert_eq!(statement["subject"][0]["name"], "sample");
+
+		let binary = [record.clone(), record].concat();
+		let err = generate("sample", &binary, LOCKFILE, &github()).unwrap_err();
+		assert_eq!(
+			err.to_string(),
+			"sample contains 2 records of build information, but provenance can only be generated for binaries with a \
+			 single record"
+		);
+	}
+}
@@ -0,0 +1,57 @@
+use anyhow::{Context, Result, anyhow};
+use build_info_common::BuildInfo;
+use build_info_format::{format_to_string, syntax::Syntax, with_build_info};
+
+/// Renders a template such as `"{} v{}", $.crate_info.name, $.crate_info.version`.
+///
+/// Templates are the arguments of `build_info::format!`, which are evaluated just like the macro does at compile time,
+/// except that `$` refers to the build information that was embedded into the binary.
+pub fn render(template: &str, build_info: &BuildInfo) -> Result<String> {
+	let syntax = syn::parse_str::<Syntax>(template).context("Could not parse the template")?;
+	// The spans of the error refer to the template, so only the message and its notes are reported
+	with_build_info(build_info.clone(), || format_to_string(&syntax)).map_err(|err| anyhow!("{err}"))
+}
+
+#[cfg(test)]
+mod test {
+	use build_info_fixtures::build_info;
+	use pretty_assertions::assert_eq;
+
+	use super::*;
+
+	#[test]
+	fn templates() {
+		let build_info = build_info();
+		let render = |template| render(template, &build_info).unwrap();
+		assert_eq!(
+			render(r#""{} v{}", $.crate_info.name, $.crate_info.version"#),
+			"sample v1.2.3"
+		);
+		assert_eq!(render(r#""{}", $.version_control?.git()?.commit_short_id"#), "0123456");
+		assert_eq!(
+			render(r#""{authors:list} ({count})", authors = $.crate_info.authors, count = $.crate_info.authors.len()"#),
+			"Jane Doe and John Doe (2)"
+		);
+		assert_eq!(
+			render(r#""[{}] {{{}}}", $.crate_info.license ?? "unlicensed", $.target.cpu.pointer_width"#),
+			"[unlicensed] {64}"
+		);
+		assert_eq!(render(""), build_info.to_string());
+	}
+
+	#[test]
+	fn errors() {
+		let build_info = build_info();
+		let error = |template| render(template, &build_info).unwrap_err().to_string();
+		assert_eq!(
+			error(r#""{}", $.crate_info.nme"#),
+			"The field `nme` does not exist for objects of type build_info::CrateInfo\n  = note: Did you mean `name`?"
+		);
+		assert_eq!(
+			error(r#""{} {}", $.crate_info.name"#),
+			"Invalid implicit reference to positional argument 1 (1 arguments were given)\n  = note: Positional arguments \
+			 are zero-based"
+		);
+		assert_eq!(error("{crate_info.name}"), "Could not parse the template");
+	}
+}
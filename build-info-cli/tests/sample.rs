@@ -0,0 +1,85 @@
+//! Runs `build-info` on the `sample` binary, which embeds its build information using `build_info::embed!()`.
+
+use std::{
+	path::{Path, PathBuf},
+	process::{Command, Output},
+	sync::OnceLock,
+};
+
+use pretty_assertions::assert_eq;
+use serde_json::Value;
+
+/// Builds `sample` (which is usually up to date already) and returns the path of the executable
+fn sample() -> &'static Path {
+	static SAMPLE: OnceLock<PathBuf> = OnceLock::new();
+	SAMPLE.get_or_init(|| {
+		let output = Command::new(env!("CARGO"))
+			.args(["build", "--package", "sample", "--message-format=json"])
+			.current_dir(env!("CARGO_MANIFEST_DIR"))
+			.output()
+			.unwrap();
+		assert!(
+			output.status.success(),
+			"Could not build sample: {}",
+			String::from_utf8_lossy(&output.stderr)
+		);
+		String::from_utf8(output.stdout)
+			.unwrap()
+			.lines()
+			.filter_map(|line| serde_json::from_str::<Value>(line).ok())
+			.filter(|message| message["reason"] == "compiler-artifact" && message["target"]["name"] == "sample")
+			.find_map(|message| message["executable"].as_str().map(PathBuf::from))
+			.expect("Cargo did not report the sample executable")
+	})
+}
+
+fn build_info(args: &[&str]) -> Output {
+	Command::new(env!("CARGO_BIN_EXE_build-info"))
+		.args(args)
+		.output()
+		.unwrap()
+}
+
+fn stdout(output: &Output) -> String {
+	assert!(
+		output.status.success(),
+		"build-info failed: {}",
+		String::from_utf8_lossy(&output.stderr)
+	);
+	String::from_utf8(output.stdout.clone()).unwrap()
+}
+
+#[test]
+fn inspect() {
+	let sample = sample().to_str().unwrap();
+	// `sample` is versioned together with build-info-cli
+	let version = env!("CARGO_PKG_VERSION");
+
+	let text = stdout(&build_info(&["inspect", sample]));
+	assert!(text.starts_with(&format!("sample v{version} (")), "{text}");
+	assert!(text.contains("\n  compiler:     rustc "), "{text}");
+
+	let json: Value = serde_json::from_str(&stdout(&build_info(&["inspect", "--json", sample]))).unwrap();
+	assert_eq!(json["crate_info"]["name"], "sample");
+	assert_eq!(json["crate_info"]["version"], version);
+
+	let formatted = stdout(&build_info(&[
+		"inspect",
+		"--format",
+		r#""{} {}", $.crate_info.name, $.crate_info.version"#,
+		sample,
+	]));
+	assert_eq!(formatted, format!("sample {version}\n"));
+}
+
+#[test]
+fn diff() {
+	let sample = sample().to_str().unwrap();
+	let output = build_info(&["diff", sample, sample]);
+	assert_eq!(stdout(&output), "");
+	assert_eq!(output.status.code(), Some(0));
+
+	let output = build_info(&["diff", sample, env!("CARGO_BIN_EXE_build-info")]);
+	assert_eq!(output.status.code(), Some(2));
+	assert!(String::from_utf8_lossy(&output.stderr).contains("Could not extract build information"));
+}
@@ -0,0 +1,542 @@
+//! Borrowed views of the build information, which can be constructed in `const` contexts.
+//!
+//! `build_info!` places a `BuildInfoRef<'static>` in a `static`, so accessing it neither allocates nor deserializes
+//! anything. The owned types can be recovered using `TryFrom`, e.g., `BuildInfo::try_from(build_info())`, which only
+//! fails if a version was not taken from a valid [`semver::Version`].
+
+use alloc::{
+	collections::BTreeMap,
+	format,
+	string::{String, ToString},
+	vec::Vec,
+};
+use core::cmp::Ordering;
+
+use chrono::{DateTime, NaiveDate, Utc};
+use semver::{BuildMetadata, Prerelease, Version};
+#[cfg(feature = "serde")]
+use serde::Serialize;
+
+use crate::{
+	BuildInfo, CompilerChannel, CompilerInfo, CpuInfo, CrateInfo, Endianness, GitInfo, OptimizationLevel, TargetInfo,
+	VersionControl,
+};
+
+/// Information about the current build (borrowed version of [`BuildInfo`])
+#[cfg_attr(feature = "serde", derive(Serialize))]
+#[derive(Clone, Copy, Eq, PartialEq, Ord, PartialOrd, Hash, Debug)]
+pub struct BuildInfoRef<'a> {
+	/// Updated whenever `build.rs` is rerun.
+	pub timestamp: DateTime<Utc>,
+
+	/// Cargo currently supports two different build types: `"Release"` and `"Debug"`
+	pub profile: &'a str,
+
+	/// The optimization level can be set in `Cargo.toml` for each profile
+	pub optimization_level: OptimizationLevel,
+
+	/// Information about the current crate
+	pub crate_info: CrateInfoRef<'a>,
+
+	/// Information about the target system
+	pub target: TargetInfoRef<'a>,
+
+	/// Information about the compiler used
+	pub compiler: CompilerInfoRef<'a>,
+
+	/// `Some` if the project is inside a check-out of a supported version control system
+	pub version_control: Option<VersionControlRef<'a>>,
+}
+
+/// Information about a crate (borrowed version of [`CrateInfo`])
+#[cfg_attr(feature = "serde", derive(Serialize))]
+#[derive(Clone, Copy, Eq, PartialEq, Ord, PartialOrd, Hash, Debug)]
+pub struct CrateInfoRef<'a> {
+	/// The name, as defined in `Cargo.toml`.
+	pub name: &'a str,
+
+	/// The version, as defined in `Cargo.toml`.
+	pub version: VersionRef<'a>,
+
+	/// The authors, as defined in `Cargo.toml`.
+	pub authors: &'a [&'a str],
+
+	/// The license string, as defined in `Cargo.toml`.
+	pub license: Option<&'a str>,
+
+	/// The source of the crate, as listed in `Cargo.lock` (e.g., `registry+https://github.com/rust-lang/crates.io-index`),
+	/// or `None` for crates from local paths.
+	pub source: Option<&'a str>,
+
+	/// The features of this crate that are currently enabled in this configuration.
+	pub enabled_features: &'a [&'a str],
+
+	/// All features that are available from this crate.
+	pub available_features: &'a [&'a str],
+
+	/// Dependencies of this crate.
+	/// Will only be filled with data if `collect_dependencies(true)` was called on `build_script()`.
+	pub dependencies: &'a [CrateInfoRef<'a>],
+}
+
+impl<'a> CrateInfoRef<'a> {
+	/// Groups all (transitive) dependencies by their license, as described for [`CrateInfo::licenses`].
+	pub fn licenses(&self) -> BTreeMap<Option<&'a str>, Vec<&'a CrateInfoRef<'a>>> {
+		fn collect<'a>(
+			crate_info: &CrateInfoRef<'a>,
+			crates: &mut BTreeMap<(&'a str, VersionRef<'a>), &'a CrateInfoRef<'a>>,
+		) {
+			for dependency in crate_info.dependencies {
+				crates.insert((dependency.name, dependency.version), dependency);
+				collect(dependency, crates);
+			}
+		}
+
+		let mut crates = BTreeMap::new();
+		collect(self, &mut crates);
+		let mut licenses = BTreeMap::<_, Vec<_>>::new();
+		for crate_info in crates.into_values() {
+			licenses.entry(crate_info.license).or_default().push(crate_info);
+		}
+		licenses
+	}
+}
+
+/// A semantic version (borrowed version of [`semver::Version`], which cannot be constructed in `const` contexts)
+///
+/// Versions are ordered like [`semver::Version`], i.e., by their precedence (`1.0.0-alpha.2 < 1.0.0-alpha.10 <
+/// 1.0.0`), with the build metadata only used to break ties.
+#[cfg_attr(feature = "serde", derive(Serialize))]
+#[derive(Clone, Copy, Eq, PartialEq, Hash, Debug)]
+pub struct VersionRef<'a> {
+	pub major: u64,
+	pub minor: u64,
+	pub patch: u64,
+	/// The pre-release identifiers (e.g., `"alpha.1"`), which are empty for regular releases
+	pub pre: &'a str,
+	/// The build metadata (e.g., `"20200528"`), which is usually empty
+	pub build: &'a str,
+}
+
+impl VersionRef<'_> {
+	/// Checks whether this is a pre-release version, e.g., `1.0.0-alpha.1`.
+	pub fn is_prerelease(&self) -> bool {
+		!self.pre.is_empty()
+	}
+}
+
+impl Ord for VersionRef<'_> {
+	fn cmp(&self, other: &Self) -> Ordering {
+		(self.major, self.minor, self.patch)
+			.cmp(&(other.major, other.minor, other.patch))
+			.then_with(|| match (self.pre.is_empty(), other.pre.is_empty()) {
+				// A pre-release has a lower precedence than the associated normal version
+				(true, true) => Ordering::Equal,
+				(true, false) => Ordering::Greater,
+				(false, true) => Ordering::Less,
+				(false, false) => cmp_identifiers(self.pre, other.pre),
+			})
+			.then_with(|| match (self.build.is_empty(), other.build.is_empty()) {
+				(true, true) => Ordering::Equal,
+				(true, false) => Ordering::Less,
+				(false, true) => Ordering::Greater,
+				(false, false) => cmp_identifiers(self.build, other.build),
+			})
+	}
+}
+
+impl PartialOrd for VersionRef<'_> {
+	fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+		Some(self.cmp(other))
+	}
+}
+
+/// Information about the target system (borrowed version of [`TargetInfo`])
+#[cfg_attr(feature = "serde", derive(Serialize))]
+#[derive(Clone, Copy, Eq, PartialEq, Ord, PartialOrd, Hash, Debug)]
+pub struct TargetInfoRef<'a> {
+	/// Identifies the target architecture for which the crate is being compiled
+	pub triple: &'a str,
+	/// A generic description of the target, e.g., `"unix"` or `"wasm"`
+	pub family: &'a str,
+	/// The target OS
+	pub os: &'a str,
+	/// The target CPU
+	pub cpu: CpuInfoRef<'a>,
+}
+
+/// Information about the target CPU (borrowed version of [`CpuInfo`])
+#[cfg_attr(feature = "serde", derive(Serialize))]
+#[derive(Clone, Copy, Eq, PartialEq, Ord, PartialOrd, Hash, Debug)]
+pub struct CpuInfoRef<'a> {
+	/// The CPU target architecture
+	pub arch: &'a str,
+	/// The CPU pointer width
+	pub pointer_width: u64,
+	/// The CPU target endianness
+	pub endianness: Endianness,
+	/// List of CPU target features enabled, sorted and without duplicates
+	pub features: &'a [&'a str],
+}
+
+impl CpuInfoRef<'_> {
+	/// Checks whether the target feature `feature` (e.g., `"avx2"`) was enabled for this build.
+	pub fn has_feature(&self, feature: &str) -> bool {
+		self.features.contains(&feature)
+	}
+
+	/// Checks whether all of the given target features were enabled for this build.
+	pub fn has_all_features(&self, features: &[&str]) -> bool {
+		features.iter().all(|feature| self.has_feature(feature))
+	}
+
+	/// The pointer width as a human-readable string, e.g., `"64-bit"`.
+	pub fn bitness(&self) -> String {
+		format!("{}-bit", self.pointer_width)
+	}
+}
+
+/// `rustc` version and configuration (borrowed version of [`CompilerInfo`])
+#[cfg_attr(feature = "serde", derive(Serialize))]
+#[derive(Clone, Copy, Eq, PartialEq, Ord, PartialOrd, Hash, Debug)]
+pub struct CompilerInfoRef<'a> {
+	/// Version of the current `rustc`
+	pub version: VersionRef<'a>,
+
+	/// Commit hash from which `rustc` was built
+	pub commit_id: Option<&'a str>,
+
+	/// Date on which `rustc` was built
+	pub commit_date: Option<NaiveDate>,
+
+	/// Channel which was configured for this version of `rustc`
+	pub channel: CompilerChannel,
+
+	/// Identifies the host on which `rustc` was running
+	pub host_triple: &'a str,
+}
+
+/// Support for different version control systems (borrowed version of [`VersionControl`])
+#[cfg_attr(feature = "serde", derive(Serialize))]
+#[derive(Clone, Copy, Eq, PartialEq, Ord, PartialOrd, Hash, Debug)]
+pub enum VersionControlRef<'a> {
+	Git(GitInfoRef<'a>),
+}
+
+impl<'a> VersionControlRef<'a> {
+	pub fn git(&self) -> Option<&GitInfoRef<'a>> {
+		match self {
+			VersionControlRef::Git(git) => Some(git),
+		}
+	}
+}
+
+/// Information about a git repository (borrowed version of [`GitInfo`])
+#[cfg_attr(feature = "serde", derive(Serialize))]
+#[derive(Clone, Copy, Eq, PartialEq, Ord, PartialOrd, Hash, Debug)]
+pub struct GitInfoRef<'a> {
+	/// Full commit hash for the currently checked out commit
+	pub commit_id: &'a str,
+
+	/// Short commit hash for the currently checked out commit
+	pub commit_short_id: &'a str,
+
+	/// Timestamp of the currently checked out commit
+	pub commit_timestamp: DateTime<Utc>,
+
+	/// `true` iff the repository had uncommitted changes when building the project.
+	pub dirty: bool,
+
+	/// Names the branch that is currently checked out, if any
+	pub branch: Option<&'a str>,
+
+	/// All tags that point to the current commit (e.g., `["v0.0.10", "sample@v0.0.10"]`)
+	pub tags: &'a [&'a str],
+}
+
+/// Compares dot-separated identifiers like [`semver::Prerelease`]: numeric identifiers are compared numerically and
+/// sort before alphanumeric ones, which are compared in ASCII order. If all identifiers are equal, the longer list
+/// sorts last.
+fn cmp_identifiers(lhs: &str, rhs: &str) -> Ordering {
+	let is_numeric = |identifier: &str| !identifier.is_empty() && identifier.bytes().all(|byte| byte.is_ascii_digit());
+	let mut lhs = lhs.split('.');
+	let mut rhs = rhs.split('.');
+	loop {
+		let ordering = match (lhs.next(), rhs.next()) {
+			(None, None) => return Ordering::Equal,
+			(None, Some(_)) => return Ordering::Less,
+			(Some(_), None) => return Ordering::Greater,
+			(Some(lhs), Some(rhs)) => match (is_numeric(lhs), is_numeric(rhs)) {
+				(true, true) => {
+					// Numbers may exceed `u64`, and leading zeros (which are only valid in build metadata) break ties
+					let (lhs_digits, rhs_digits) = (lhs.trim_start_matches('0'), rhs.trim_start_matches('0'));
+					lhs_digits
+						.len()
+						.cmp(&rhs_digits.len())
+						.then_with(|| lhs_digits.cmp(rhs_digits))
+						.then_with(|| lhs.len().cmp(&rhs.len()))
+				}
+				(true, false) => Ordering::Less,
+				(false, true) => Ordering::Greater,
+				(false, false) => lhs.cmp(rhs),
+			},
+		};
+		if ordering != Ordering::Equal {
+			return ordering;
+		}
+	}
+}
+
+fn to_strings(strings: &[&str]) -> Vec<String> {
+	strings.iter().map(|string| string.to_string()).collect()
+}
+
+impl TryFrom<&BuildInfoRef<'_>> for BuildInfo {
+	type Error = semver::Error;
+
+	fn try_from(value: &BuildInfoRef<'_>) -> Result<Self, Self::Error> {
+		Ok(BuildInfo {
+			timestamp: value.timestamp,
+			profile: value.profile.to_string(),
+			optimization_level: value.optimization_level,
+			crate_info: (&value.crate_info).try_into()?,
+			target: (&value.target).into(),
+			compiler: (&value.compiler).try_into()?,
+			version_control: value.version_control.as_ref().map(Into::into),
+		})
+	}
+}
+
+impl TryFrom<&CrateInfoRef<'_>> for CrateInfo {
+	type Error = semver::Error;
+
+	fn try_from(value: &CrateInfoRef<'_>) -> Result<Self, Self::Error> {
+		Ok(CrateInfo {
+			name: value.name.to_string(),
+			version: (&value.version).try_into()?,
+			authors: to_strings(value.authors),
+			license: value.license.map(str::to_string),
+			source: value.source.map(str::to_string),
+			enabled_features: to_strings(value.enabled_features),
+			available_features: to_strings(value.available_features),
+			dependencies: value
+				.dependencies
+				.iter()
+				.map(TryInto::try_into)
+				.collect::<Result<_, _>>()?,
+		})
+	}
+}
+
+impl TryFrom<&VersionRef<'_>> for Version {
+	type Error = semver::Error;
+
+	/// Fails if the pre-release identifiers or the build metadata are invalid, which can only happen if the
+	/// `VersionRef` was not generated by `build_info!`.
+	fn try_from(value: &VersionRef<'_>) -> Result<Self, Self::Error> {
+		Ok(Version {
+			major: value.major,
+			minor: value.minor,
+			patch: value.patch,
+			pre: Prerelease::new(value.pre)?,
+			build: BuildMetadata::new(value.build)?,
+		})
+	}
+}
+
+impl From<&TargetInfoRef<'_>> for TargetInfo {
+	fn from(value: &TargetInfoRef<'_>) -> Self {
+		TargetInfo {
+			triple: value.triple.to_string(),
+			family: value.family.to_string(),
+			os: value.os.to_string(),
+			cpu: (&value.cpu).into(),
+		}
+	}
+}
+
+impl From<&CpuInfoRef<'_>> for CpuInfo {
+	fn from(value: &CpuInfoRef<'_>) -> Self {
+		CpuInfo {
+			arch: value.arch.to_string(),
+			pointer_width: value.pointer_width,
+			endianness: value.endianness,
+			features: to_strings(value.features),
+		}
+	}
+}
+
+impl TryFrom<&CompilerInfoRef<'_>> for CompilerInfo {
+	type Error = semver::Error;
+
+	fn try_from(value: &CompilerInfoRef<'_>) -> Result<Self, Self::Error> {
+		Ok(CompilerInfo {
+			version: (&value.version).try_into()?,
+			commit_id: value.commit_id.map(str::to_string),
+			commit_date: value.commit_date,
+			channel: value.channel,
+			host_triple: value.host_triple.to_string(),
+		})
+	}
+}
+
+impl From<&VersionControlRef<'_>> for VersionControl {
+	fn from(value: &VersionControlRef<'_>) -> Self {
+		match value {
+			VersionControlRef::Git(git) => VersionControl::Git(git.into()),
+		}
+	}
+}
+
+impl From<&GitInfoRef<'_>> for GitInfo {
+	fn from(value: &GitInfoRef<'_>) -> Self {
+		GitInfo {
+			commit_id: value.commit_id.to_string(),
+			commit_short_id: value.commit_short_id.to_string(),
+			commit_timestamp: value.commit_timestamp,
+			dirty: value.dirty,
+			branch: value.branch.map(str::to_string),
+			tags: to_strings(value.tags),
+		}
+	}
+}
+
+#[cfg(test)]
+mod test {
+	use super::*;
+
+	fn version(pre: &'static str, build: &'static str) -> VersionRef<'static> {
+		VersionRef {
+			major: 1,
+			minor: 0,
+			patch: 0,
+			pre,
+			build,
+		}
+	}
+
+	#[test]
+	fn version_precedence() {
+		let mut versions = [
+			version("", ""),
+			version("alpha.10", ""),
+			version("", "2"),
+			version("beta", ""),
+			version("alpha.2", ""),
+			version("alpha", ""),
+		];
+		versions.sort();
+		assert_eq!(
+			versions,
+			[
+				version("alpha", ""),
+				version("alpha.2", ""),
+				version("alpha.10", ""),
+				version("beta", ""),
+				version("", ""),
+				version("", "2"),
+			]
+		);
+		assert!(
+			version("", "")
+				< VersionRef {
+					patch: 1,
+					..version("alpha", "")
+				}
+		);
+
+		// The order matches the one of `semver::Version`
+		let identifiers = ["", "1", "2", "10", "01", "a", "a.1", "a.b", "a.1.0", "b", "1a", "A"];
+		let versions: Vec<VersionRef> = identifiers
+			.iter()
+			.flat_map(|pre| identifiers.iter().map(|build| version(pre, build)))
+			.filter(|version| Version::try_from(version).is_ok())
+			.collect();
+		for lhs in &versions {
+			for rhs in &versions {
+				let owned = Version::try_from(lhs).unwrap().cmp(&Version::try_from(rhs).unwrap());
+				assert_eq!(lhs.cmp(rhs), owned, "{lhs} <=> {rhs}");
+			}
+		}
+
+		// Invalid identifiers can neither make the comparison nor the conversion panic
+		let invalid = version("!!", "");
+		assert_eq!(invalid.cmp(&version("alpha", "")), Ordering::Less);
+		assert!(Version::try_from(&invalid).is_err());
+	}
+
+	#[test]
+	fn licenses() {
+		use crate::fixtures::{crate_info, dependency_tree};
+
+		let mut root = dependency_tree().crate_info;
+		root
+			.dependencies
+			.push(crate_info("custom", Some("MIT OR Apache-2.0"), Vec::new()));
+		fn names<'a>(crates: &[&'a CrateInfo]) -> Vec<&'a str> {
+			crates.iter().map(|crate_info| crate_info.name.as_str()).collect()
+		}
+		let licenses: Vec<_> = root
+			.licenses()
+			.iter()
+			.map(|(license, crates)| (*license, names(crates)))
+			.collect();
+		assert_eq!(
+			licenses,
+			[
+				(None, Vec::from(["b"])),
+				(Some("MIT"), Vec::from(["a"])),
+				(Some("MIT OR Apache-2.0"), Vec::from(["custom"])),
+				(Some("MIT/Apache-2.0"), Vec::from(["shared"])),
+			]
+		);
+
+		let shared = [CrateInfoRef {
+			name: "shared",
+			version: version("", ""),
+			authors: &[],
+			license: Some("MIT/Apache-2.0"),
+			source: None,
+			enabled_features: &[],
+			available_features: &[],
+			dependencies: &[],
+		}];
+		let dependencies = [
+			CrateInfoRef {
+				name: "a",
+				license: None,
+				dependencies: &shared,
+				..shared[0]
+			},
+			CrateInfoRef {
+				name: "b",
+				dependencies: &shared,
+				..shared[0]
+			},
+		];
+		let root = CrateInfoRef {
+			name: "root",
+			dependencies: &dependencies,
+			..shared[0]
+		};
+		let licenses = root.licenses();
+		assert_eq!(licenses.len(), 2);
+		assert_eq!(licenses[&None][0].name, "a");
+		let names: Vec<&str> = licenses[&Some("MIT/Apache-2.0")]
+			.iter()
+			.map(|crate_info| crate_info.name)
+			.collect();
+		assert_eq!(names, ["b", "shared"]);
+	}
+
+	#[test]
+	fn bitness() {
+		let cpu = CpuInfoRef {
+			arch: "x86_64",
+			pointer_width: 64,
+			endianness: Endianness::Little,
+			features: &[],
+		};
+		assert_eq!(cpu.bitness(), "64-bit");
+	}
+}
@@ -1,5 +1,5 @@
-impl std::fmt::Display for crate::BuildInfo {
-	fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+impl core::fmt::Display for crate::BuildInfo {
+	fn fmt(&self, f: &mut core::fmt::Formatter) -> core::fmt::Result {
 		write!(f, "{} {} build", self.crate_info, self.profile)?;
 
 		if let Some(crate::VersionControl::Git(ref git)) = self.version_control {
@@ -10,8 +10,8 @@ impl std::fmt::Display for crate::BuildInfo {
 	}
 }
 
-impl std::fmt::Display for crate::OptimizationLevel {
-	fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+impl core::fmt::Display for crate::OptimizationLevel {
+	fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
 		match self {
 			Self::O0 => write!(f, "0"),
 			Self::O1 => write!(f, "1"),
@@ -23,26 +23,26 @@ impl std::fmt::Display for crate::OptimizationLevel {
 	}
 }
 
-impl std::fmt::Display for crate::CrateInfo {
-	fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+impl core::fmt::Display for crate::CrateInfo {
+	fn fmt(&self, f: &mut core::fmt::Formatter) -> core::fmt::Result {
 		write!(f, "{} v{}", self.name, self.version)
 	}
 }
 
-impl std::fmt::Display for crate::TargetInfo {
-	fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+impl core::fmt::Display for crate::TargetInfo {
+	fn fmt(&self, f: &mut core::fmt::Formatter) -> core::fmt::Result {
 		write!(f, "{}", self.triple)
 	}
 }
 
-impl std::fmt::Display for crate::CpuInfo {
-	fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+impl core::fmt::Display for crate::CpuInfo {
+	fn fmt(&self, f: &mut core::fmt::Formatter) -> core::fmt::Result {
 		write!(f, "{}", self.arch)
 	}
 }
 
-impl std::fmt::Display for crate::CompilerInfo {
-	fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+impl core::fmt::Display for crate::CompilerInfo {
+	fn fmt(&self, f: &mut core::fmt::Formatter) -> core::fmt::Result {
 		write!(f, "rustc {}", self.version)?;
 
 		if let Some(ref commit_id) = self.commit_id {
@@ -58,16 +58,16 @@ impl std::fmt::Display for crate::CompilerInfo {
 	}
 }
 
-impl std::fmt::Display for crate::VersionControl {
-	fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+impl core::fmt::Display for crate::VersionControl {
+	fn fmt(&self, f: &mut core::fmt::Formatter) -> core::fmt::Result {
 		match self {
 			crate::VersionControl::Git(git) => write!(f, "{git}"),
 		}
 	}
 }
 
-impl std::fmt::Display for crate::GitInfo {
-	fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+impl core::fmt::Display for crate::GitInfo {
+	fn fmt(&self, f: &mut core::fmt::Formatter) -> core::fmt::Result {
 		write!(f, "{}", &self.commit_id)?;
 
 		if self.dirty {
@@ -81,3 +81,90 @@ impl std::fmt::Display for crate::GitInfo {
 		Ok(())
 	}
 }
+
+impl core::fmt::Display for crate::BuildInfoRef<'_> {
+	fn fmt(&self, f: &mut core::fmt::Formatter) -> core::fmt::Result {
+		write!(f, "{} {} build", self.crate_info, self.profile)?;
+
+		if let Some(crate::VersionControlRef::Git(ref git)) = self.version_control {
+			write!(f, " from {git}")?;
+		}
+
+		Ok(())
+	}
+}
+
+impl core::fmt::Display for crate::CrateInfoRef<'_> {
+	fn fmt(&self, f: &mut core::fmt::Formatter) -> core::fmt::Result {
+		write!(f, "{} v{}", self.name, self.version)
+	}
+}
+
+impl core::fmt::Display for crate::VersionRef<'_> {
+	fn fmt(&self, f: &mut core::fmt::Formatter) -> core::fmt::Result {
+		write!(f, "{}.{}.{}", self.major, self.minor, self.patch)?;
+
+		if !self.pre.is_empty() {
+			write!(f, "-{}", self.pre)?;
+		}
+
+		if !self.build.is_empty() {
+			write!(f, "+{}", self.build)?;
+		}
+
+		Ok(())
+	}
+}
+
+impl core::fmt::Display for crate::TargetInfoRef<'_> {
+	fn fmt(&self, f: &mut core::fmt::Formatter) -> core::fmt::Result {
+		write!(f, "{}", self.triple)
+	}
+}
+
+impl core::fmt::Display for crate::CpuInfoRef<'_> {
+	fn fmt(&self, f: &mut core::fmt::Formatter) -> core::fmt::Result {
+		write!(f, "{}", self.arch)
+	}
+}
+
+impl core::fmt::Display for crate::CompilerInfoRef<'_> {
+	fn fmt(&self, f: &mut core::fmt::Formatter) -> core::fmt::Result {
+		write!(f, "rustc {}", self.version)?;
+
+		if let Some(commit_id) = self.commit_id {
+			let commit_id = &commit_id[0..9];
+			if let Some(ref commit_date) = self.commit_date {
+				write!(f, " ({commit_id} {commit_date})")?;
+			} else {
+				write!(f, " ({commit_id})")?;
+			}
+		}
+
+		Ok(())
+	}
+}
+
+impl core::fmt::Display for crate::VersionControlRef<'_> {
+	fn fmt(&self, f: &mut core::fmt::Formatter) -> core::fmt::Result {
+		match self {
+			crate::VersionControlRef::Git(git) => write!(f, "{git}"),
+		}
+	}
+}
+
+impl core::fmt::Display for crate::GitInfoRef<'_> {
+	fn fmt(&self, f: &mut core::fmt::Formatter) -> core::fmt::Result {
+		write!(f, "{}", self.commit_id)?;
+
+		if self.dirty {
+			write!(f, ".+")?;
+		}
+
+		if let Some(branch) = self.branch {
+			write!(f, " ({branch})")?;
+		}
+
+		Ok(())
+	}
+}
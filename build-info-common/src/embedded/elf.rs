@@ -0,0 +1,217 @@
+//! A minimal reader for the section headers of ELF files, which supports both 32 and 64 bit files in either byte order.
+
+use super::Error;
+
+const SHN_XINDEX: usize = 0xffff;
+const SHT_NOBITS: u32 = 8;
+
+#[derive(Copy, Clone)]
+struct Reader<'a> {
+	data: &'a [u8],
+	is_64: bool,
+	big_endian: bool,
+}
+
+impl<'a> Reader<'a> {
+	fn bytes<const N: usize>(&self, offset: usize) -> Result<[u8; N], Error> {
+		let end = offset.checked_add(N).ok_or(Error::Truncated)?;
+		let bytes = self.data.get(offset..end).ok_or(Error::Truncated)?;
+		Ok(bytes.try_into().expect("Slice has the requested length"))
+	}
+
+	fn u16(&self, offset: usize) -> Result<usize, Error> {
+		let bytes = self.bytes(offset)?;
+		Ok(usize::from(if self.big_endian {
+			u16::from_be_bytes(bytes)
+		} else {
+			u16::from_le_bytes(bytes)
+		}))
+	}
+
+	fn u32(&self, offset: usize) -> Result<u32, Error> {
+		let bytes = self.bytes(offset)?;
+		Ok(if self.big_endian {
+			u32::from_be_bytes(bytes)
+		} else {
+			u32::from_le_bytes(bytes)
+		})
+	}
+
+	/// Reads an address-sized value, i.e., 32 bits for ELF32 and 64 bits for ELF64.
+	fn word(&self, offset: usize) -> Result<usize, Error> {
+		let value = if !self.is_64 {
+			u64::from(self.u32(offset)?)
+		} else if self.big_endian {
+			u64::from_be_bytes(self.bytes(offset)?)
+		} else {
+			u64::from_le_bytes(self.bytes(offset)?)
+		};
+		usize::try_from(value).map_err(|_| Error::Truncated)
+	}
+
+	fn slice(&self, offset: usize, size: usize) -> Result<&'a [u8], Error> {
+		let end = offset.checked_add(size).ok_or(Error::Truncated)?;
+		self.data.get(offset..end).ok_or(Error::Truncated)
+	}
+}
+
+struct SectionHeader {
+	name: usize,
+	kind: u32,
+	link: usize,
+	offset: usize,
+	size: usize,
+}
+
+/// Returns the contents of the section called `name`, or `None` if the file does not contain such a section.
+pub fn elf_section<'a>(file: &'a [u8], name: &str) -> Result<Option<&'a [u8]>, Error> {
+	let ident = file.get(..16).ok_or(Error::NotElf)?;
+	if ident[..4] != *b"\x7fELF" {
+		return Err(Error::NotElf);
+	}
+	let reader = Reader {
+		data: file,
+		is_64: match ident[4] {
+			1 => false,
+			2 => true,
+			_ => return Err(Error::NotElf),
+		},
+		big_endian: match ident[5] {
+			1 => false,
+			2 => true,
+			_ => return Err(Error::NotElf),
+		},
+	};
+
+	let (shoff, shentsize, shnum, shstrndx) = if reader.is_64 {
+		(
+			reader.word(0x28)?,
+			reader.u16(0x3a)?,
+			reader.u16(0x3c)?,
+			reader.u16(0x3e)?,
+		)
+	} else {
+		(
+			reader.word(0x20)?,
+			reader.u16(0x2e)?,
+			reader.u16(0x30)?,
+			reader.u16(0x32)?,
+		)
+	};
+	if shoff == 0 {
+		return Ok(None);
+	}
+
+	let header = |index: usize| -> Result<SectionHeader, Error> {
+		let base = index
+			.checked_mul(shentsize)
+			.and_then(|offset| offset.checked_add(shoff))
+			.ok_or(Error::Truncated)?;
+		// The fields are read relative to the header, so that their offsets cannot overflow
+		let header = Reader {
+			data: reader.slice(base, shentsize)?,
+			..reader
+		};
+		Ok(if header.is_64 {
+			SectionHeader {
+				name: header.u32(0x00)? as usize,
+				kind: header.u32(0x04)?,
+				link: header.u32(0x28)? as usize,
+				offset: header.word(0x18)?,
+				size: header.word(0x20)?,
+			}
+		} else {
+			SectionHeader {
+				name: header.u32(0x00)? as usize,
+				kind: header.u32(0x04)?,
+				link: header.u32(0x18)? as usize,
+				offset: header.word(0x10)?,
+				size: header.word(0x14)?,
+			}
+		})
+	};
+
+	// Files with many sections store the actual section count and string table index in the first section header
+	let first = header(0)?;
+	let shnum = if shnum == 0 { first.size } else { shnum };
+	let shstrndx = if shstrndx == SHN_XINDEX { first.link } else { shstrndx };
+
+	let strings = header(shstrndx)?;
+	let strings = reader.slice(strings.offset, strings.size)?;
+	for index in 1..shnum {
+		let section = header(index)?;
+		let section_name = strings.get(section.name..).ok_or(Error::Truncated)?;
+		let section_name = section_name.split(|&byte| byte == 0).next().unwrap_or_default();
+		if section_name == name.as_bytes() {
+			if section.kind == SHT_NOBITS {
+				return Ok(Some(&[]));
+			}
+			return reader.slice(section.offset, section.size).map(Some);
+		}
+	}
+
+	Ok(None)
+}
+
+#[cfg(test)]
+pub(super) mod test {
+	use alloc::vec::Vec;
+
+	use super::*;
+
+	/// Creates a little-endian ELF64 file that contains only the given sections (and a section name string table).
+	pub(in super::super) fn elf64(sections: &[(&str, &[u8])]) -> Vec<u8> {
+		let mut file = alloc::vec![0u8; 64];
+		file[..4].copy_from_slice(b"\x7fELF");
+		file[4] = 2;
+		file[5] = 1;
+		file[6] = 1;
+
+		let mut strings = alloc::vec![0u8];
+		let mut headers = alloc::vec![[0u8; 64]];
+		let mut push_section = |file: &mut Vec<u8>, name_offset: usize, data: &[u8]| {
+			let mut header = [0u8; 64];
+			header[..4].copy_from_slice(&(name_offset as u32).to_le_bytes());
+			header[4..8].copy_from_slice(&1u32.to_le_bytes());
+			header[0x18..0x20].copy_from_slice(&(file.len() as u64).to_le_bytes());
+			header[0x20..0x28].copy_from_slice(&(data.len() as u64).to_le_bytes());
+			file.extend_from_slice(data);
+			headers.push(header);
+		};
+		for (name, data) in sections {
+			let name_offset = strings.len();
+			strings.extend_from_slice(name.as_bytes());
+			strings.push(0);
+			push_section(&mut file, name_offset, data);
+		}
+		let name_offset = strings.len();
+		strings.extend_from_slice(b".shstrtab\0");
+		push_section(&mut file, name_offset, &strings.clone());
+
+		let shoff = file.len() as u64;
+		for header in &headers {
+			file.extend_from_slice(header);
+		}
+		file[0x28..0x30].copy_from_slice(&shoff.to_le_bytes());
+		file[0x3a..0x3c].copy_from_slice(&64u16.to_le_bytes());
+		file[0x3c..0x3e].copy_from_slice(&(headers.len() as u16).to_le_bytes());
+		file[0x3e..0x40].copy_from_slice(&(headers.len() as u16 - 1).to_le_bytes());
+		file
+	}
+
+	#[test]
+	fn sections() {
+		let file = elf64(&[(".text", &[1, 2, 3]), (".data", &[4])]);
+		assert_eq!(elf_section(&file, ".text"), Ok(Some(&[1, 2, 3][..])));
+		assert_eq!(elf_section(&file, ".data"), Ok(Some(&[4][..])));
+		assert_eq!(elf_section(&file, ".bss"), Ok(None));
+		assert_eq!(elf_section(&file[..100], ".text"), Err(Error::Truncated));
+
+		let mut file = file;
+		file[0x28..0x30].copy_from_slice(&(u64::MAX - 8).to_le_bytes());
+		assert_eq!(elf_section(&file, ".text"), Err(Error::Truncated));
+		file[0x28..0x30].copy_from_slice(&0x40u64.to_le_bytes());
+		file[0x3a..0x3c].copy_from_slice(&8u16.to_le_bytes());
+		assert_eq!(elf_section(&file, ".text"), Err(Error::Truncated));
+	}
+}
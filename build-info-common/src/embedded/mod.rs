@@ -0,0 +1,290 @@
+//! Build information that is embedded into a dedicated section of a binary by `build_info::embed!()`.
+//!
+//! The section contains one or more records, each of which consists of a fixed-size header (all integers are
+//! little-endian), followed by the version of `build-info-common` that wrote the record and the CBOR-encoded
+//! [`BuildInfo`]:
+//!
+//! | Offset | Size | Content                                          |
+//! |--------|------|--------------------------------------------------|
+//! | 0      | 8    | [`MAGIC`]                                        |
+//! | 8      | 2    | Format version (currently [`FORMAT_VERSION`])    |
+//! | 10     | 1    | Encoding of the payload ([`Encoding`])           |
+//! | 11     | 1    | Length of the `build-info-common` version string |
+//! | 12     | 4    | Length of the payload                            |
+//! | 16     |      | `build-info-common` version string, then payload |
+//!
+//! Since the records are self-describing, they can be extracted from a binary (or a core dump) without executing it,
+//! e.g., using [`read_elf`].
+
+use alloc::{
+	string::{String, ToString},
+	vec::Vec,
+};
+use core::{fmt, str};
+
+use crate::{BuildInfo, crate_version};
+
+mod elf;
+pub use elf::elf_section;
+
+/// Identifies the start of a record
+pub const MAGIC: [u8; 8] = *b"BLDINFO\0";
+
+/// The version of the record layout that is written by this crate
+pub const FORMAT_VERSION: u16 = 1;
+
+/// The name of the ELF section into which `build_info::embed!()` places the records
+pub const ELF_SECTION: &str = ".build_info";
+
+/// The name of the PE/COFF section into which `build_info::embed!()` places the records on Windows, where section names
+/// in the final image are limited to 8 bytes
+pub const PE_SECTION: &str = ".bldinfo";
+
+/// The name of the Mach-O section into which `build_info::embed!()` places the records
+pub const MACHO_SECTION: &str = "__DATA,__build_info";
+
+const HEADER_SIZE: usize = 16;
+
+/// The ways in which the payload of a record may be encoded
+#[derive(Copy, Clone, Eq, PartialEq, Debug)]
+#[repr(u8)]
+pub enum Encoding {
+	Cbor = 1,
+}
+
+/// Errors that can occur while extracting embedded build information
+#[derive(Clone, Eq, PartialEq, Debug)]
+pub enum Error {
+	/// The file is not a (supported) ELF file
+	NotElf,
+	/// The binary does not contain a section with the given name
+	MissingSection(String),
+	/// The file does not contain any embedded build information
+	NotFound,
+	/// The data ended unexpectedly
+	Truncated,
+	/// The data does not start with [`MAGIC`]
+	BadMagic,
+	/// The record was written using an unknown format version
+	UnsupportedFormat(u16),
+	/// The payload was encoded in an unknown way
+	UnsupportedEncoding(u8),
+	/// The payload could not be decoded
+	Decode(String),
+}
+
+impl fmt::Display for Error {
+	fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+		match self {
+			Error::NotElf => write!(f, "Not an ELF file"),
+			Error::MissingSection(name) => write!(f, "The binary does not contain a `{name}` section"),
+			Error::NotFound => write!(f, "The file does not contain any embedded build information"),
+			Error::Truncated => write!(f, "The embedded build information is truncated"),
+			Error::BadMagic => write!(
+				f,
+				"The embedded build information does not start with the expected magic bytes"
+			),
+			Error::UnsupportedFormat(version) => write!(f, "Unsupported format version {version}"),
+			Error::UnsupportedEncoding(encoding) => write!(f, "Unsupported payload encoding {encoding}"),
+			Error::Decode(err) => write!(f, "Could not decode the embedded build information: {err}"),
+		}
+	}
+}
+
+impl core::error::Error for Error {}
+
+/// A single record of embedded build information
+#[derive(Copy, Clone, Eq, PartialEq, Debug)]
+pub struct Record<'a> {
+	/// The format version of the record
+	pub format_version: u16,
+	/// The encoding of the payload
+	pub encoding: Encoding,
+	/// The version of `build-info-common` that wrote the record
+	pub crate_version: &'a str,
+	/// The encoded build information
+	pub payload: &'a [u8],
+}
+
+impl Record<'_> {
+	/// Decodes the build information stored in this record.
+	pub fn build_info(&self) -> Result<BuildInfo, Error> {
+		match self.encoding {
+			Encoding::Cbor => ciborium::from_reader(self.payload).map_err(|err| Error::Decode(alloc::format!("{err}"))),
+		}
+	}
+}
+
+/// Encodes `build_info` as a record that can be embedded into a binary.
+pub fn encode(build_info: &BuildInfo) -> Vec<u8> {
+	let mut payload = Vec::new();
+	ciborium::into_writer(build_info, &mut payload).expect("Could not serialize BuildInfo");
+	let version = crate_version().to_string();
+
+	let mut record = Vec::with_capacity(HEADER_SIZE + version.len() + payload.len());
+	record.extend_from_slice(&MAGIC);
+	record.extend_from_slice(&FORMAT_VERSION.to_le_bytes());
+	record.push(Encoding::Cbor as u8);
+	record.push(u8::try_from(version.len()).expect("Version string is too long"));
+	record.extend_from_slice(
+		&u32::try_from(payload.len())
+			.expect("Payload is too large")
+			.to_le_bytes(),
+	);
+	record.extend_from_slice(version.as_bytes());
+	record.extend_from_slice(&payload);
+	record
+}
+
+/// Parses the record at the start of `data`, returning it together with the remaining data.
+pub fn parse_record(data: &[u8]) -> Result<(Record<'_>, &[u8]), Error> {
+	let header = data.get(..HEADER_SIZE).ok_or(Error::Truncated)?;
+	if header[..8] != MAGIC {
+		return Err(Error::BadMagic);
+	}
+
+	let format_version = u16::from_le_bytes([header[8], header[9]]);
+	if format_version != FORMAT_VERSION {
+		return Err(Error::UnsupportedFormat(format_version));
+	}
+	let encoding = match header[10] {
+		1 => Encoding::Cbor,
+		encoding => return Err(Error::UnsupportedEncoding(encoding)),
+	};
+	let version_len = usize::from(header[11]);
+	let payload_len = u32::from_le_bytes([header[12], header[13], header[14], header[15]]);
+	let payload_len = usize::try_from(payload_len).map_err(|_| Error::Truncated)?;
+
+	// The length comes from untrusted data, which must not make the reader panic
+	let rest = &data[HEADER_SIZE..];
+	if rest.len() < version_len.checked_add(payload_len).ok_or(Error::Truncated)? {
+		return Err(Error::Truncated);
+	}
+	let (crate_version, rest) = rest.split_at(version_len);
+	let (payload, rest) = rest.split_at(payload_len);
+	let crate_version = str::from_utf8(crate_version).map_err(|err| Error::Decode(alloc::format!("{err}")))?;
+
+	let record = Record {
+		format_version,
+		encoding,
+		crate_version,
+		payload,
+	};
+	Ok((record, rest))
+}
+
+/// Parses all records in the contents of a section. The linker may pad the records with zero bytes, which are skipped.
+pub fn parse_section(mut section: &[u8]) -> Result<Vec<Record<'_>>, Error> {
+	let mut records = Vec::new();
+	loop {
+		let start = section.iter().position(|&byte| byte != 0).unwrap_or(section.len());
+		section = &section[start..];
+		if section.is_empty() {
+			return Ok(records);
+		}
+
+		let (record, rest) = parse_record(section)?;
+		records.push(record);
+		section = rest;
+	}
+}
+
+/// Extracts all embedded build information from the contents of an ELF file (e.g., an executable, a shared library or
+/// a core dump).
+pub fn read_elf(file: &[u8]) -> Result<Vec<BuildInfo>, Error> {
+	let section = elf_section(file, ELF_SECTION)?.ok_or_else(|| Error::MissingSection(ELF_SECTION.into()))?;
+	parse_section(section)?.iter().map(Record::build_info).collect()
+}
+
+/// Finds all records in arbitrary data by searching for [`MAGIC`]. This works for any kind of file (e.g., Mach-O or PE
+/// files, which are not parsed by this crate, or data extracted from a firmware image), but is slower than reading the
+/// section directly.
+pub fn scan(data: &[u8]) -> Vec<Record<'_>> {
+	let mut records = Vec::new();
+	let mut rest = data;
+	while let Some(start) = rest.windows(MAGIC.len()).position(|window| window == MAGIC) {
+		match parse_record(&rest[start..]) {
+			Ok((record, remaining)) => {
+				records.push(record);
+				rest = remaining;
+			}
+			// Not every occurrence of the magic bytes starts a record
+			Err(_) => rest = &rest[start + 1..],
+		}
+	}
+	records
+}
+
+/// Extracts all embedded build information from a file, using the `.build_info` section of ELF files and falling back
+/// to [`scan`] for all other files.
+pub fn read(file: &[u8]) -> Result<Vec<BuildInfo>, Error> {
+	let records = match elf_section(file, ELF_SECTION) {
+		Ok(Some(section)) => parse_section(section)?,
+		Ok(None) | Err(Error::NotElf) => scan(file),
+		Err(err) => return Err(err),
+	};
+	if records.is_empty() {
+		return Err(Error::NotFound);
+	}
+	records.iter().map(Record::build_info).collect()
+}
+
+#[cfg(test)]
+mod test {
+	use alloc::vec;
+
+	use super::{elf::test::elf64, *};
+
+	fn build_info(name: &str) -> BuildInfo {
+		let mut build_info = crate::fixtures::build_info();
+		build_info.crate_info.name = name.to_string();
+		build_info
+	}
+
+	#[test]
+	fn roundtrip() {
+		let first = build_info("first");
+		let second = build_info("second");
+		let mut section = encode(&first);
+		section.extend_from_slice(&[0; 5]);
+		section.extend_from_slice(&encode(&second));
+
+		let records = parse_section(&section).unwrap();
+		assert_eq!(records.len(), 2);
+		assert_eq!(records[0].crate_version, crate_version().to_string());
+		assert_eq!(records[0].build_info(), Ok(first));
+		assert_eq!(records[1].build_info(), Ok(second));
+
+		let record = encode(&build_info("truncated"));
+		assert_eq!(parse_section(&record[..record.len() - 1]), Err(Error::Truncated));
+		assert_eq!(parse_section(b"BLDINFO?"), Err(Error::Truncated));
+		assert_eq!(parse_section(&[1; 20]), Err(Error::BadMagic));
+
+		let mut huge = record[..HEADER_SIZE].to_vec();
+		huge[11] = u8::MAX;
+		huge[12..16].copy_from_slice(&u32::MAX.to_le_bytes());
+		assert_eq!(parse_section(&huge), Err(Error::Truncated));
+	}
+
+	#[test]
+	fn elf() {
+		let info = build_info("elf");
+		let file = elf64(&[(".text", &[0x90; 4]), (ELF_SECTION, &encode(&info))]);
+		assert_eq!(read_elf(&file), Ok(vec![info]));
+
+		let file = elf64(&[(".text", &[0x90; 4])]);
+		assert_eq!(read_elf(&file), Err(Error::MissingSection(ELF_SECTION.into())));
+		assert_eq!(read_elf(b"MZ"), Err(Error::NotElf));
+	}
+
+	#[test]
+	fn scanning() {
+		let info = build_info("scan");
+		let mut file = b"MZ...BLDINFO\0 is not a record".to_vec();
+		file.extend_from_slice(&encode(&info));
+		file.extend_from_slice(b"trailing data");
+		assert_eq!(scan(&file).len(), 1);
+		assert_eq!(read(&file), Ok(vec![info]));
+		assert_eq!(read(b"MZ"), Err(Error::NotFound));
+	}
+}
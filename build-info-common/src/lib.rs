@@ -7,6 +7,11 @@ For example, `build_info_common::BuildInfo` should be used as `build_info::Build
 */
 
 #![forbid(unsafe_code)]
+#![no_std]
+
+extern crate alloc;
+
+use alloc::{collections::BTreeMap, format, string::String, vec::Vec};
 
 pub use chrono;
 use chrono::{DateTime, NaiveDate, Utc};
@@ -21,8 +26,19 @@ mod versioned_string;
 #[cfg(feature = "serde")]
 pub use versioned_string::VersionedString;
 
+mod borrowed;
+pub use borrowed::{
+	BuildInfoRef, CompilerInfoRef, CpuInfoRef, CrateInfoRef, GitInfoRef, TargetInfoRef, VersionControlRef, VersionRef,
+};
+
 mod display;
 
+#[cfg(feature = "embedded")]
+pub mod embedded;
+
+#[cfg(test)]
+mod fixtures;
+
 /// Gets the version of the `build-info-common` crate (this crate)
 pub fn crate_version() -> Version {
 	Version::parse(env!("CARGO_PKG_VERSION")).unwrap()
@@ -30,6 +46,7 @@ pub fn crate_version() -> Version {
 
 /// Information about the current build
 #[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 #[derive(Clone, Eq, PartialEq, Ord, PartialOrd, Hash, Debug)]
 pub struct BuildInfo {
 	/// Updated whenever `build.rs` is rerun.
@@ -56,6 +73,7 @@ pub struct BuildInfo {
 
 /// The various possible optimization levels
 #[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 #[derive(Clone, Copy, Eq, PartialEq, Ord, PartialOrd, Hash, Debug)]
 pub enum OptimizationLevel {
 	O0,
@@ -68,6 +86,7 @@ pub enum OptimizationLevel {
 
 /// Information about the current crate (i.e., the crate for which build information has been generated)
 #[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 #[derive(Clone, Eq, PartialEq, Ord, PartialOrd, Hash, Debug)]
 pub struct CrateInfo {
 	/// The name, as defined in `Cargo.toml`.
@@ -82,6 +101,10 @@ pub struct CrateInfo {
 	/// The license string, as defined in `Cargo.toml`.
 	pub license: Option<String>,
 
+	/// The source of the crate, as listed in `Cargo.lock` (e.g., `registry+https://github.com/rust-lang/crates.io-index`),
+	/// or `None` for crates from local paths.
+	pub source: Option<String>,
+
 	/// The features of this crate that are currently enabled in this configuration.
 	pub enabled_features: Vec<String>,
 
@@ -93,7 +116,36 @@ pub struct CrateInfo {
 	pub dependencies: Vec<CrateInfo>,
 }
 
+impl CrateInfo {
+	/// Groups all (transitive) dependencies by their license, e.g., to show an "Open source notices" screen.
+	///
+	/// Each dependency is listed once, sorted by name and version. Licenses are grouped by their license string as
+	/// written in `Cargo.toml`, so, e.g., `MIT/Apache-2.0` and `MIT OR Apache-2.0` are separate groups. Crates that do
+	/// not declare a license are grouped under `None`. Dependencies are only known if they were collected by the build
+	/// script.
+	pub fn licenses(&self) -> BTreeMap<Option<&str>, Vec<&CrateInfo>> {
+		fn collect<'a>(crate_info: &'a CrateInfo, crates: &mut BTreeMap<(&'a str, &'a Version), &'a CrateInfo>) {
+			for dependency in &crate_info.dependencies {
+				crates.insert((&dependency.name, &dependency.version), dependency);
+				collect(dependency, crates);
+			}
+		}
+
+		let mut crates = BTreeMap::new();
+		collect(self, &mut crates);
+		let mut licenses = BTreeMap::<_, Vec<_>>::new();
+		for crate_info in crates.into_values() {
+			licenses
+				.entry(crate_info.license.as_deref())
+				.or_default()
+				.push(crate_info);
+		}
+		licenses
+	}
+}
+
 #[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 #[derive(Clone, Eq, PartialEq, Ord, PartialOrd, Hash, Debug)]
 pub struct TargetInfo {
 	/// Identifies the target architecture for which the crate is being compiled
@@ -107,6 +159,7 @@ pub struct TargetInfo {
 }
 
 #[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 #[derive(Clone, Eq, PartialEq, Ord, PartialOrd, Hash, Debug)]
 pub struct CpuInfo {
 	/// The CPU target architecture
@@ -115,12 +168,30 @@ pub struct CpuInfo {
 	pub pointer_width: u64,
 	/// The CPU target endianness
 	pub endianness: Endianness,
-	///  List of CPU target features enabled
+	/// List of CPU target features enabled, sorted and without duplicates
 	pub features: Vec<String>,
 }
 
+impl CpuInfo {
+	/// Checks whether the target feature `feature` (e.g., `"avx2"`) was enabled for this build.
+	pub fn has_feature(&self, feature: &str) -> bool {
+		self.features.iter().any(|enabled| enabled == feature)
+	}
+
+	/// Checks whether all of the given target features were enabled for this build.
+	pub fn has_all_features(&self, features: &[&str]) -> bool {
+		features.iter().all(|feature| self.has_feature(feature))
+	}
+
+	/// The pointer width as a human-readable string, e.g., `"64-bit"`.
+	pub fn bitness(&self) -> String {
+		format!("{}-bit", self.pointer_width)
+	}
+}
+
 /// CPU Endianness
 #[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 #[derive(Display, Copy, Clone, Eq, PartialEq, Ord, PartialOrd, Hash, Debug)]
 pub enum Endianness {
 	Big,
@@ -129,6 +200,7 @@ pub enum Endianness {
 
 /// `rustc` version and configuration
 #[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 #[derive(Clone, Eq, PartialEq, Ord, PartialOrd, Hash, Debug)]
 pub struct CompilerInfo {
 	/// Version of the current `rustc`
@@ -149,6 +221,7 @@ pub struct CompilerInfo {
 
 /// `rustc` distribution channel (some compiler features are only available on specific channels)
 #[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 #[derive(Display, Copy, Clone, Eq, PartialEq, Ord, PartialOrd, Hash, Debug)]
 pub enum CompilerChannel {
 	Dev,
@@ -159,6 +232,7 @@ pub enum CompilerChannel {
 
 /// Support for different version control systems
 #[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 #[derive(Clone, Eq, PartialEq, Ord, PartialOrd, Hash, Debug)]
 pub enum VersionControl {
 	Git(GitInfo),
@@ -180,6 +254,7 @@ If a git repository is detected (and, thereby, this information included), the b
 currently checked out commit changes.
 */
 #[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 #[derive(Clone, Eq, PartialEq, Ord, PartialOrd, Hash, Debug)]
 pub struct GitInfo {
 	/// Full commit hash for the currently checked out commit
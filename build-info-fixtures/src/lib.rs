@@ -0,0 +1,80 @@
+//! Sample build information that is shared by the tests of the build-info crates.
+//!
+//! This crate is not published, it is only used as a dev-dependency within the workspace.
+
+use build_info_common::{
+	BuildInfo, CompilerChannel, CompilerInfo, CpuInfo, CrateInfo, Endianness, GitInfo, OptimizationLevel, TargetInfo,
+	VersionControl, chrono::DateTime, semver::Version,
+};
+
+/// A release build of `sample` 1.2.3 for `x86_64-unknown-linux-gnu` from a clean git checkout of `main`, without
+/// dependencies
+pub fn build_info() -> BuildInfo {
+	BuildInfo {
+		timestamp: DateTime::from_timestamp(1_590_697_780, 0).unwrap(),
+		profile: "release".to_string(),
+		optimization_level: OptimizationLevel::O3,
+		crate_info: CrateInfo {
+			name: "sample".to_string(),
+			version: Version::new(1, 2, 3),
+			authors: Vec::from(["Jane Doe".to_string(), "John Doe".to_string()]),
+			license: None,
+			source: None,
+			enabled_features: Vec::new(),
+			available_features: Vec::from(["default".to_string()]),
+			dependencies: Vec::new(),
+		},
+		target: TargetInfo {
+			triple: "x86_64-unknown-linux-gnu".to_string(),
+			family: "unix".to_string(),
+			os: "linux".to_string(),
+			cpu: CpuInfo {
+				arch: "x86_64".to_string(),
+				pointer_width: 64,
+				endianness: Endianness::Little,
+				features: Vec::new(),
+			},
+		},
+		compiler: CompilerInfo {
+			version: Version::new(1, 88, 0),
+			commit_id: None,
+			commit_date: None,
+			channel: CompilerChannel::Stable,
+			host_triple: "x86_64-unknown-linux-gnu".to_string(),
+		},
+		version_control: Some(VersionControl::Git(GitInfo {
+			commit_id: "0123456789abcdef".to_string(),
+			commit_short_id: "0123456".to_string(),
+			commit_timestamp: DateTime::from_timestamp(1_590_697_000, 0).unwrap(),
+			dirty: false,
+			branch: Some("main".to_string()),
+			tags: Vec::new(),
+		})),
+	}
+}
+
+/// A crate from crates.io with version 1.0.0 and no features
+pub fn crate_info(name: &str, license: Option<&str>, dependencies: Vec<CrateInfo>) -> CrateInfo {
+	CrateInfo {
+		name: name.to_string(),
+		version: Version::new(1, 0, 0),
+		authors: Vec::new(),
+		license: license.map(String::from),
+		source: Some("registry+https://github.com/rust-lang/crates.io-index".to_string()),
+		enabled_features: Vec::new(),
+		available_features: Vec::new(),
+		dependencies,
+	}
+}
+
+/// [`build_info`] with the dependencies `a -> shared` and `b -> shared`, where `shared` is licensed under
+/// `MIT/Apache-2.0`, `a` under `MIT` and `b` does not declare a license
+pub fn dependency_tree() -> BuildInfo {
+	let mut build_info = build_info();
+	let shared = crate_info("shared", Some("MIT/Apache-2.0"), Vec::new());
+	build_info.crate_info.dependencies = Vec::from([
+		crate_info("a", Some("MIT"), Vec::from([shared.clone()])),
+		crate_info("b", None, Vec::from([shared])),
+	]);
+	build_info
+}
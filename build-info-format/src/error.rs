@@ -0,0 +1,196 @@
+use std::fmt;
+
+use proc_macro_error2::{Diagnostic, Level};
+use proc_macro2::Span;
+
+/// An error that occurred while evaluating an expression, along with the span of the sub-expression that caused it.
+#[derive(Debug)]
+pub struct EvalError {
+	pub span: Span,
+	pub error: anyhow::Error,
+	/// The position in the template file that contains the offending expression, if it was parsed from one
+	pub location: Option<String>,
+}
+
+pub type Result<T> = std::result::Result<T, EvalError>;
+
+impl EvalError {
+	pub fn new(span: Span, error: anyhow::Error) -> Self {
+		Self {
+			span,
+			error,
+			location: None,
+		}
+	}
+
+	/// Reports the error at `span` instead, which is used for expressions that were parsed from a template.
+	pub fn at(self, span: Span, location: Option<String>) -> Self {
+		Self { span, location, ..self }
+	}
+
+	/// Reports the error at the offending sub-expression and stops the macro expansion.
+	pub fn abort(&self) -> ! {
+		self
+			.notes()
+			.into_iter()
+			.fold(
+				Diagnostic::spanned(self.span, Level::Error, self.message()),
+				|diagnostic, note| diagnostic.note(note),
+			)
+			.abort()
+	}
+
+	fn message(&self) -> String {
+		match self.error.downcast_ref::<Hint>() {
+			Some(hint) => hint.message.clone(),
+			None if self.error.is::<UnwrapNone>() => self.error.to_string(),
+			None => format!("{:#}", self.error),
+		}
+	}
+
+	fn notes(&self) -> Vec<String> {
+		let mut notes = if let Some(hint) = self.error.downcast_ref::<Hint>() {
+			hint.notes.clone()
+		} else if self.error.is::<UnwrapNone>() {
+			vec!["Use `?? <expr>` to fall back to another value if this `Option` does not contain a value".to_string()]
+		} else {
+			Vec::new()
+		};
+		notes.extend(self.location.clone());
+		notes
+	}
+}
+
+/// Writes the message and the notes as they would be reported by the compiler, e.g., for the `build-info` command.
+impl fmt::Display for EvalError {
+	fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+		write!(f, "{}", self.message())?;
+		for note in self.notes() {
+			write!(f, "\n  = note: {note}")?;
+		}
+		Ok(())
+	}
+}
+
+impl std::error::Error for EvalError {}
+
+/// An error message with additional notes, such as suggestions for misspelled names.
+#[derive(Debug)]
+pub struct Hint {
+	pub message: String,
+	pub notes: Vec<String>,
+}
+
+impl fmt::Display for Hint {
+	fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+		write!(f, "{}", self.message)
+	}
+}
+
+impl std::error::Error for Hint {}
+
+/// The error produced by unwrapping an empty `Option` using `?`, which can be caught by the `??` operator.
+#[derive(Debug)]
+pub(crate) struct UnwrapNone;
+
+impl fmt::Display for UnwrapNone {
+	fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+		write!(f, "Could not unwrap Option (object does not contain a value)")
+	}
+}
+
+impl std::error::Error for UnwrapNone {}
+
+/// Finds the candidate that is closest to `name`, if any is close enough to be a plausible typo.
+pub(crate) fn similar_name<'a>(name: &str, candidates: &[&'a str]) -> Option<&'a str> {
+	let max_distance = (name.chars().count() / 3).max(1);
+	candidates
+		.iter()
+		.map(|candidate| (edit_distance(name, candidate), *candidate))
+		.filter(|(distance, _candidate)| *distance <= max_distance)
+		.min_by_key(|(distance, _candidate)| *distance)
+		.map(|(_distance, candidate)| candidate)
+}
+
+/// Edit distance between `a` and `b`, counted in `char`s, where swapping two adjacent characters counts as one edit.
+fn edit_distance(a: &str, b: &str) -> usize {
+	let a: Vec<char> = a.chars().collect();
+	let b: Vec<char> = b.chars().collect();
+	let mut distances = vec![vec![0; b.len() + 1]; a.len() + 1];
+
+	for (i, row) in distances.iter_mut().enumerate() {
+		row[0] = i;
+	}
+	for (j, distance) in distances[0].iter_mut().enumerate() {
+		*distance = j;
+	}
+
+	for i in 1..=a.len() {
+		for j in 1..=b.len() {
+			let substitution = distances[i - 1][j - 1] + usize::from(a[i - 1] != b[j - 1]);
+			let mut distance = substitution.min(distances[i - 1][j] + 1).min(distances[i][j - 1] + 1);
+			if i > 1 && j > 1 && a[i - 1] == b[j - 2] && a[i - 2] == b[j - 1] {
+				distance = distance.min(distances[i - 2][j - 2] + 1);
+			}
+			distances[i][j] = distance;
+		}
+	}
+
+	distances[a.len()][b.len()]
+}
+
+#[cfg(test)]
+mod test {
+	use build_info_common::semver::Version;
+	use pretty_assertions::assert_eq;
+
+	use super::{
+		super::value::{OP_FIELD_ACCESS, Value},
+		*,
+	};
+
+	#[test]
+	fn distances() {
+		assert_eq!(edit_distance("", ""), 0);
+		assert_eq!(edit_distance("abc", ""), 3);
+		assert_eq!(edit_distance("kitten", "sitting"), 3);
+		assert_eq!(edit_distance("commit_shortid", "commit_short_id"), 1);
+		assert_eq!(edit_distance("mjaor", "major"), 1);
+	}
+
+	#[test]
+	fn suggestions() {
+		let candidates = ["commit_id", "commit_short_id", "commit_timestamp", "dirty"];
+		assert_eq!(similar_name("commit_shortid", &candidates), Some("commit_short_id"));
+		assert_eq!(similar_name("dirt", &candidates), Some("dirty"));
+		assert_eq!(similar_name("branch", &candidates), None);
+	}
+
+	#[test]
+	fn unknown_field_hint() {
+		let error = Version::new(1, 2, 3)
+			.call(OP_FIELD_ACCESS, &[Box::new("mjaor".to_string())])
+			.unwrap_err();
+		let hint = error.downcast_ref::<Hint>().unwrap();
+		assert_eq!(
+			hint.message,
+			"The field `mjaor` does not exist for objects of type build_info::semver::Version"
+		);
+		assert_eq!(hint.notes, vec!["Did you mean `major`?".to_string()]);
+	}
+
+	#[test]
+	fn unknown_method_hint() {
+		let error = true.call("to_strin", &[]).unwrap_err();
+		let hint = error.downcast_ref::<Hint>().unwrap();
+		assert_eq!(
+			hint.message,
+			"The method `to_strin` does not exist for objects of type bool"
+		);
+		assert_eq!(hint.notes, vec!["Did you mean `to_string`?".to_string()]);
+
+		let error = true.call("frobnicate", &[]).unwrap_err();
+		let hint = error.downcast_ref::<Hint>().unwrap();
+		assert_eq!(hint.notes, vec!["The available methods are: to_string".to_string()]);
+	}
+}
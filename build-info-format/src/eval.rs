@@ -0,0 +1,100 @@
+use num_bigint::BigInt;
+
+use super::{
+	OP_ARRAY_INDEX, OP_FIELD_ACCESS, OP_TUPLE_INDEX, Type, Value,
+	error::{EvalError, Result, UnwrapNone},
+	syntax::{AtomicExpr, Expr, Suffix},
+};
+
+pub trait Eval {
+	fn eval(&self) -> Result<Box<dyn Value>>;
+}
+
+impl Eval for AtomicExpr {
+	fn eval(&self) -> Result<Box<dyn Value>> {
+		match self {
+			AtomicExpr::LitBool(value, _) => Ok(Box::new(*value)),
+			AtomicExpr::LitChar(value, _) => Ok(Box::new(*value)),
+			AtomicExpr::LitInt(value, _) => Ok(Box::new(value.clone())),
+			AtomicExpr::LitTypedInt(value, int_type, meta) => {
+				super::value::typed_int(value, *int_type).map_err(|err| EvalError::new(meta.span, err))
+			}
+			AtomicExpr::LitFloat(digits, meta) => digits
+				.parse::<f64>()
+				.map(|value| Box::new(value) as Box<dyn Value>)
+				.map_err(|err| EvalError::new(meta.span, err.into())),
+			AtomicExpr::LitStr(value, _) => Ok(Box::new(value.clone())),
+			AtomicExpr::BuildInfo(_) => Ok(Box::new(super::build_info())),
+			AtomicExpr::Parenthesized(expr, _) => expr.eval(),
+			AtomicExpr::FunctionCall(name, args, meta) => {
+				let args: Result<Vec<Box<dyn Value>>> = args.iter().map(|expr| expr.eval()).collect();
+				super::value::call_function(name, &args?).map_err(|err| EvalError::new(meta.span, err))
+			}
+			AtomicExpr::MacroCall(name, args, meta) => {
+				let args: Result<Vec<_>> = args
+					.iter()
+					.map(|(name, expr)| Ok((name.as_ref().map(|id| id.to_string()), expr.eval()?)))
+					.collect();
+				super::value::call_macro(name, &args?).map_err(|err| EvalError::new(meta.span, err))
+			}
+			AtomicExpr::Stringify(tokens, _) => Ok(Box::new(tokens.clone())),
+			AtomicExpr::Cfg(predicate, meta) => super::value::eval_cfg(predicate, &super::build_info())
+				.map(|value| Box::new(value) as Box<dyn Value>)
+				.map_err(|err| EvalError::new(meta.span, err)),
+		}
+	}
+}
+
+impl Eval for Expr {
+	fn eval(&self) -> Result<Box<dyn Value>> {
+		let Some(fallback) = &self.fallback else {
+			return self.eval_without_fallback();
+		};
+
+		match self.eval_without_fallback() {
+			Ok(value) if value.get_type() == Type::Option => match value.call("?", &[]) {
+				Ok(value) => Ok(value),
+				Err(err) if err.is::<UnwrapNone>() => fallback.eval(),
+				Err(err) => Err(EvalError::new(self.meta().span, err)),
+			},
+			Err(err) if err.error.is::<UnwrapNone>() => fallback.eval(),
+			result => result,
+		}
+	}
+}
+
+impl Expr {
+	fn eval_without_fallback(&self) -> Result<Box<dyn Value>> {
+		let mut value = self.atom.eval()?;
+
+		for suffix in &self.suffixes {
+			let result = match suffix {
+				Suffix::Unwrap(_) => value.call("?", &[]),
+				Suffix::Field(name, _) => value.call(OP_FIELD_ACCESS, &[Box::new(name.clone())]),
+				Suffix::TupleIndex(index, _) => {
+					let index: BigInt = (*index).into();
+					value.call(OP_TUPLE_INDEX, &[Box::new(index)])
+				}
+				Suffix::ArrayIndex(expr, _) => value.call(OP_ARRAY_INDEX, &[expr.eval()?]),
+				Suffix::FunctionCall(name, args, _) => {
+					if args.iter().any(|(name, _arg)| name.is_some()) {
+						let args = args
+							.iter()
+							.map(|(name, arg)| Ok((name.as_ref().map(|id| id.to_string()), arg.eval()?)))
+							.collect::<Result<Vec<_>>>()?;
+						value.call_named(name, &args)
+					} else {
+						let args = args
+							.iter()
+							.map(|(_name, arg)| arg.eval())
+							.collect::<Result<Vec<Box<dyn Value>>>>()?;
+						value.call(name, &args)
+					}
+				}
+			};
+			value = result.map_err(|err| EvalError::new(suffix.meta().span, err))?;
+		}
+
+		Ok(value)
+	}
+}
@@ -0,0 +1,499 @@
+use std::{path::Path, str::Chars};
+
+use num_bigint::BigInt;
+use proc_macro2::Span;
+
+use super::{
+	Eval, OP_FIELD_ACCESS, OP_TUPLE_INDEX, Value,
+	error::{EvalError, Hint, Result},
+	spec::Spec,
+	syntax,
+	value::LoopInfo,
+};
+
+/// The format string being interpolated, which is either a string literal or the contents of a template file.
+pub struct Template<'a> {
+	pub text: &'a str,
+	pub path: Option<&'a Path>,
+	pub span: Span,
+}
+
+impl Template<'_> {
+	/// Describes where the most recently consumed character of `chars` is located in the template file, if any.
+	fn location(&self, chars: &Chars) -> Option<String> {
+		let path = self.path?;
+		// `chars` may iterate over a part of the template (e.g., the body of a loop), so the position is recovered from
+		// the address of the remaining text
+		let offset = chars.as_str().as_ptr() as usize - self.text.as_ptr() as usize;
+		let consumed = &self.text[..offset];
+		let start = consumed.char_indices().next_back().map_or(0, |(i, _c)| i);
+		let before = &self.text[..start];
+		let line = before.matches('\n').count() + 1;
+		let column = before[before.rfind('\n').map_or(0, |i| i + 1)..].chars().count() + 1;
+		Some(format!("In {}:{}:{}", path.display(), line, column))
+	}
+
+	/// Creates an error at the span of the template, which points into the template file if there is one.
+	fn error(&self, chars: &Chars, message: impl Into<String>, notes: &[&str]) -> EvalError {
+		let hint = Hint {
+			message: message.into(),
+			notes: notes.iter().map(|note| note.to_string()).collect(),
+		};
+		EvalError::new(self.span, hint.into()).at(self.span, self.location(chars))
+	}
+}
+
+const CLOSING_BRACE_EXPECTED: &str = "Invalid format string: unmatched `{` found";
+const CLOSING_BRACE_NOTE: &str = "If you intended to print `{`, you can escape it using `{{`.";
+const CLOSING_TAG_EXPECTED: &str = "Invalid format string: unmatched `{%` found";
+const CLOSING_TAG_NOTE: &str = "Tags such as `{% for x in xs %}` must be closed with `%}`";
+
+pub fn interpolate(format: &str, args: &[(Option<String>, Box<dyn Value>)], span: Span) -> Result<String> {
+	let template = Template {
+		text: format,
+		path: None,
+		span,
+	};
+	interpolate_template(&template, args)
+}
+
+pub fn interpolate_template(template: &Template, args: &[(Option<String>, Box<dyn Value>)]) -> Result<String> {
+	let mut res = String::with_capacity(template.text.len());
+	let mut interpolator = Interpolator {
+		template,
+		args,
+		argument_used: vec![false; args.len()],
+		implicit_position: 0,
+		scope: Vec::new(),
+	};
+	interpolator.render(&mut res, template.text)?;
+
+	if let Some(i) = interpolator.argument_used.iter().position(|used| !used) {
+		let message = match args[i].0 {
+			Some(ref name) => format!("Parameter `{name}` is not used in format string."),
+			None => format!("Parameter {i} is not used in format string."),
+		};
+		let notes = vec!["Positional arguments are zero-based".to_string()];
+		return Err(EvalError::new(template.span, Hint { message, notes }.into()));
+	}
+
+	Ok(res)
+}
+
+struct Interpolator<'a> {
+	template: &'a Template<'a>,
+	args: &'a [(Option<String>, Box<dyn Value>)],
+	argument_used: Vec<bool>,
+	implicit_position: usize,
+	/// Variables bound by the enclosing loops, innermost last
+	scope: Vec<(String, Box<dyn Value>)>,
+}
+
+impl<'a> Interpolator<'a> {
+	fn render(&mut self, buffer: &mut String, text: &'a str) -> Result<()> {
+		let mut chars = text.chars();
+		while let Some(c) = chars.next() {
+			if c == '{' {
+				let n = self.next(&mut chars)?;
+				if n == '{' {
+					buffer.push(c);
+				} else if n == '%' {
+					self.render_tag(buffer, &mut chars)?;
+				} else {
+					self.interpolate_once(buffer, n, &mut chars)?;
+				}
+			} else if c == '}' {
+				let n = chars.next();
+				if n == Some('}') {
+					buffer.push(c);
+				} else {
+					return Err(self.template.error(
+						&chars,
+						"Invalid format string: unmatched `}` found",
+						&["If you intended to print `}`, you can escape it using `}}`."],
+					));
+				}
+			} else {
+				buffer.push(c);
+			}
+		}
+		Ok(())
+	}
+
+	/// Consumes the next character of a placeholder, which must be closed before the end of the format string.
+	fn next(&self, chars: &mut Chars) -> Result<char> {
+		chars.next().ok_or_else(|| {
+			self
+				.template
+				.error(chars, CLOSING_BRACE_EXPECTED, &[CLOSING_BRACE_NOTE])
+		})
+	}
+
+	/// Renders a `{% ... %}` tag, whose opening `{%` has already been consumed from `chars`.
+	fn render_tag(&mut self, buffer: &mut String, chars: &mut Chars<'a>) -> Result<()> {
+		let rest = chars.as_str();
+		let end = rest
+			.find("%}")
+			.ok_or_else(|| self.template.error(chars, CLOSING_TAG_EXPECTED, &[CLOSING_TAG_NOTE]))?;
+		let tag = rest[..end].trim();
+		*chars = rest[end + 2..].chars();
+
+		if let Some((variable, expr)) = parse_for_tag(tag) {
+			let (body, after) = find_endfor(chars.as_str()).ok_or_else(|| {
+				self.template.error(
+					chars,
+					format!("`{{% {tag} %}}` is missing a matching `{{% endfor %}}`"),
+					&[],
+				)
+			})?;
+			let value = self.eval_loop_source(expr, chars)?;
+			let elements = value.elements().ok_or_else(|| {
+				self.template.error(
+					chars,
+					format!("Objects of type {} cannot be iterated over", value.get_type()),
+					&["Only values of type `Vec<_>` can be used in `{% for %}` loops"],
+				)
+			})?;
+
+			let length = elements.len();
+			for (index, element) in elements.into_iter().enumerate() {
+				self.scope.push((variable.to_string(), element));
+				self
+					.scope
+					.push(("loop".to_string(), Box::new(LoopInfo { index, length })));
+				self.render(buffer, body)?;
+				self.scope.truncate(self.scope.len() - 2);
+			}
+			*chars = after.chars();
+			Ok(())
+		} else if tag == "endfor" {
+			Err(
+				self
+					.template
+					.error(chars, "`{% endfor %}` without a matching `{% for %}`", &[]),
+			)
+		} else {
+			Err(self.template.error(
+				chars,
+				format!("Unknown tag `{{% {tag} %}}`"),
+				&["The only supported tags are `{% for x in xs %}` and `{% endfor %}`"],
+			))
+		}
+	}
+
+	/// Evaluates the expression after the `in` of a loop, which is either a path starting at a named argument or a loop
+	/// variable (e.g., `dep.dependencies`), or a full expression (e.g., `$.crate_info.dependencies`).
+	fn eval_loop_source(&mut self, expr: &str, chars: &Chars) -> Result<Box<dyn Value>> {
+		let span = self.template.span;
+		let mut segments = expr.split('.');
+		let name = segments.next().unwrap();
+		if is_identifier(name)
+			&& segments.clone().all(is_path_segment)
+			&& let Some(value) = self.lookup(name)
+		{
+			let path: Vec<&str> = segments.collect();
+			return self.apply_path(value, &path, chars);
+		}
+
+		let expr = syn::parse_str::<syntax::Expr>(expr).map_err(|err| {
+			self.template.error(
+				chars,
+				format!("Could not parse the loop expression `{expr}`"),
+				&[&format!("Caused by: {err}")],
+			)
+		})?;
+		expr.eval().map_err(|err| err.at(span, self.template.location(chars)))
+	}
+
+	/// Finds the value of a loop variable or a named argument.
+	fn lookup(&mut self, name: &str) -> Option<Box<dyn Value>> {
+		if let Some((_name, value)) = self.scope.iter().rev().find(|(variable, _value)| variable == name) {
+			return Some(value.clone_value());
+		}
+
+		let (pos, (_name, value)) = self
+			.args
+			.iter()
+			.enumerate()
+			.find(|(_i, (arg, _value))| arg.as_deref() == Some(name))?;
+		self.argument_used[pos] = true;
+		Some(value.clone_value())
+	}
+
+	fn apply_path(&self, mut value: Box<dyn Value>, path: &[&str], chars: &Chars) -> Result<Box<dyn Value>> {
+		let span = self.template.span;
+		for segment in path {
+			let result = if let Ok(index) = segment.parse::<u32>() {
+				value.call(OP_TUPLE_INDEX, &[Box::new(BigInt::from(index))])
+			} else {
+				value.call(OP_FIELD_ACCESS, &[Box::new(segment.to_string())])
+			};
+			value = result.map_err(|err| EvalError::new(span, err).at(span, self.template.location(chars)))?;
+		}
+		Ok(value)
+	}
+
+	fn interpolate_once(&mut self, buffer: &mut String, mut c: char, chars: &mut Chars) -> Result<()> {
+		let args = self.args;
+		let mut explicit_position = None;
+		let mut named = None;
+		let mut path = Vec::new();
+		if c.is_ascii_digit() {
+			let mut acc = 0;
+			while c.is_ascii_digit() {
+				acc = acc * 10 + c.to_digit(10).unwrap() as usize;
+				c = self.next(chars)?;
+			}
+			explicit_position = Some(acc);
+		} else if c.is_alphabetic() {
+			let mut acc = String::new();
+			while c.is_alphanumeric() || c == '_' || c == '.' {
+				acc.push(c);
+				c = self.next(chars)?;
+			}
+			let mut segments = acc.split('.').map(str::to_string);
+			named = segments.next();
+			path.extend(segments);
+		}
+
+		let arg = if let Some(pos) = explicit_position {
+			let arg = args
+				.get(pos)
+				.ok_or_else(|| {
+					self.template.error(
+						chars,
+						format!(
+							"Invalid reference to positional argument {} ({} arguments were given)",
+							pos,
+							args.len()
+						),
+						&["Positional arguments are zero-based"],
+					)
+				})?
+				.1
+				.clone_value();
+			self.argument_used[pos] = true;
+			arg
+		} else if let Some(named) = named {
+			if !path.iter().all(|segment| is_path_segment(segment)) {
+				return Err(self.template.error(
+					chars,
+					format!("Invalid path `{}.{}` in format string", named, path.join(".")),
+					&["Paths consist of names and tuple indices separated by `.`, e.g., `{dep.name}`"],
+				));
+			}
+			let arg = self.lookup(&named).ok_or_else(|| {
+				self
+					.template
+					.error(chars, format!("Invalid reference to named argument {named}"), &[])
+			})?;
+			let path: Vec<&str> = path.iter().map(String::as_str).collect();
+			self.apply_path(arg, &path, chars)?
+		} else {
+			if !self.scope.is_empty() {
+				return Err(self.template.error(
+					chars,
+					"Implicit positional arguments cannot be used inside of loops",
+					&["Use a loop variable, a named argument or an explicit position such as `{0}` instead"],
+				));
+			}
+			let arg = args
+				.get(self.implicit_position)
+				.ok_or_else(|| {
+					self.template.error(
+						chars,
+						format!(
+							"Invalid implicit reference to positional argument {} ({} arguments were given)",
+							self.implicit_position,
+							args.len()
+						),
+						&["Positional arguments are zero-based"],
+					)
+				})?
+				.1
+				.clone_value();
+			self.argument_used[self.implicit_position] = true;
+			self.implicit_position += 1;
+			arg
+		};
+
+		let spec = if c == ':' {
+			let mut text = String::new();
+			loop {
+				c = self.next(chars)?;
+				if c == '}' {
+					break;
+				}
+				text.push(c);
+			}
+			Spec::parse(&text).map_err(|err| {
+				self.template.error(
+					chars,
+					err,
+					&["Format specifiers look like `{:>8.2}`, `{:?}`, `{:#?}`, `{:list}` or `{:#list}`"],
+				)
+			})?
+		} else if c == '}' {
+			Spec::default()
+		} else {
+			return Err(self.template.error(
+				chars,
+				format!("Unexpected character {c:?} in format specifier."),
+				&[CLOSING_BRACE_NOTE],
+			));
+		};
+
+		*buffer += &spec.apply(&*arg).map_err(|err| self.template.error(chars, err, &[]))?;
+		Ok(())
+	}
+}
+
+/// Splits `for x in expr` into the variable name and the expression.
+fn parse_for_tag(tag: &str) -> Option<(&str, &str)> {
+	let rest = tag.strip_prefix("for")?;
+	if !rest.starts_with(char::is_whitespace) {
+		return None;
+	}
+	let (variable, expr) = rest.trim_start().split_once(char::is_whitespace)?;
+	let expr = expr.trim_start().strip_prefix("in")?;
+	if !expr.starts_with(char::is_whitespace) || !is_identifier(variable) {
+		return None;
+	}
+	Some((variable, expr.trim()))
+}
+
+/// Splits `text` at the `{% endfor %}` that closes the loop whose body starts at the beginning of `text`. Returns the
+/// loop body and the text after the closing tag.
+fn find_endfor(text: &str) -> Option<(&str, &str)> {
+	let mut depth = 0usize;
+	let mut rest = text;
+	while let Some(start) = rest.find('{') {
+		let after = &rest[start + 1..];
+		if let Some(after) = after.strip_prefix('{') {
+			rest = after;
+		} else if let Some(tag) = after.strip_prefix('%') {
+			let end = tag.find("%}")?;
+			let name = tag[..end].trim();
+			if parse_for_tag(name).is_some() {
+				depth += 1;
+			} else if name == "endfor" {
+				if depth == 0 {
+					let body_end = text.len() - rest.len() + start;
+					return Some((&text[..body_end], &tag[end + 2..]));
+				}
+				depth -= 1;
+			}
+			rest = &tag[end + 2..];
+		} else {
+			rest = after;
+		}
+	}
+	None
+}
+
+fn is_identifier(name: &str) -> bool {
+	let mut chars = name.chars();
+	chars.next().is_some_and(char::is_alphabetic) && chars.all(|c| c.is_alphanumeric() || c == '_')
+}
+
+fn is_path_segment(segment: &str) -> bool {
+	is_identifier(segment) || (!segment.is_empty() && segment.chars().all(|c| c.is_ascii_digit()))
+}
+
+#[cfg(test)]
+mod test {
+	use pretty_assertions::assert_eq;
+
+	use super::*;
+
+	#[test]
+	fn template_location() {
+		let text = "Line one\nLine {two}\n";
+		let template = Template {
+			text,
+			path: Some(Path::new("version.txt")),
+			span: Span::call_site(),
+		};
+
+		let mut chars = text.chars();
+		chars.nth(14);
+		assert_eq!(template.location(&chars).as_deref(), Some("In version.txt:2:6"));
+
+		let template = Template { path: None, ..template };
+		assert_eq!(template.location(&chars), None);
+	}
+
+	#[test]
+	fn for_tags() {
+		assert_eq!(
+			parse_for_tag("for dep in $.crate_info.dependencies"),
+			Some(("dep", "$.crate_info.dependencies"))
+		);
+		assert_eq!(parse_for_tag("for  x  in  xs "), Some(("x", "xs")));
+		assert_eq!(parse_for_tag("format x in xs"), None);
+		assert_eq!(parse_for_tag("for x inside xs"), None);
+		assert_eq!(parse_for_tag("endfor"), None);
+	}
+
+	#[test]
+	fn nested_loops() {
+		let text = "{a}{% for b in c %}{{%{b}{% endfor %}!{% endfor %} tail";
+		assert_eq!(
+			find_endfor(text),
+			Some(("{a}{% for b in c %}{{%{b}{% endfor %}!", " tail"))
+		);
+		assert_eq!(find_endfor("{% for x in y %}"), None);
+	}
+
+	#[test]
+	fn loops() {
+		let args: Vec<(Option<String>, Box<dyn Value>)> = vec![(
+			Some("deps".to_string()),
+			Box::new(vec!["a".to_string(), "b".to_string(), "c".to_string()]),
+		)];
+		let interpolate = |format| interpolate(format, &args, Span::call_site()).unwrap();
+		assert_eq!(
+			interpolate("{% for dep in deps %}{loop.index}: {dep}{% endfor %}"),
+			"0: a1: b2: c"
+		);
+		assert_eq!(
+			interpolate("{% for dep in deps %}{dep:?}={loop.last} {% endfor %}"),
+			"\"a\"=false \"b\"=false \"c\"=true "
+		);
+		assert_eq!(interpolate("{deps:list}, {deps:#list}"), "a, b and c, a, b, and c");
+		assert_eq!(
+			interpolate("{% for x in deps %}{% for y in deps %}{x}{y}.{% endfor %}{% endfor %}"),
+			"aa.ab.ac.ba.bb.bc.ca.cb.cc."
+		);
+	}
+
+	#[test]
+	fn errors() {
+		let args: Vec<(Option<String>, Box<dyn Value>)> = vec![(None, Box::new("a".to_string()))];
+		let error = |format| interpolate(format, &args, Span::call_site()).unwrap_err().to_string();
+		assert_eq!(
+			error("{"),
+			"Invalid format string: unmatched `{` found\n  = note: If you intended to print `{`, you can escape it using `{{`."
+		);
+		assert_eq!(
+			error("{} {}"),
+			"Invalid implicit reference to positional argument 1 (1 arguments were given)\n  = note: Positional arguments \
+			 are zero-based"
+		);
+		assert_eq!(
+			error("text"),
+			"Parameter 0 is not used in format string.\n  = note: Positional arguments are zero-based"
+		);
+
+		let text = "{0}\n{% endfor %}";
+		let template = Template {
+			text,
+			path: Some(Path::new("version.txt")),
+			span: Span::call_site(),
+		};
+		assert_eq!(
+			interpolate_template(&template, &args).unwrap_err().to_string(),
+			"`{% endfor %}` without a matching `{% for %}`\n  = note: In version.txt:2:12"
+		);
+	}
+}
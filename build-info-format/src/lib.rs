@@ -0,0 +1,100 @@
+/*!
+Part of the build-info crate: This crate implements the language of `build_info::format!`, i.e., it parses and
+evaluates the arguments and interpolates the format string. It is used by the proc-macros to format the build
+information at compile time and by the `build-info` command to format the build information that was embedded into a
+binary.
+*/
+
+#![forbid(unsafe_code)]
+
+extern crate proc_macro;
+
+use std::cell::RefCell;
+
+use build_info_common::BuildInfo;
+use proc_macro2::Span;
+
+pub mod error;
+use error::{EvalError, Hint, Result};
+
+mod eval;
+pub use eval::Eval;
+
+mod interpolate;
+pub use interpolate::{Template, interpolate, interpolate_template};
+
+mod spec;
+
+pub mod syntax;
+
+mod types;
+pub use types::Type;
+
+mod value;
+pub use value::{FormatSpecifier, Value, take_included_files};
+use value::{OP_ARRAY_INDEX, OP_FIELD_ACCESS, OP_TUPLE_INDEX};
+
+thread_local! {
+	/// The build information that `$` refers to while `with_build_info` is running
+	static BUILD_INFO: RefCell<Option<BuildInfo>> = const { RefCell::new(None) };
+}
+
+/// Runs `f` with `build_info` as the value of `$`.
+pub fn with_build_info<T>(build_info: BuildInfo, f: impl FnOnce() -> T) -> T {
+	let outer = BUILD_INFO.replace(Some(build_info));
+	let result = f();
+	BUILD_INFO.set(outer);
+	result
+}
+
+/// The value of `$`, which must have been set by `with_build_info`.
+fn build_info() -> BuildInfo {
+	BUILD_INFO.with_borrow(|build_info| {
+		build_info
+			.clone()
+			.expect("`$` is only available inside `with_build_info`")
+	})
+}
+
+/// Evaluates the arguments and performs the string interpolation, which is shared by all `format!`-like macros.
+pub fn format_to_string(syntax: &syntax::Syntax) -> Result<String> {
+	let values = eval_arguments(syntax)?;
+
+	if values.is_empty() {
+		Ok(build_info().to_string())
+	} else {
+		let format = first_argument_as_string(syntax, &values)?;
+		interpolate(format, &values[1..], Span::call_site())
+	}
+}
+
+/// The values of the arguments of a macro, along with their names if they are named arguments
+pub type Arguments = Vec<(Option<String>, Box<dyn Value>)>;
+
+pub fn eval_arguments(syntax: &syntax::Syntax) -> Result<Arguments> {
+	syntax
+		.args
+		.iter()
+		.map(|(name, expr)| Ok((name.as_ref().map(|id| id.to_string()), expr.eval()?)))
+		.collect()
+}
+
+pub fn first_argument_as_string<'a>(
+	syntax: &syntax::Syntax,
+	values: &'a [(Option<String>, Box<dyn Value>)],
+) -> Result<&'a String> {
+	let span = syntax.args[0].1.meta().span;
+	if values[0].0.is_some() {
+		return Err(EvalError::new(
+			span,
+			anyhow::anyhow!("The first argument cannot be named (it should be a positional argument containing a string)"),
+		));
+	}
+	values[0].1.as_any().downcast_ref::<String>().ok_or_else(|| {
+		let hint = Hint {
+			message: "Could not interpret first argument as a string".to_string(),
+			notes: vec![format!("It has type {}", values[0].1.get_type())],
+		};
+		EvalError::new(span, hint.into())
+	})
+}
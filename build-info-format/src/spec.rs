@@ -0,0 +1,220 @@
+use super::{FormatSpecifier, Value, value::join_human};
+
+#[derive(Copy, Clone, Eq, PartialEq, Debug)]
+pub(crate) enum Align {
+	Left,
+	Center,
+	Right,
+}
+
+#[derive(Copy, Clone, Eq, PartialEq, Debug)]
+pub(crate) enum Kind {
+	Display,
+	Debug,
+	List,
+}
+
+/// The format specification of a placeholder, i.e., everything after the `:` in `{x:>8.2}`. It follows the syntax of
+/// `std::fmt`: `[[fill]align][sign]['#']['0'][width]['.' precision][type]`, where the type is either empty, `?` or
+/// `list`.
+#[derive(Clone, Eq, PartialEq, Debug)]
+pub(crate) struct Spec {
+	pub(crate) fill: char,
+	pub(crate) align: Option<Align>,
+	pub(crate) sign_plus: bool,
+	pub(crate) alternate: bool,
+	pub(crate) zero: bool,
+	pub(crate) width: Option<usize>,
+	pub(crate) precision: Option<usize>,
+	pub(crate) kind: Kind,
+}
+
+impl Default for Spec {
+	fn default() -> Self {
+		Self {
+			fill: ' ',
+			align: None,
+			sign_plus: false,
+			alternate: false,
+			zero: false,
+			width: None,
+			precision: None,
+			kind: Kind::Display,
+		}
+	}
+}
+
+fn as_align(c: char) -> Option<Align> {
+	match c {
+		'<' => Some(Align::Left),
+		'^' => Some(Align::Center),
+		'>' => Some(Align::Right),
+		_ => None,
+	}
+}
+
+impl Spec {
+	pub(crate) fn parse(spec: &str) -> Result<Self, String> {
+		let mut result = Spec::default();
+		let chars: Vec<char> = spec.chars().collect();
+		let mut i = 0;
+
+		if let Some(align) = chars.get(1).copied().and_then(as_align) {
+			result.fill = chars[0];
+			result.align = Some(align);
+			i = 2;
+		} else if let Some(align) = chars.first().copied().and_then(as_align) {
+			result.align = Some(align);
+			i = 1;
+		}
+
+		if chars.get(i) == Some(&'+') {
+			result.sign_plus = true;
+			i += 1;
+		} else if chars.get(i) == Some(&'-') {
+			i += 1;
+		}
+
+		if chars.get(i) == Some(&'#') {
+			result.alternate = true;
+			i += 1;
+		}
+
+		if chars.get(i) == Some(&'0') {
+			result.zero = true;
+			i += 1;
+		}
+
+		let (width, next) = parse_number(&chars, i);
+		result.width = width;
+		i = next;
+
+		if chars.get(i) == Some(&'.') {
+			let (precision, next) = parse_number(&chars, i + 1);
+			if precision.is_none() {
+				return Err("Expected a precision after `.` in format specifier".to_string());
+			}
+			result.precision = precision;
+			i = next;
+		}
+
+		let kind: String = chars[i..].iter().collect();
+		result.kind = match kind.as_str() {
+			"" => Kind::Display,
+			"?" => Kind::Debug,
+			"list" => Kind::List,
+			_ => return Err(format!("Unknown format type `{kind}`")),
+		};
+
+		Ok(result)
+	}
+
+	/// Formats `value` according to this specification.
+	pub(crate) fn apply(&self, value: &dyn Value) -> Result<String, String> {
+		let mut text = String::new();
+		match self.kind {
+			Kind::Display => match self.precision {
+				Some(precision) => value.format_precision(&mut text, precision),
+				None => value.format(&mut text, FormatSpecifier::Default),
+			},
+			Kind::Debug if self.alternate => value.format(&mut text, FormatSpecifier::DebugAlt),
+			Kind::Debug => value.format(&mut text, FormatSpecifier::Debug),
+			Kind::List => {
+				// `{:list}` joins the elements as in "a, b and c", while `{:#list}` adds an Oxford comma
+				let elements = value
+					.elements()
+					.ok_or_else(|| format!("Objects of type {} cannot be formatted as a list", value.get_type()))?;
+				let items: Vec<String> = elements
+					.iter()
+					.map(|element| {
+						let mut buffer = String::new();
+						element.format(&mut buffer, FormatSpecifier::Default);
+						buffer
+					})
+					.collect();
+				text = join_human(&items, "and", self.alternate);
+			}
+		}
+
+		let numeric = value.get_type().is_numeric();
+		if self.sign_plus && numeric && !text.starts_with('-') {
+			text.insert(0, '+');
+		}
+
+		let len = text.chars().count();
+		if let Some(width) = self.width.filter(|width| *width > len) {
+			let padding = width - len;
+			if self.zero && numeric {
+				let sign = usize::from(text.starts_with(['+', '-']));
+				text.insert_str(sign, &"0".repeat(padding));
+			} else {
+				let align = self.align.unwrap_or(if numeric { Align::Right } else { Align::Left });
+				let before = match align {
+					Align::Left => 0,
+					Align::Center => padding / 2,
+					Align::Right => padding,
+				};
+				let fill = |n| std::iter::repeat_n(self.fill, n);
+				text = fill(before).chain(text.chars()).chain(fill(padding - before)).collect();
+			}
+		}
+
+		Ok(text)
+	}
+}
+
+fn parse_number(chars: &[char], start: usize) -> (Option<usize>, usize) {
+	let mut end = start;
+	while chars.get(end).is_some_and(char::is_ascii_digit) {
+		end += 1;
+	}
+	let number = chars[start..end].iter().collect::<String>().parse().ok();
+	(number, end)
+}
+
+#[cfg(test)]
+mod test {
+	use num_bigint::BigInt;
+	use pretty_assertions::assert_eq;
+
+	use super::*;
+
+	fn apply(spec: &str, value: &dyn Value) -> String {
+		Spec::parse(spec).unwrap().apply(value).unwrap()
+	}
+
+	#[test]
+	fn parse() {
+		assert_eq!(Spec::parse(""), Ok(Spec::default()));
+		assert_eq!(
+			Spec::parse("*^+#08.3?"),
+			Ok(Spec {
+				fill: '*',
+				align: Some(Align::Center),
+				sign_plus: true,
+				alternate: true,
+				zero: true,
+				width: Some(8),
+				precision: Some(3),
+				kind: Kind::Debug,
+			})
+		);
+		assert_eq!(Spec::parse("#list").map(|spec| spec.kind), Ok(Kind::List));
+		assert!(Spec::parse("x").is_err());
+		assert!(Spec::parse(".").is_err());
+	}
+
+	#[test]
+	fn width_and_precision() {
+		assert_eq!(apply("8", &"ab".to_string()), "ab      ");
+		assert_eq!(apply(">8", &"ab".to_string()), "      ab");
+		assert_eq!(apply("-^7", &"ab".to_string()), "--ab---");
+		assert_eq!(apply(".1", &"ab".to_string()), "a");
+		assert_eq!(apply("5", &BigInt::from(42)), "   42");
+		assert_eq!(apply("+05", &BigInt::from(42)), "+0042");
+		assert_eq!(apply("05", &-42i8), "-0042");
+		assert_eq!(apply(".2", &(100.0f64 / 3.0)), "33.33");
+		assert_eq!(apply("8.1", &12.345f64), "    12.3");
+		assert_eq!(apply(".2", &BigInt::from(7)), "7");
+	}
+}
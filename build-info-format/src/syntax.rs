@@ -0,0 +1,658 @@
+use std::{
+	cmp::Ordering,
+	hash::{Hash, Hasher},
+};
+
+use num_bigint::BigInt;
+use proc_macro2::{Delimiter, Span, TokenTree};
+use syn::{
+	Attribute, Ident, LitBool, LitChar, LitFloat, LitInt, LitStr, Token, Visibility, braced, bracketed, ext::IdentExt,
+	parenthesized, parse,
+};
+
+use super::types::IntType;
+
+#[derive(Clone, Debug)]
+pub struct Meta {
+	pub span: Span,
+}
+
+impl PartialOrd for Meta {
+	fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+		Some(self.cmp(other))
+	}
+}
+
+impl Ord for Meta {
+	fn cmp(&self, _other: &Self) -> Ordering {
+		Ordering::Equal
+	}
+}
+
+impl PartialEq for Meta {
+	fn eq(&self, _other: &Self) -> bool {
+		true
+	}
+}
+
+impl Eq for Meta {}
+
+impl Hash for Meta {
+	fn hash<H: Hasher>(&self, _state: &mut H) {}
+}
+
+impl Default for Meta {
+	fn default() -> Self {
+		Self {
+			span: Span::call_site(),
+		}
+	}
+}
+
+#[derive(Clone, Eq, PartialEq, Ord, PartialOrd, Hash, Debug)]
+pub struct Syntax {
+	pub args: Vec<(Option<Ident>, Expr)>,
+}
+
+/// The input of `format_const!`: `[attributes] [visibility] const NAME = "format string", args...`
+pub struct ConstItem {
+	pub attrs: Vec<Attribute>,
+	pub vis: Visibility,
+	pub ident: Ident,
+	pub syntax: Syntax,
+}
+
+#[derive(Clone, Eq, PartialEq, Ord, PartialOrd, Hash, Debug)]
+pub struct Expr {
+	pub(crate) atom: AtomicExpr,
+	pub(crate) suffixes: Vec<Suffix>,
+	/// The right-hand side of `??`, which is used if unwrapping an `Option` fails
+	pub(crate) fallback: Option<Box<Expr>>,
+}
+
+impl Expr {
+	pub fn meta(&self) -> &Meta {
+		self.atom.meta()
+	}
+}
+
+#[derive(Clone, Eq, PartialEq, Ord, PartialOrd, Hash, Debug)]
+pub(crate) enum AtomicExpr {
+	BuildInfo(Meta),
+	LitBool(bool, Meta),
+	LitInt(BigInt, Meta),
+	LitTypedInt(BigInt, IntType, Meta),
+	/// The digits of a float literal, which is kept as a string to keep the syntax tree `Eq`
+	LitFloat(String, Meta),
+	LitChar(char, Meta),
+	LitStr(String, Meta),
+	Parenthesized(Box<Expr>, Meta),
+	FunctionCall(String, Vec<Expr>, Meta),
+	MacroCall(String, Vec<(Option<Ident>, Expr)>, Meta),
+	/// `stringify!(...)`, whose arguments are kept as tokens instead of being parsed as expressions
+	Stringify(String, Meta),
+	Cfg(CfgPredicate, Meta),
+}
+
+impl AtomicExpr {
+	pub fn meta(&self) -> &Meta {
+		match self {
+			AtomicExpr::BuildInfo(meta) => meta,
+			AtomicExpr::LitBool(_, meta) => meta,
+			AtomicExpr::LitInt(_, meta) => meta,
+			AtomicExpr::LitTypedInt(.., meta) => meta,
+			AtomicExpr::LitFloat(_, meta) => meta,
+			AtomicExpr::LitChar(_, meta) => meta,
+			AtomicExpr::LitStr(_, meta) => meta,
+			AtomicExpr::Parenthesized(_, meta) => meta,
+			AtomicExpr::FunctionCall(.., meta) => meta,
+			AtomicExpr::MacroCall(.., meta) => meta,
+			AtomicExpr::Stringify(_, meta) => meta,
+			AtomicExpr::Cfg(_, meta) => meta,
+		}
+	}
+}
+
+#[derive(Clone, Eq, PartialEq, Ord, PartialOrd, Hash, Debug)]
+pub(crate) enum Suffix {
+	Unwrap(Meta),
+	Field(String, Meta),
+	TupleIndex(u32, Meta),
+	ArrayIndex(Box<Expr>, Meta),
+	FunctionCall(String, Vec<(Option<Ident>, Expr)>, Meta),
+}
+
+impl Suffix {
+	pub fn meta(&self) -> &Meta {
+		match self {
+			Suffix::Unwrap(meta) => meta,
+			Suffix::Field(_, meta) => meta,
+			Suffix::TupleIndex(_, meta) => meta,
+			Suffix::ArrayIndex(_, meta) => meta,
+			Suffix::FunctionCall(.., meta) => meta,
+		}
+	}
+}
+
+impl parse::Parse for Syntax {
+	fn parse(input: parse::ParseStream) -> parse::Result<Self> {
+		let args = parse_named_arguments(input)?;
+
+		Ok(Self { args })
+	}
+}
+
+impl parse::Parse for ConstItem {
+	fn parse(input: parse::ParseStream) -> parse::Result<Self> {
+		let attrs = input.call(Attribute::parse_outer)?;
+		let vis = input.parse::<Visibility>()?;
+		input.parse::<Token![const]>()?;
+		let ident = input.parse::<Ident>()?;
+		input.parse::<Token![=]>()?;
+		let syntax = input.parse::<Syntax>()?;
+
+		Ok(Self {
+			attrs,
+			vis,
+			ident,
+			syntax,
+		})
+	}
+}
+
+fn parse_simple_arguments(input: parse::ParseStream) -> parse::Result<Vec<Expr>> {
+	let result = input.parse_terminated(parse::Parse::parse, Token![,])?;
+	Ok(result.into_pairs().map(|pair| pair.into_tuple().0).collect())
+}
+
+fn parse_named_arguments(input: parse::ParseStream) -> parse::Result<Vec<(Option<Ident>, Expr)>> {
+	let result = input.parse_terminated(parse_named_argument, Token![,])?;
+
+	let mut named = Vec::new();
+	for (name, expr) in result.iter() {
+		if name.is_none() {
+			if !named.is_empty() {
+				// TODO: use the spans in `named` to also point to the previous named args
+				return Err(syn::Error::new(
+					expr.meta().span,
+					"Positional arguments must be before named arguments",
+				));
+			}
+		} else {
+			named.push(expr.meta().span);
+		}
+	}
+
+	Ok(result.into_pairs().map(|pair| pair.into_tuple().0).collect())
+}
+
+fn parse_named_argument(input: parse::ParseStream) -> parse::Result<(Option<Ident>, Expr)> {
+	if input.peek(Ident) && input.peek2(Token![=]) {
+		let id = input.parse::<Ident>()?;
+		input.parse::<Token![=]>()?;
+		Ok((Some(id), input.parse()?))
+	} else {
+		Ok((None, input.parse()?))
+	}
+}
+
+impl parse::Parse for AtomicExpr {
+	fn parse(input: parse::ParseStream) -> parse::Result<Self> {
+		let lookahead = input.lookahead1();
+		if lookahead.peek(Token![$]) {
+			let token = input.parse::<Token![$]>()?;
+			Ok(AtomicExpr::BuildInfo(Meta { span: token.spans[0] }))
+		} else if lookahead.peek(syn::token::Paren) {
+			let expr;
+			parenthesized!(expr in input);
+			Ok(AtomicExpr::Parenthesized(
+				Box::new(expr.parse::<Expr>()?),
+				Meta { span: expr.span() },
+			))
+		} else if lookahead.peek(LitBool) {
+			let lit_bool = input.parse::<LitBool>()?;
+			Ok(AtomicExpr::LitBool(lit_bool.value, Meta { span: lit_bool.span }))
+		} else if lookahead.peek(LitChar) {
+			let lit_char = input.parse::<LitChar>()?;
+			Ok(AtomicExpr::LitChar(lit_char.value(), Meta { span: lit_char.span() }))
+		} else if lookahead.peek(LitInt) {
+			let lit_int = input.parse::<LitInt>()?;
+			let value = lit_int.base10_parse::<BigInt>()?;
+			let meta = Meta { span: lit_int.span() };
+			match lit_int.suffix() {
+				"" => Ok(AtomicExpr::LitInt(value, meta)),
+				"f64" => Ok(AtomicExpr::LitFloat(lit_int.base10_digits().to_string(), meta)),
+				suffix => {
+					let int_type = IntType::from_suffix(suffix)
+						.ok_or_else(|| syn::Error::new(lit_int.span(), format!("Unsupported integer suffix `{suffix}`")))?;
+					Ok(AtomicExpr::LitTypedInt(value, int_type, meta))
+				}
+			}
+		} else if lookahead.peek(LitFloat) {
+			let lit_float = input.parse::<LitFloat>()?;
+			if !matches!(lit_float.suffix(), "" | "f64") {
+				return Err(syn::Error::new(
+					lit_float.span(),
+					"Only floats of type f64 are supported in [build-info]",
+				));
+			}
+			Ok(AtomicExpr::LitFloat(
+				lit_float.base10_digits().to_string(),
+				Meta { span: lit_float.span() },
+			))
+		} else if lookahead.peek(LitStr) {
+			let lit_str = input.parse::<LitStr>()?;
+			Ok(AtomicExpr::LitStr(lit_str.value(), Meta { span: lit_str.span() }))
+		} else if lookahead.peek(Ident) || lookahead.peek(Token![typeof]) {
+			// `typeof` is a reserved keyword, but can be called as a function inside of `format!`
+			let id = input.call(Ident::parse_any)?;
+
+			let lookahead = input.lookahead1();
+			if lookahead.peek(syn::token::Paren) {
+				let arguments;
+				parenthesized!(arguments in input);
+				let arguments = parse_simple_arguments(&arguments)?;
+				Ok(AtomicExpr::FunctionCall(
+					id.to_string(),
+					arguments,
+					Meta { span: id.span() },
+				))
+			} else if lookahead.peek(Token![!]) {
+				input.parse::<Token![!]>()?;
+				if id == "stringify" || id == "cfg" {
+					let group = match input.parse::<TokenTree>()? {
+						TokenTree::Group(group) if group.delimiter() != Delimiter::None => group,
+						tree => return Err(parse::Error::new(tree.span(), "Expected `(`, `[` or `{`")),
+					};
+					let meta = Meta { span: id.span() };
+					return if id == "stringify" {
+						Ok(AtomicExpr::Stringify(group.stream().to_string(), meta))
+					} else {
+						Ok(AtomicExpr::Cfg(syn::parse2(group.stream())?, meta))
+					};
+				}
+				let lookahead = input.lookahead1();
+				let arguments = if lookahead.peek(syn::token::Paren) {
+					let arguments;
+					parenthesized!(arguments in input);
+					parse_named_arguments(&arguments)?
+				} else if lookahead.peek(syn::token::Brace) {
+					let arguments;
+					braced!(arguments in input);
+					parse_named_arguments(&arguments)?
+				} else if lookahead.peek(syn::token::Bracket) {
+					let arguments;
+					bracketed!(arguments in input);
+					parse_named_arguments(&arguments)?
+				} else {
+					return Err(lookahead.error());
+				};
+				Ok(AtomicExpr::MacroCall(
+					id.to_string(),
+					arguments,
+					Meta { span: id.span() },
+				))
+			} else {
+				Err(lookahead.error())
+			}
+		} else {
+			Err(lookahead.error())
+		}
+	}
+}
+
+/// The predicate of `cfg!`, e.g., `all(unix, feature = "serde")`
+#[derive(Clone, Eq, PartialEq, Ord, PartialOrd, Hash, Debug)]
+pub(crate) enum CfgPredicate {
+	Name(String),
+	KeyValue(String, String),
+	All(Vec<CfgPredicate>),
+	Any(Vec<CfgPredicate>),
+	Not(Box<CfgPredicate>),
+}
+
+impl parse::Parse for CfgPredicate {
+	fn parse(input: parse::ParseStream) -> parse::Result<Self> {
+		let id = input.call(Ident::parse_any)?;
+		if input.peek(Token![=]) {
+			input.parse::<Token![=]>()?;
+			let value = input.parse::<LitStr>()?;
+			Ok(CfgPredicate::KeyValue(id.to_string(), value.value()))
+		} else if input.peek(syn::token::Paren) {
+			let arguments;
+			parenthesized!(arguments in input);
+			let mut predicates: Vec<CfgPredicate> = arguments
+				.parse_terminated(CfgPredicate::parse, Token![,])?
+				.into_iter()
+				.collect();
+			match id.to_string().as_str() {
+				"all" => Ok(CfgPredicate::All(predicates)),
+				"any" => Ok(CfgPredicate::Any(predicates)),
+				"not" if predicates.len() == 1 => Ok(CfgPredicate::Not(Box::new(predicates.remove(0)))),
+				"not" => Err(parse::Error::new(id.span(), "`not` expects exactly one predicate")),
+				_ => Err(parse::Error::new(
+					id.span(),
+					format!("Unknown cfg predicate `{id}` (expected `all`, `any` or `not`)"),
+				)),
+			}
+		} else {
+			Ok(CfgPredicate::Name(id.to_string()))
+		}
+	}
+}
+
+impl parse::Parse for Expr {
+	fn parse(input: parse::ParseStream) -> parse::Result<Self> {
+		let atom = input.parse::<AtomicExpr>()?;
+
+		let mut suffixes = Vec::new();
+		let mut fallback = None;
+		while !input.is_empty() {
+			let lookahead = input.lookahead1();
+			if lookahead.peek(Token![,]) {
+				break;
+			} else if peek_fallback(input) {
+				input.parse::<Token![?]>()?;
+				input.parse::<Token![?]>()?;
+				fallback = Some(Box::new(input.parse::<Expr>()?));
+				break;
+			} else if lookahead.peek(Token![?]) {
+				let token = input.parse::<Token![?]>()?;
+				suffixes.push(Suffix::Unwrap(Meta { span: token.span }));
+			} else if lookahead.peek(Token![.]) {
+				input.parse::<Token![.]>()?;
+				let lookahead = input.lookahead1();
+				if lookahead.peek(Ident) {
+					let id = input.parse::<Ident>()?;
+
+					let lookahead = input.lookahead1();
+					if lookahead.peek(syn::token::Paren) {
+						let arguments;
+						parenthesized!(arguments in input);
+						let arguments = parse_named_arguments(&arguments)?;
+						suffixes.push(Suffix::FunctionCall(
+							id.to_string(),
+							arguments,
+							Meta { span: id.span() },
+						));
+					} else {
+						suffixes.push(Suffix::Field(id.to_string(), Meta { span: id.span() }));
+					}
+				} else if lookahead.peek(LitInt) {
+					let tuple_index = input.parse::<LitInt>()?;
+					suffixes.push(Suffix::TupleIndex(
+						tuple_index.base10_parse()?,
+						Meta {
+							span: tuple_index.span(),
+						},
+					));
+				} else {
+					return Err(lookahead.error());
+				}
+			} else if lookahead.peek(syn::token::Bracket) {
+				let expr;
+				let bracket = bracketed!(expr in input);
+				let expr = expr.parse::<Expr>()?;
+				suffixes.push(Suffix::ArrayIndex(
+					Box::new(expr),
+					Meta {
+						span: bracket.span.join(),
+					},
+				));
+			} else {
+				return Err(lookahead.error());
+			}
+		}
+
+		Ok(Self {
+			atom,
+			suffixes,
+			fallback,
+		})
+	}
+}
+
+/// `??` is only a fallback operator if it is followed by an expression, as `x??` may also unwrap twice.
+fn peek_fallback(input: parse::ParseStream) -> bool {
+	input.peek(Token![?])
+		&& input.peek2(Token![?])
+		&& (input.peek3(Token![$])
+			|| input.peek3(syn::token::Paren)
+			|| input.peek3(LitBool)
+			|| input.peek3(LitChar)
+			|| input.peek3(LitInt)
+			|| input.peek3(LitFloat)
+			|| input.peek3(LitStr)
+			|| input.peek3(Ident))
+}
+
+#[cfg(test)]
+mod test {
+	use pretty_assertions::assert_eq;
+	use quote::quote;
+
+	use super::*;
+
+	#[test]
+	fn no_format() -> anyhow::Result<()> {
+		let format = "This is a $test".to_string();
+		let ast = quote! {#format};
+		let result = syn::parse2::<Syntax>(ast)?;
+		assert_eq!(
+			result,
+			Syntax {
+				args: vec![(
+					None,
+					Expr {
+						atom: AtomicExpr::LitStr(format, Meta::default()),
+						suffixes: vec![],
+						fallback: None,
+					}
+				)],
+			}
+		);
+
+		Ok(())
+	}
+
+	#[test]
+	fn format_self() -> anyhow::Result<()> {
+		let format = "{}".to_string();
+		let ast = quote! {#format, $};
+		let result = syn::parse2::<Syntax>(ast)?;
+		assert_eq!(
+			result,
+			Syntax {
+				args: vec![
+					(
+						None,
+						Expr {
+							atom: AtomicExpr::LitStr(format, Meta::default()),
+							suffixes: vec![],
+							fallback: None,
+						}
+					),
+					(
+						None,
+						Expr {
+							atom: AtomicExpr::BuildInfo(Meta::default()),
+							suffixes: vec![],
+							fallback: None,
+						}
+					)
+				]
+			}
+		);
+
+		Ok(())
+	}
+
+	#[test]
+	fn format_suffixes() -> anyhow::Result<()> {
+		let format = "{}".to_string();
+		let ast = quote! {#format, $.foo().7[0x0_C].foo};
+		let result = syn::parse2::<Syntax>(ast)?;
+		assert_eq!(
+			result,
+			Syntax {
+				args: vec![
+					(
+						None,
+						Expr {
+							atom: AtomicExpr::LitStr(format, Meta::default()),
+							suffixes: vec![],
+							fallback: None,
+						}
+					),
+					(
+						None,
+						Expr {
+							atom: AtomicExpr::BuildInfo(Meta::default()),
+							suffixes: vec![
+								Suffix::FunctionCall("foo".to_string(), vec![], Meta::default()),
+								Suffix::TupleIndex(7, Meta::default()),
+								Suffix::ArrayIndex(
+									Box::new(Expr {
+										atom: AtomicExpr::LitInt(12.into(), Meta::default()),
+										suffixes: vec![],
+										fallback: None,
+									}),
+									Meta::default()
+								),
+								Suffix::Field("foo".to_string(), Meta::default())
+							],
+							fallback: None,
+						}
+					)
+				],
+			}
+		);
+
+		Ok(())
+	}
+
+	#[test]
+	fn format_fallback() -> anyhow::Result<()> {
+		let ast = quote! {$.foo?? "unknown", $.bar??};
+		let result = syn::parse2::<Syntax>(ast)?;
+		assert_eq!(
+			result,
+			Syntax {
+				args: vec![
+					(
+						None,
+						Expr {
+							atom: AtomicExpr::BuildInfo(Meta::default()),
+							suffixes: vec![Suffix::Field("foo".to_string(), Meta::default())],
+							fallback: Some(Box::new(Expr {
+								atom: AtomicExpr::LitStr("unknown".to_string(), Meta::default()),
+								suffixes: vec![],
+								fallback: None,
+							})),
+						}
+					),
+					(
+						None,
+						Expr {
+							atom: AtomicExpr::BuildInfo(Meta::default()),
+							suffixes: vec![
+								Suffix::Field("bar".to_string(), Meta::default()),
+								Suffix::Unwrap(Meta::default()),
+								Suffix::Unwrap(Meta::default())
+							],
+							fallback: None,
+						}
+					)
+				],
+			}
+		);
+
+		Ok(())
+	}
+
+	#[test]
+	fn const_item() -> anyhow::Result<()> {
+		let ast = quote! {
+			/// The crate version
+			pub(crate) const VERSION = "{}", $.crate_info.version
+		};
+		let result = syn::parse2::<ConstItem>(ast)?;
+		assert_eq!(result.attrs.len(), 1);
+		assert!(matches!(result.vis, Visibility::Restricted(_)));
+		assert_eq!(result.ident, "VERSION");
+		assert_eq!(result.syntax.args.len(), 2);
+
+		Ok(())
+	}
+
+	#[test]
+	fn format_typeof() -> anyhow::Result<()> {
+		let ast = quote! {typeof($)};
+		let result = syn::parse2::<Expr>(ast)?;
+		assert_eq!(
+			result.atom,
+			AtomicExpr::FunctionCall(
+				"typeof".to_string(),
+				vec![Expr {
+					atom: AtomicExpr::BuildInfo(Meta::default()),
+					suffixes: vec![],
+					fallback: None,
+				}],
+				Meta::default()
+			)
+		);
+
+		Ok(())
+	}
+
+	#[test]
+	fn format_trailing_comma() -> anyhow::Result<()> {
+		let format = "3".to_string();
+		let ast = quote! {#format,};
+		let result = syn::parse2::<Syntax>(ast)?;
+		assert_eq!(
+			result,
+			Syntax {
+				args: vec![(
+					None,
+					Expr {
+						atom: AtomicExpr::LitStr(format, Meta::default()),
+						suffixes: vec![],
+						fallback: None,
+					}
+				),],
+			}
+		);
+
+		Ok(())
+	}
+
+	#[test]
+	fn raw_macros() -> anyhow::Result<()> {
+		let expr = syn::parse2::<Expr>(quote! {cfg!(all(unix, not(feature = "foo"), any()))})?;
+		assert_eq!(
+			expr.atom,
+			AtomicExpr::Cfg(
+				CfgPredicate::All(vec![
+					CfgPredicate::Name("unix".to_string()),
+					CfgPredicate::Not(Box::new(CfgPredicate::KeyValue(
+						"feature".to_string(),
+						"foo".to_string()
+					))),
+					CfgPredicate::Any(vec![]),
+				]),
+				Meta::default()
+			)
+		);
+		assert!(syn::parse2::<Expr>(quote! {cfg!(not(a, b))}).is_err());
+		assert!(syn::parse2::<Expr>(quote! {cfg!(none(a))}).is_err());
+
+		let expr = syn::parse2::<Expr>(quote! {stringify![a + $.b]})?;
+		assert_eq!(
+			expr.atom,
+			AtomicExpr::Stringify("a + $ . b".to_string(), Meta::default())
+		);
+
+		Ok(())
+	}
+}
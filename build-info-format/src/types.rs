@@ -0,0 +1,129 @@
+use std::fmt;
+
+#[derive(Copy, Clone, Eq, PartialEq, Ord, PartialOrd, Hash, Debug)]
+pub enum Type {
+	Bool,
+	Char,
+	Integer,
+	Int(IntType),
+	Float,
+	String,
+
+	BuildInfo,
+	OptimizationLevel,
+	CrateInfo,
+	CompilerInfo,
+	CompilerChannel,
+	VersionControl,
+	GitInfo,
+	TargetInfo,
+	CpuInfo,
+	Endianness,
+
+	Version,
+	DateTimeUtc,
+	DateTimeTz,
+	NaiveDate,
+	Duration,
+
+	Option,
+	Vec,
+	LoopInfo,
+}
+
+impl Type {
+	pub(crate) fn is_numeric(self) -> bool {
+		matches!(self, Type::Integer | Type::Int(_) | Type::Float)
+	}
+}
+
+/// The primitive integer types, which are produced by integer literals with a suffix such as `42u8`.
+#[derive(Copy, Clone, Eq, PartialEq, Ord, PartialOrd, Hash, Debug)]
+pub enum IntType {
+	I8,
+	I16,
+	I32,
+	I64,
+	I128,
+	Isize,
+	U8,
+	U16,
+	U32,
+	U64,
+	U128,
+	Usize,
+}
+
+impl IntType {
+	pub(crate) fn from_suffix(suffix: &str) -> Option<Self> {
+		match suffix {
+			"i8" => Some(IntType::I8),
+			"i16" => Some(IntType::I16),
+			"i32" => Some(IntType::I32),
+			"i64" => Some(IntType::I64),
+			"i128" => Some(IntType::I128),
+			"isize" => Some(IntType::Isize),
+			"u8" => Some(IntType::U8),
+			"u16" => Some(IntType::U16),
+			"u32" => Some(IntType::U32),
+			"u64" => Some(IntType::U64),
+			"u128" => Some(IntType::U128),
+			"usize" => Some(IntType::Usize),
+			_ => None,
+		}
+	}
+}
+
+impl fmt::Display for IntType {
+	fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+		match self {
+			IntType::I8 => write!(f, "i8"),
+			IntType::I16 => write!(f, "i16"),
+			IntType::I32 => write!(f, "i32"),
+			IntType::I64 => write!(f, "i64"),
+			IntType::I128 => write!(f, "i128"),
+			IntType::Isize => write!(f, "isize"),
+			IntType::U8 => write!(f, "u8"),
+			IntType::U16 => write!(f, "u16"),
+			IntType::U32 => write!(f, "u32"),
+			IntType::U64 => write!(f, "u64"),
+			IntType::U128 => write!(f, "u128"),
+			IntType::Usize => write!(f, "usize"),
+		}
+	}
+}
+
+impl fmt::Display for Type {
+	fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+		match self {
+			Type::Bool => write!(f, "bool"),
+			Type::Char => write!(f, "char"),
+			Type::Integer => write!(f, "integer"),
+			Type::Int(int_type) => write!(f, "{int_type}"),
+			Type::Float => write!(f, "f64"),
+			Type::String => write!(f, "string"),
+
+			Type::BuildInfo => write!(f, "build_info::BuildInfo"),
+			Type::OptimizationLevel => write!(f, "build_info::OptimizationLevel"),
+			Type::CrateInfo => write!(f, "build_info::CrateInfo"),
+			Type::CompilerInfo => write!(f, "build_info::CompilerInfo"),
+			Type::CompilerChannel => write!(f, "build_info::CompilerChannel"),
+			Type::VersionControl => write!(f, "build_info::VersionControl"),
+			Type::GitInfo => write!(f, "build_info::GitInfo"),
+			Type::TargetInfo => write!(f, "build_info::TargetInfo"),
+			Type::CpuInfo => write!(f, "build_info::CpuInfo"),
+			Type::Endianness => write!(f, "build_info::Endianness"),
+
+			Type::Version => write!(f, "build_info::semver::Version"),
+			Type::DateTimeUtc => write!(f, "build_info::chrono::DateTime<build_info::chrono::Utc>"),
+			// Timestamps with a time zone only exist within `format!`, so there is no type path that could be shown
+			Type::DateTimeTz => write!(f, "timestamp with time zone"),
+			Type::NaiveDate => write!(f, "build_info::chrono::NaiveDate"),
+			Type::Duration => write!(f, "build_info::chrono::TimeDelta"),
+
+			Type::Option => write!(f, "Option<_>"),
+			Type::Vec => write!(f, "Vec<_>"),
+			Type::LoopInfo => write!(f, "loop"),
+		}
+	}
+}
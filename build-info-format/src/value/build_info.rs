@@ -29,6 +29,22 @@ impl Value for BuildInfo {
 		Type::BuildInfo
 	}
 
+	fn fields(&self) -> &'static [&'static str] {
+		&[
+			"compiler",
+			"crate_info",
+			"optimization_level",
+			"profile",
+			"target",
+			"timestamp",
+			"version_control",
+		]
+	}
+
+	fn methods(&self) -> &'static [&'static str] {
+		&["to_string"]
+	}
+
 	fn as_any(&self) -> &dyn Any {
 		self
 	}
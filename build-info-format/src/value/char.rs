@@ -1,5 +1,8 @@
 use std::any::Any;
 
+use proc_macro2::TokenStream;
+use quote::quote;
+
 use super::{FormatSpecifier, Type, Value, as_arguments_0};
 
 impl Value for char {
@@ -17,10 +20,18 @@ impl Value for char {
 		Type::Char
 	}
 
+	fn methods(&self) -> &'static [&'static str] {
+		&["to_string"]
+	}
+
 	fn as_any(&self) -> &dyn Any {
 		self
 	}
 
+	fn to_tokens(&self) -> Option<TokenStream> {
+		Some(quote!(#self))
+	}
+
 	fn format(&self, buffer: &mut String, spec: FormatSpecifier) {
 		use std::fmt::Write;
 
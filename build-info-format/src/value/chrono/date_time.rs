@@ -2,15 +2,18 @@ use std::any::Any;
 
 use chrono::{DateTime, Utc};
 
-use super::super::{FormatSpecifier, Type, Value, as_arguments_0, as_simple_arguments_1};
+use super::{
+	super::{FormatSpecifier, Type, Value, as_arguments_0},
+	call_date_time,
+};
 
 impl Value for DateTime<Utc> {
 	fn call(&self, func: &str, args: &[Box<dyn Value>]) -> anyhow::Result<Box<dyn Value>> {
+		if let Some(value) = call_date_time(self, func, args)? {
+			return Ok(value);
+		}
+
 		match func {
-			"format" => {
-				let (format_string,) = as_simple_arguments_1::<String>(args)?;
-				Ok(Box::new(self.format(format_string).to_string()))
-			}
 			"to_string" => {
 				as_arguments_0(args)?;
 				Ok(Box::new(self.format("%Y-%m-%d %H:%M:%SZ").to_string()))
@@ -23,6 +26,26 @@ impl Value for DateTime<Utc> {
 		Type::DateTimeUtc
 	}
 
+	fn methods(&self) -> &'static [&'static str] {
+		&[
+			"date",
+			"day",
+			"format",
+			"hour",
+			"minute",
+			"month",
+			"second",
+			"signed_duration_since",
+			"timestamp",
+			"timestamp_millis",
+			"to_rfc2822",
+			"to_rfc3339",
+			"to_string",
+			"with_timezone",
+			"year",
+		]
+	}
+
 	fn as_any(&self) -> &dyn Any {
 		self
 	}
@@ -0,0 +1,100 @@
+use std::any::Any;
+
+use chrono::DateTime;
+use chrono_tz::Tz;
+
+use super::{
+	super::{FormatSpecifier, Type, Value, as_arguments_0},
+	call_date_time,
+};
+
+impl Value for DateTime<Tz> {
+	fn call(&self, func: &str, args: &[Box<dyn Value>]) -> anyhow::Result<Box<dyn Value>> {
+		if let Some(value) = call_date_time(self, func, args)? {
+			return Ok(value);
+		}
+
+		match func {
+			"to_string" => {
+				as_arguments_0(args)?;
+				Ok(Box::new(self.format("%Y-%m-%d %H:%M:%S %Z").to_string()))
+			}
+			"timezone" => {
+				as_arguments_0(args)?;
+				Ok(Box::new(self.timezone().name().to_string()))
+			}
+			_ => self.call_base(func, args),
+		}
+	}
+
+	fn get_type(&self) -> Type {
+		Type::DateTimeTz
+	}
+
+	fn methods(&self) -> &'static [&'static str] {
+		&[
+			"date",
+			"day",
+			"format",
+			"hour",
+			"minute",
+			"month",
+			"second",
+			"signed_duration_since",
+			"timestamp",
+			"timestamp_millis",
+			"timezone",
+			"to_rfc2822",
+			"to_rfc3339",
+			"to_string",
+			"with_timezone",
+			"year",
+		]
+	}
+
+	fn as_any(&self) -> &dyn Any {
+		self
+	}
+
+	fn format(&self, buffer: &mut String, spec: FormatSpecifier) {
+		use std::fmt::Write;
+
+		match spec {
+			FormatSpecifier::Default => write!(buffer, "{}", self.format("%Y-%m-%d %H:%M:%S %Z")).unwrap(),
+			FormatSpecifier::Debug => write!(buffer, "{self:?}").unwrap(),
+			FormatSpecifier::DebugAlt => write!(buffer, "{self:#?}").unwrap(),
+		}
+	}
+}
+
+#[cfg(test)]
+mod test {
+	use chrono::{TimeZone, Utc};
+	use pretty_assertions::assert_eq;
+
+	use super::*;
+	use crate::value::test::call;
+
+	fn timestamp() -> DateTime<Utc> {
+		Utc.with_ymd_and_hms(2024, 7, 1, 12, 30, 0).unwrap()
+	}
+
+	#[test]
+	fn with_timezone() {
+		let berlin = timestamp()
+			.call("with_timezone", &[Box::new("Europe/Berlin".to_string())])
+			.unwrap();
+		let mut buffer = String::new();
+		berlin.format(&mut buffer, FormatSpecifier::Default);
+		assert_eq!(buffer, "2024-07-01 14:30:00 CEST");
+		assert_eq!(call(&*berlin, "hour", vec![]), "14");
+		assert_eq!(call(&*berlin, "timezone", vec![]), "Europe/Berlin");
+		assert_eq!(call(&*berlin, "to_rfc3339", vec![]), "2024-07-01T14:30:00+02:00");
+
+		assert!(
+			timestamp()
+				.call("with_timezone", &[Box::new("Middle/Earth".to_string())])
+				.is_err()
+		);
+	}
+}
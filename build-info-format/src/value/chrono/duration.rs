@@ -0,0 +1,150 @@
+use std::any::Any;
+
+use chrono::TimeDelta;
+use num_bigint::BigInt;
+
+use super::super::{FormatSpecifier, Type, Value, as_arguments_0};
+
+impl Value for TimeDelta {
+	fn call(&self, func: &str, args: &[Box<dyn Value>]) -> anyhow::Result<Box<dyn Value>> {
+		match func {
+			"abs" => {
+				as_arguments_0(args)?;
+				Ok(Box::new(self.abs()))
+			}
+			"humanize" => {
+				as_arguments_0(args)?;
+				Ok(Box::new(humanize(self)))
+			}
+			"num_days" => {
+				as_arguments_0(args)?;
+				Ok(Box::new(BigInt::from(self.num_days())))
+			}
+			"num_hours" => {
+				as_arguments_0(args)?;
+				Ok(Box::new(BigInt::from(self.num_hours())))
+			}
+			"num_milliseconds" => {
+				as_arguments_0(args)?;
+				Ok(Box::new(BigInt::from(self.num_milliseconds())))
+			}
+			"num_minutes" => {
+				as_arguments_0(args)?;
+				Ok(Box::new(BigInt::from(self.num_minutes())))
+			}
+			"num_seconds" => {
+				as_arguments_0(args)?;
+				Ok(Box::new(BigInt::from(self.num_seconds())))
+			}
+			"num_weeks" => {
+				as_arguments_0(args)?;
+				Ok(Box::new(BigInt::from(self.num_weeks())))
+			}
+			"to_string" => {
+				as_arguments_0(args)?;
+				let mut buffer = String::new();
+				self.format(&mut buffer, FormatSpecifier::Default);
+				Ok(Box::new(buffer))
+			}
+			_ => self.call_base(func, args),
+		}
+	}
+
+	fn get_type(&self) -> Type {
+		Type::Duration
+	}
+
+	fn methods(&self) -> &'static [&'static str] {
+		&[
+			"abs",
+			"humanize",
+			"num_days",
+			"num_hours",
+			"num_milliseconds",
+			"num_minutes",
+			"num_seconds",
+			"num_weeks",
+			"to_string",
+		]
+	}
+
+	fn as_any(&self) -> &dyn Any {
+		self
+	}
+
+	fn format(&self, buffer: &mut String, spec: FormatSpecifier) {
+		use std::fmt::Write;
+
+		match spec {
+			FormatSpecifier::Default => {
+				if *self < TimeDelta::zero() {
+					*buffer += "-";
+				}
+				let seconds = self.num_seconds().unsigned_abs();
+				let parts = [
+					(seconds / 86400, "d"),
+					(seconds / 3600 % 24, "h"),
+					(seconds / 60 % 60, "m"),
+					(seconds % 60, "s"),
+				];
+				let mut empty = true;
+				for (amount, unit) in parts {
+					if amount > 0 {
+						if !empty {
+							*buffer += " ";
+						}
+						write!(buffer, "{amount}{unit}").unwrap();
+						empty = false;
+					}
+				}
+				if empty {
+					*buffer += "0s";
+				}
+			}
+			FormatSpecifier::Debug => write!(buffer, "{self:?}").unwrap(),
+			FormatSpecifier::DebugAlt => write!(buffer, "{self:#?}").unwrap(),
+		}
+	}
+}
+
+/// Describes the duration in its largest whole unit, e.g., "3 hours" or "1 day".
+fn humanize(duration: &TimeDelta) -> String {
+	let seconds = duration.num_seconds().abs();
+	let (amount, unit) = [(86400 * 7, "week"), (86400, "day"), (3600, "hour"), (60, "minute")]
+		.into_iter()
+		.find(|(length, _unit)| seconds >= *length)
+		.map_or((seconds, "second"), |(length, unit)| (seconds / length, unit));
+	let sign = if *duration < TimeDelta::zero() { "-" } else { "" };
+	let plural = if amount == 1 { "" } else { "s" };
+	format!("{sign}{amount} {unit}{plural}")
+}
+
+#[cfg(test)]
+mod test {
+	use pretty_assertions::assert_eq;
+
+	use super::*;
+
+	#[test]
+	fn format_default() {
+		let mut buff = String::new();
+		Value::format(&TimeDelta::seconds(3 * 3600 + 5), &mut buff, FormatSpecifier::Default);
+		assert_eq!(buff, "3h 5s");
+
+		buff.clear();
+		Value::format(&TimeDelta::seconds(-90061), &mut buff, FormatSpecifier::Default);
+		assert_eq!(buff, "-1d 1h 1m 1s");
+
+		buff.clear();
+		Value::format(&TimeDelta::zero(), &mut buff, FormatSpecifier::Default);
+		assert_eq!(buff, "0s");
+	}
+
+	#[test]
+	fn humanized() {
+		assert_eq!(humanize(&TimeDelta::seconds(3 * 3600 + 1200)), "3 hours");
+		assert_eq!(humanize(&TimeDelta::days(1)), "1 day");
+		assert_eq!(humanize(&TimeDelta::weeks(-2)), "-2 weeks");
+		assert_eq!(humanize(&TimeDelta::seconds(1)), "1 second");
+	}
+}
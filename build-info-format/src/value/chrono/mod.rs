@@ -0,0 +1,134 @@
+use anyhow::anyhow;
+use chrono::{DateTime, Datelike, TimeZone, Timelike, Utc};
+use chrono_tz::Tz;
+use num_bigint::BigInt;
+
+use super::{Value, as_arguments_0, as_simple_arguments_1};
+
+mod date_time;
+mod date_time_tz;
+mod duration;
+mod naive_date;
+
+/// Implements the methods shared by all timezone-aware timestamps. Returns `Ok(None)` if `func` is not one of them.
+fn call_date_time<T: TimeZone>(
+	this: &DateTime<T>,
+	func: &str,
+	args: &[Box<dyn Value>],
+) -> anyhow::Result<Option<Box<dyn Value>>>
+where
+	T::Offset: std::fmt::Display,
+{
+	match func {
+		"date" => {
+			as_arguments_0(args)?;
+			Ok(Some(Box::new(this.date_naive())))
+		}
+		"day" => {
+			as_arguments_0(args)?;
+			Ok(Some(Box::new(BigInt::from(this.day()))))
+		}
+		"format" => {
+			let (format_string,) = as_simple_arguments_1::<String>(args)?;
+			Ok(Some(Box::new(this.format(format_string).to_string())))
+		}
+		"hour" => {
+			as_arguments_0(args)?;
+			Ok(Some(Box::new(BigInt::from(this.hour()))))
+		}
+		"minute" => {
+			as_arguments_0(args)?;
+			Ok(Some(Box::new(BigInt::from(this.minute()))))
+		}
+		"month" => {
+			as_arguments_0(args)?;
+			Ok(Some(Box::new(BigInt::from(this.month()))))
+		}
+		"second" => {
+			as_arguments_0(args)?;
+			Ok(Some(Box::new(BigInt::from(this.second()))))
+		}
+		"signed_duration_since" => {
+			if args.len() != 1 {
+				return Err(anyhow!("Wrong number of arguments (should be 1)"));
+			}
+			let other = as_date_time_utc(&*args[0])
+				.ok_or_else(|| anyhow!("Argument #1 should be a timestamp, but has type {}", args[0].get_type()))?;
+			Ok(Some(Box::new(this.to_utc().signed_duration_since(other))))
+		}
+		"timestamp" => {
+			as_arguments_0(args)?;
+			Ok(Some(Box::new(BigInt::from(this.timestamp()))))
+		}
+		"timestamp_millis" => {
+			as_arguments_0(args)?;
+			Ok(Some(Box::new(BigInt::from(this.timestamp_millis()))))
+		}
+		"to_rfc2822" => {
+			as_arguments_0(args)?;
+			Ok(Some(Box::new(this.to_rfc2822())))
+		}
+		"to_rfc3339" => {
+			as_arguments_0(args)?;
+			Ok(Some(Box::new(this.to_rfc3339())))
+		}
+		"with_timezone" => {
+			let (name,) = as_simple_arguments_1::<String>(args)?;
+			let timezone = name
+				.parse::<Tz>()
+				.map_err(|err| anyhow!("Unknown timezone {name:?}: {err}"))?;
+			Ok(Some(Box::new(this.with_timezone(&timezone))))
+		}
+		"year" => {
+			as_arguments_0(args)?;
+			Ok(Some(Box::new(BigInt::from(this.year()))))
+		}
+		_ => Ok(None),
+	}
+}
+
+fn as_date_time_utc(value: &dyn Value) -> Option<DateTime<Utc>> {
+	if let Some(value) = value.as_any().downcast_ref::<DateTime<Utc>>() {
+		Some(*value)
+	} else {
+		value
+			.as_any()
+			.downcast_ref::<DateTime<Tz>>()
+			.map(|value| value.with_timezone(&Utc))
+	}
+}
+
+#[cfg(test)]
+mod test {
+	use pretty_assertions::assert_eq;
+
+	use super::*;
+	use crate::value::test::call;
+
+	fn timestamp() -> DateTime<Utc> {
+		Utc.with_ymd_and_hms(2024, 7, 1, 12, 30, 0).unwrap()
+	}
+
+	#[test]
+	fn accessors() {
+		assert_eq!(call(&timestamp(), "year", vec![]), "2024");
+		assert_eq!(call(&timestamp(), "month", vec![]), "7");
+		assert_eq!(call(&timestamp(), "day", vec![]), "1");
+		assert_eq!(call(&timestamp(), "date", vec![]), "2024-07-01");
+		assert_eq!(call(&timestamp(), "timestamp", vec![]), "1719837000");
+		assert_eq!(call(&timestamp(), "timestamp_millis", vec![]), "1719837000000");
+		assert_eq!(
+			call(&timestamp(), "to_rfc2822", vec![]),
+			"Mon, 1 Jul 2024 12:30:00 +0000"
+		);
+	}
+
+	#[test]
+	fn duration_since() {
+		let commit = Utc.with_ymd_and_hms(2024, 7, 1, 9, 15, 30).unwrap();
+		assert_eq!(
+			call(&timestamp(), "signed_duration_since", vec![Box::new(commit)]),
+			"3h 14m 30s"
+		);
+	}
+}
@@ -1,26 +1,43 @@
 use std::any::Any;
 
-use chrono::NaiveDate;
+use chrono::{Datelike, NaiveDate};
+use num_bigint::BigInt;
 
 use super::super::{FormatSpecifier, Type, Value, as_arguments_0, as_simple_arguments_1};
 
 impl Value for NaiveDate {
 	fn call(&self, func: &str, args: &[Box<dyn Value>]) -> anyhow::Result<Box<dyn Value>> {
 		match func {
+			"day" => {
+				as_arguments_0(args)?;
+				Ok(Box::new(BigInt::from(self.day())))
+			}
 			"format" => {
 				let (format_string,) = as_simple_arguments_1::<String>(args)?;
 				Ok(Box::new(self.format(format_string).to_string()))
 			}
+			"month" => {
+				as_arguments_0(args)?;
+				Ok(Box::new(BigInt::from(self.month())))
+			}
 			"to_string" => {
 				as_arguments_0(args)?;
 				Ok(Box::new(self.format("%Y-%m-%d").to_string()))
 			}
+			"year" => {
+				as_arguments_0(args)?;
+				Ok(Box::new(BigInt::from(self.year())))
+			}
 			_ => self.call_base(func, args),
 		}
 	}
 
 	fn get_type(&self) -> Type {
-		Type::DateTimeUtc
+		Type::NaiveDate
+	}
+
+	fn methods(&self) -> &'static [&'static str] {
+		&["day", "format", "month", "to_string", "year"]
 	}
 
 	fn as_any(&self) -> &dyn Any {
@@ -27,6 +27,14 @@ impl Value for CompilerInfo {
 		Type::CompilerInfo
 	}
 
+	fn fields(&self) -> &'static [&'static str] {
+		&["channel", "commit_date", "commit_id", "host_triple", "version"]
+	}
+
+	fn methods(&self) -> &'static [&'static str] {
+		&["to_string"]
+	}
+
 	fn as_any(&self) -> &dyn Any {
 		self
 	}
@@ -1,9 +1,10 @@
 use std::any::Any;
 
+use anyhow::anyhow;
 use build_info_common::CpuInfo;
 use num_bigint::BigInt;
 
-use super::{FormatSpecifier, OP_FIELD_ACCESS, Type, Value, as_arguments_0, as_field_name};
+use super::{FormatSpecifier, OP_FIELD_ACCESS, Type, Value, as_arguments_0, as_field_name, as_simple_arguments_1};
 
 impl Value for CpuInfo {
 	fn call(&self, func: &str, args: &[Box<dyn Value>]) -> anyhow::Result<Box<dyn Value>> {
@@ -15,6 +16,28 @@ impl Value for CpuInfo {
 				"features" => Ok(Box::new(self.features.clone())),
 				_ => self.call_base(func, args),
 			},
+			"bitness" => {
+				as_arguments_0(args)?;
+				Ok(Box::new(self.bitness()))
+			}
+			"has_all_features" => {
+				let features = args
+					.iter()
+					.enumerate()
+					.map(|(i, arg)| {
+						arg
+							.as_any()
+							.downcast_ref::<String>()
+							.map(|feature| feature.as_str())
+							.ok_or_else(|| anyhow!("Argument #{} should be a string", i + 1))
+					})
+					.collect::<anyhow::Result<Vec<_>>>()?;
+				Ok(Box::new(self.has_all_features(&features)))
+			}
+			"has_feature" => {
+				let (feature,) = as_simple_arguments_1::<String>(args)?;
+				Ok(Box::new(self.has_feature(feature)))
+			}
 			"to_string" => {
 				as_arguments_0(args)?;
 				Ok(Box::new(self.to_string()))
@@ -24,7 +47,15 @@ impl Value for CpuInfo {
 	}
 
 	fn get_type(&self) -> Type {
-		Type::CompilerInfo
+		Type::CpuInfo
+	}
+
+	fn fields(&self) -> &'static [&'static str] {
+		&["arch", "endianness", "features", "pointer_width"]
+	}
+
+	fn methods(&self) -> &'static [&'static str] {
+		&["bitness", "has_all_features", "has_feature", "to_string"]
 	}
 
 	fn as_any(&self) -> &dyn Any {
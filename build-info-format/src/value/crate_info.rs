@@ -12,6 +12,7 @@ impl Value for CrateInfo {
 				"version" => Ok(Box::new(self.version.clone())),
 				"authors" => Ok(Box::new(self.authors.clone())),
 				"license" => Ok(Box::new(self.license.clone())),
+				"source" => Ok(Box::new(self.source.clone())),
 				"enabled_features" => Ok(Box::new(self.enabled_features.clone())),
 				"available_features" => Ok(Box::new(self.available_features.clone())),
 				"dependencies" => Ok(Box::new(self.dependencies.clone())),
@@ -29,6 +30,23 @@ impl Value for CrateInfo {
 		Type::CrateInfo
 	}
 
+	fn fields(&self) -> &'static [&'static str] {
+		&[
+			"authors",
+			"available_features",
+			"dependencies",
+			"enabled_features",
+			"license",
+			"name",
+			"source",
+			"version",
+		]
+	}
+
+	fn methods(&self) -> &'static [&'static str] {
+		&["to_string"]
+	}
+
 	fn as_any(&self) -> &dyn Any {
 		self
 	}
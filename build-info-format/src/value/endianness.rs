@@ -16,7 +16,11 @@ impl Value for Endianness {
 	}
 
 	fn get_type(&self) -> Type {
-		Type::CompilerChannel
+		Type::Endianness
+	}
+
+	fn methods(&self) -> &'static [&'static str] {
+		&["to_string"]
 	}
 
 	fn as_any(&self) -> &dyn Any {
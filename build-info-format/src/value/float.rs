@@ -0,0 +1,146 @@
+use std::any::Any;
+
+use anyhow::anyhow;
+use num_bigint::BigInt;
+use num_traits::{FromPrimitive, ToPrimitive};
+use proc_macro2::{Literal, TokenStream};
+use quote::quote;
+
+use super::{FormatSpecifier, Type, Value, as_arguments_0};
+
+/// Arithmetic accepts another float or an integer.
+fn as_operand(args: &[Box<dyn Value>]) -> anyhow::Result<f64> {
+	if args.len() != 1 {
+		return Err(anyhow!("Wrong number of arguments (should be 1)"));
+	}
+
+	if let Some(value) = args[0].as_any().downcast_ref::<f64>() {
+		Ok(*value)
+	} else if let Some(value) = args[0].as_any().downcast_ref::<BigInt>() {
+		value
+			.to_f64()
+			.ok_or_else(|| anyhow!("The integer {value} cannot be represented as f64"))
+	} else {
+		Err(anyhow!(
+			"Argument #1 should have type f64 or integer, but has type {}",
+			args[0].get_type()
+		))
+	}
+}
+
+impl Value for f64 {
+	fn call(&self, func: &str, args: &[Box<dyn Value>]) -> anyhow::Result<Box<dyn Value>> {
+		match func {
+			"abs" => {
+				as_arguments_0(args)?;
+				Ok(Box::new(self.abs()))
+			}
+			"add" => Ok(Box::new(self + as_operand(args)?)),
+			"ceil" => {
+				as_arguments_0(args)?;
+				Ok(Box::new(self.ceil()))
+			}
+			"div" => Ok(Box::new(self / as_operand(args)?)),
+			"floor" => {
+				as_arguments_0(args)?;
+				Ok(Box::new(self.floor()))
+			}
+			"mul" => Ok(Box::new(self * as_operand(args)?)),
+			"round" => {
+				as_arguments_0(args)?;
+				Ok(Box::new(self.round()))
+			}
+			"sub" => Ok(Box::new(self - as_operand(args)?)),
+			"to_integer" => {
+				as_arguments_0(args)?;
+				BigInt::from_f64(self.trunc())
+					.map(|value| Box::new(value) as Box<dyn Value>)
+					.ok_or_else(|| anyhow!("{self} cannot be converted to an integer"))
+			}
+			"to_string" => {
+				as_arguments_0(args)?;
+				Ok(Box::new(self.to_string()))
+			}
+			_ => self.call_base(func, args),
+		}
+	}
+
+	fn get_type(&self) -> Type {
+		Type::Float
+	}
+
+	fn methods(&self) -> &'static [&'static str] {
+		&[
+			"abs",
+			"add",
+			"ceil",
+			"div",
+			"floor",
+			"mul",
+			"round",
+			"sub",
+			"to_integer",
+			"to_string",
+		]
+	}
+
+	fn as_any(&self) -> &dyn Any {
+		self
+	}
+
+	fn to_tokens(&self) -> Option<TokenStream> {
+		Some(if self.is_nan() {
+			quote!(::core::primitive::f64::NAN)
+		} else if self.is_infinite() && self.is_sign_positive() {
+			quote!(::core::primitive::f64::INFINITY)
+		} else if self.is_infinite() {
+			quote!(::core::primitive::f64::NEG_INFINITY)
+		} else {
+			let literal = Literal::f64_suffixed(*self);
+			quote!(#literal)
+		})
+	}
+
+	fn format(&self, buffer: &mut String, spec: FormatSpecifier) {
+		use std::fmt::Write;
+
+		match spec {
+			FormatSpecifier::Default => write!(buffer, "{self}").unwrap(),
+			FormatSpecifier::Debug => write!(buffer, "{self:?}").unwrap(),
+			FormatSpecifier::DebugAlt => write!(buffer, "{self:#?}").unwrap(),
+		}
+	}
+
+	fn format_precision(&self, buffer: &mut String, precision: usize) {
+		use std::fmt::Write;
+
+		write!(buffer, "{self:.precision$}").unwrap();
+	}
+}
+
+#[cfg(test)]
+mod test {
+	use pretty_assertions::assert_eq;
+
+	use super::*;
+	use crate::value::test::format;
+
+	#[test]
+	fn arithmetic() {
+		assert_eq!(
+			format(&*0.25f64.call("mul", &[Box::new(BigInt::from(100))]).unwrap()),
+			"25"
+		);
+		assert_eq!(format(&*2.5f64.call("round", &[]).unwrap()), "3");
+		assert_eq!(format(&*(-2.5f64).call("to_integer", &[]).unwrap()), "-2");
+		assert!(f64::NAN.call("to_integer", &[]).is_err());
+		assert!(1f64.call("add", &[Box::new(true)]).is_err());
+	}
+
+	#[test]
+	fn precision() {
+		let mut buffer = String::new();
+		Value::format_precision(&(2.0f64 / 3.0), &mut buffer, 2);
+		assert_eq!(buffer, "0.67");
+	}
+}
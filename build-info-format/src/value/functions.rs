@@ -0,0 +1,439 @@
+use std::{cmp::Ordering, fmt::Write};
+
+use anyhow::anyhow;
+use base64::Engine;
+use num_bigint::BigInt;
+use num_traits::ToPrimitive;
+use sha2::{Digest, Sha256};
+
+use super::{super::error::Hint, Value, as_simple_arguments_1, as_simple_arguments_2, as_simple_arguments_3};
+
+const FUNCTIONS: &[&str] = &[
+	"all",
+	"any",
+	"base64",
+	"eq",
+	"hex",
+	"json_escape",
+	"len",
+	"lower",
+	"max",
+	"min",
+	"ne",
+	"not",
+	"pad_left",
+	"pad_right",
+	"replace",
+	"sha256",
+	"type_name",
+	"typeof",
+	"upper",
+	"url_encode",
+];
+
+pub(crate) fn call_function(name: &str, args: &[Box<dyn Value>]) -> anyhow::Result<Box<dyn Value>> {
+	match name {
+		"all" => Ok(Box::new(as_bools(args)?.into_iter().all(|value| value))),
+		"any" => Ok(Box::new(as_bools(args)?.into_iter().any(|value| value))),
+		"base64" => {
+			let (value,) = as_simple_arguments_1::<String>(args)?;
+			Ok(Box::new(base64::engine::general_purpose::STANDARD.encode(value)))
+		}
+		"eq" => Ok(Box::new(equals(args)?)),
+		"hex" => {
+			if args.len() != 1 {
+				return Err(anyhow!("Wrong number of arguments (should be 1)"));
+			}
+			if let Some(value) = args[0].as_any().downcast_ref::<BigInt>() {
+				Ok(Box::new(format!("{value:x}")))
+			} else if let Some(value) = args[0].as_any().downcast_ref::<String>() {
+				Ok(Box::new(hex(value.as_bytes())))
+			} else {
+				Err(anyhow!(
+					"Argument #1 should be an integer or a string, but has type {}",
+					args[0].get_type()
+				))
+			}
+		}
+		"json_escape" => {
+			let (value,) = as_simple_arguments_1::<String>(args)?;
+			let quoted = serde_json::to_string(value)?;
+			Ok(Box::new(quoted[1..quoted.len() - 1].to_string()))
+		}
+		"len" => {
+			if args.len() != 1 {
+				return Err(anyhow!("Wrong number of arguments (should be 1)"));
+			}
+			args[0].call("len", &[])
+		}
+		"lower" => {
+			let (value,) = as_simple_arguments_1::<String>(args)?;
+			Ok(Box::new(value.to_lowercase()))
+		}
+		"max" => extremum(args, Ordering::Greater),
+		"min" => extremum(args, Ordering::Less),
+		"ne" => Ok(Box::new(!equals(args)?)),
+		"not" => {
+			let (value,) = as_simple_arguments_1::<bool>(args)?;
+			Ok(Box::new(!value))
+		}
+		"pad_left" => {
+			let (value, width, fill) = as_padding_arguments(args)?;
+			let padding = width.saturating_sub(value.chars().count());
+			Ok(Box::new(
+				std::iter::repeat_n(fill, padding)
+					.chain(value.chars())
+					.collect::<String>(),
+			))
+		}
+		"pad_right" => {
+			let (value, width, fill) = as_padding_arguments(args)?;
+			let padding = width.saturating_sub(value.chars().count());
+			Ok(Box::new(
+				value
+					.chars()
+					.chain(std::iter::repeat_n(fill, padding))
+					.collect::<String>(),
+			))
+		}
+		"replace" => {
+			let (value, from, to) = as_simple_arguments_3::<String, String, String>(args)?;
+			Ok(Box::new(value.replace(from.as_str(), to)))
+		}
+		"sha256" => {
+			let (value,) = as_simple_arguments_1::<String>(args)?;
+			Ok(Box::new(hex(&Sha256::digest(value.as_bytes()))))
+		}
+		"type_name" | "typeof" => {
+			if args.len() != 1 {
+				return Err(anyhow!("Wrong number of arguments (should be 1)"));
+			}
+			Ok(Box::new(args[0].get_type().to_string()))
+		}
+		"upper" => {
+			let (value,) = as_simple_arguments_1::<String>(args)?;
+			Ok(Box::new(value.to_uppercase()))
+		}
+		"url_encode" => {
+			let (value,) = as_simple_arguments_1::<String>(args)?;
+			let mut result = String::with_capacity(value.len());
+			for byte in value.bytes() {
+				if byte.is_ascii_alphanumeric() || matches!(byte, b'-' | b'.' | b'_' | b'~') {
+					result.push(byte as char);
+				} else {
+					write!(result, "%{byte:02X}").unwrap();
+				}
+			}
+			Ok(Box::new(result))
+		}
+		_ => Err(
+			Hint {
+				message: format!("Function `{name}` cannot be called inside `build_info::format!`"),
+				notes: vec![format!(
+					"The following functions are available: {}",
+					FUNCTIONS.join(", ")
+				)],
+			}
+			.into(),
+		),
+	}
+}
+
+fn hex(bytes: &[u8]) -> String {
+	let mut result = String::with_capacity(2 * bytes.len());
+	for byte in bytes {
+		write!(result, "{byte:02x}").unwrap();
+	}
+	result
+}
+
+/// Converts the arguments of `all` and `any`, which must all be booleans.
+fn as_bools(args: &[Box<dyn Value>]) -> anyhow::Result<Vec<bool>> {
+	args
+		.iter()
+		.enumerate()
+		.map(|(i, arg)| {
+			arg.as_any().downcast_ref::<bool>().copied().ok_or_else(|| {
+				anyhow!(
+					"Argument #{} should have type bool, but has type {}",
+					i + 1,
+					arg.get_type()
+				)
+			})
+		})
+		.collect()
+}
+
+/// Values of the same type are compared using their `eq` method, if they have one, so that, e.g., `eq(a, b)` agrees
+/// with `a.eq(b)` for versions, which ignores the build metadata. All other values, including containers such as `Vec`
+/// and `Option` of versions, are compared structurally, using `PartialEq`. As in Rust, `0.0` equals `-0.0`, while `NaN`
+/// does not equal anything, not even itself.
+fn equals(args: &[Box<dyn Value>]) -> anyhow::Result<bool> {
+	if args.len() != 2 {
+		return Err(anyhow!("Wrong number of arguments (should be 2)"));
+	}
+	if args[0].get_type() != args[1].get_type() {
+		return Err(anyhow!(
+			"Cannot compare values of type {} and {}",
+			args[0].get_type(),
+			args[1].get_type()
+		));
+	}
+
+	if args[0].methods().contains(&"eq") {
+		let equal = args[0].call("eq", &[args[1].clone_value()])?;
+		return Ok(
+			*equal
+				.as_any()
+				.downcast_ref::<bool>()
+				.expect("`eq` methods return a bool"),
+		);
+	}
+	Ok(args[0].eq_value(&*args[1]))
+}
+
+/// Returns the argument that compares as `wanted` against all others. All arguments must have the same type, which must
+/// be an integer (typed or not), float or string type.
+fn extremum(args: &[Box<dyn Value>], wanted: Ordering) -> anyhow::Result<Box<dyn Value>> {
+	if args.is_empty() {
+		return Err(anyhow!("Wrong number of arguments (should be at least 1)"));
+	}
+	if let Some(arg) = args.iter().find(|arg| arg.get_type() != args[0].get_type()) {
+		return Err(anyhow!(
+			"Cannot compare values of type {} and {}",
+			args[0].get_type(),
+			arg.get_type()
+		));
+	}
+
+	type Extremum = fn(&[Box<dyn Value>], Ordering) -> Option<Box<dyn Value>>;
+	const EXTREMA: &[Extremum] = &[
+		extremum_of::<BigInt>,
+		extremum_of::<String>,
+		extremum_of::<f64>,
+		extremum_of::<i8>,
+		extremum_of::<i16>,
+		extremum_of::<i32>,
+		extremum_of::<i64>,
+		extremum_of::<i128>,
+		extremum_of::<isize>,
+		extremum_of::<u8>,
+		extremum_of::<u16>,
+		extremum_of::<u32>,
+		extremum_of::<u64>,
+		extremum_of::<u128>,
+		extremum_of::<usize>,
+	];
+	EXTREMA
+		.iter()
+		.find_map(|extremum| extremum(args, wanted))
+		.ok_or_else(|| {
+			anyhow!(
+				"Values of type {} cannot be compared (only integers, floats and strings can)",
+				args[0].get_type()
+			)
+		})
+}
+
+/// Returns the argument that compares as `wanted` against all others, if all arguments have type `T`. As for
+/// `f64::max`, `NaN` is only returned if all arguments are `NaN`.
+fn extremum_of<T: 'static + Value + Clone + PartialOrd>(
+	args: &[Box<dyn Value>],
+	wanted: Ordering,
+) -> Option<Box<dyn Value>> {
+	let values = args
+		.iter()
+		.map(|arg| arg.as_any().downcast_ref::<T>())
+		.collect::<Option<Vec<_>>>()?;
+	let best = values.into_iter().reduce(|best, value| {
+		let is_nan = |value: &T| value.partial_cmp(value).is_none();
+		if is_nan(best) || value.partial_cmp(best) == Some(wanted) {
+			value
+		} else {
+			best
+		}
+	})?;
+	Some(Box::new(best.clone()))
+}
+
+fn as_padding_arguments(args: &[Box<dyn Value>]) -> anyhow::Result<(&String, usize, char)> {
+	let (value, width, fill) = match args.len() {
+		2 => {
+			let (value, width) = as_simple_arguments_2::<String, BigInt>(args)?;
+			(value, width, ' ')
+		}
+		3 => {
+			let (value, width, fill) = as_simple_arguments_3::<String, BigInt, char>(args)?;
+			(value, width, *fill)
+		}
+		_ => return Err(anyhow!("Wrong number of arguments (should be 2 or 3)")),
+	};
+	let width = width
+		.to_usize()
+		.ok_or_else(|| anyhow!("The padding width {width} does not fit into the type usize"))?;
+	Ok((value, width, fill))
+}
+
+#[cfg(test)]
+mod test {
+	use build_info_common::semver::Version;
+	use pretty_assertions::assert_eq;
+
+	use super::*;
+	use crate::value::test::format;
+
+	fn call(name: &str, args: Vec<Box<dyn Value>>) -> String {
+		format(&*call_function(name, &args).unwrap())
+	}
+
+	#[test]
+	fn min_max() {
+		let numbers = || -> Vec<Box<dyn Value>> {
+			vec![
+				Box::new(BigInt::from(3)),
+				Box::new(BigInt::from(-7)),
+				Box::new(BigInt::from(12)),
+			]
+		};
+		assert_eq!(call("max", numbers()), "12");
+		assert_eq!(call("min", numbers()), "-7");
+
+		let strings = || -> Vec<Box<dyn Value>> { vec![Box::new("beta".to_string()), Box::new("alpha".to_string())] };
+		assert_eq!(call("max", strings()), "beta");
+		assert_eq!(call("min", strings()), "alpha");
+
+		assert_eq!(call("max", vec![Box::new(1u8), Box::new(2u8)]), "2");
+		assert_eq!(
+			call(
+				"typeof",
+				vec![call_function("max", &[Box::new(1u8), Box::new(2u8)]).unwrap()]
+			),
+			"u8"
+		);
+		assert_eq!(call("min", vec![Box::new(-1i64), Box::new(5i64)]), "-1");
+		assert_eq!(call("max", vec![Box::new(1.5), Box::new(2.0)]), "2");
+		assert_eq!(
+			call("min", vec![Box::new(f64::NAN), Box::new(1.5), Box::new(0.5)]),
+			"0.5"
+		);
+		assert_eq!(call("max", vec![Box::new(f64::NAN)]), "NaN");
+
+		assert!(call_function("max", &[Box::new(1u8), Box::new(BigInt::from(2))]).is_err());
+		assert!(call_function("max", &[Box::new(1u8), Box::new(2u16)]).is_err());
+		assert!(call_function("max", &[Box::new(true)]).is_err());
+		assert!(call_function("min", &[]).is_err());
+	}
+
+	#[test]
+	fn logic() {
+		assert_eq!(call("not", vec![Box::new(false)]), "true");
+		assert_eq!(call("all", vec![Box::new(true), Box::new(false)]), "false");
+		assert_eq!(call("any", vec![Box::new(true), Box::new(false)]), "true");
+		assert_eq!(call("all", vec![]), "true");
+		assert_eq!(
+			call("eq", vec![Box::new("a".to_string()), Box::new("a".to_string())]),
+			"true"
+		);
+		assert_eq!(
+			call("ne", vec![Box::new(BigInt::from(1)), Box::new(BigInt::from(2))]),
+			"true"
+		);
+		assert!(call_function("eq", &[Box::new(BigInt::from(1)), Box::new(true)]).is_err());
+
+		assert_eq!(call("eq", vec![Box::new(0.0), Box::new(-0.0)]), "true");
+		assert_eq!(call("eq", vec![Box::new(f64::NAN), Box::new(f64::NAN)]), "false");
+		assert_eq!(call("ne", vec![Box::new(f64::NAN), Box::new(f64::NAN)]), "true");
+		assert_eq!(
+			call("eq", vec![Box::new(vec![0.0, 1.5]), Box::new(vec![-0.0, 1.5])]),
+			"true"
+		);
+		assert!(call_function("any", &[Box::new(BigInt::from(1))]).is_err());
+	}
+
+	#[test]
+	fn version_equality() {
+		let abc = Version::parse("1.2.3+abc").unwrap();
+		let def = Version::parse("1.2.3+def").unwrap();
+		assert_eq!(call("eq", vec![Box::new(abc.clone()), Box::new(def.clone())]), "true");
+		assert_eq!(call("ne", vec![Box::new(abc.clone()), Box::new(def.clone())]), "false");
+
+		// Containers are compared structurally, so the build metadata matters there
+		assert_eq!(call("eq", vec![Box::new(vec![abc]), Box::new(vec![def])]), "false");
+	}
+
+	#[test]
+	fn strings() {
+		assert_eq!(call("len", vec![Box::new("äbc".to_string())]), "4");
+		assert_eq!(call("lower", vec![Box::new("AbC".to_string())]), "abc");
+		assert_eq!(call("upper", vec![Box::new("AbC".to_string())]), "ABC");
+		assert_eq!(
+			call(
+				"replace",
+				vec![
+					Box::new("a-b-c".to_string()),
+					Box::new("-".to_string()),
+					Box::new("+".to_string())
+				]
+			),
+			"a+b+c"
+		);
+	}
+
+	#[test]
+	fn padding() {
+		assert_eq!(
+			call("pad_left", vec![Box::new("7".to_string()), Box::new(BigInt::from(3))]),
+			"  7"
+		);
+		assert_eq!(
+			call(
+				"pad_left",
+				vec![Box::new("7".to_string()), Box::new(BigInt::from(3)), Box::new('0')]
+			),
+			"007"
+		);
+		assert_eq!(
+			call("pad_right", vec![Box::new("ab".to_string()), Box::new(BigInt::from(4))]),
+			"ab  "
+		);
+		assert_eq!(
+			call(
+				"pad_right",
+				vec![Box::new("abcde".to_string()), Box::new(BigInt::from(4))]
+			),
+			"abcde"
+		);
+	}
+
+	#[test]
+	fn encodings() {
+		assert_eq!(call("hex", vec![Box::new(BigInt::from(255))]), "ff");
+		assert_eq!(call("hex", vec![Box::new("AZ".to_string())]), "415a");
+		assert_eq!(
+			call("sha256", vec![Box::new("abc".to_string())]),
+			"ba7816bf8f01cfea414140de5dae2223b00361a396177a9cb410ff61f20015ad"
+		);
+		assert_eq!(
+			call("base64", vec![Box::new("build-info".to_string())]),
+			"YnVpbGQtaW5mbw=="
+		);
+		assert_eq!(call("url_encode", vec![Box::new("a b/c~d".to_string())]), "a%20b%2Fc~d");
+		assert_eq!(
+			call("json_escape", vec![Box::new("say \"hi\"\n\\".to_string())]),
+			"say \\\"hi\\\"\\n\\\\"
+		);
+	}
+
+	#[test]
+	fn types() {
+		assert_eq!(call("typeof", vec![Box::new(true)]), "bool");
+		assert_eq!(call("type_name", vec![Box::new(Some("x".to_string()))]), "Option<_>");
+	}
+
+	#[test]
+	fn wrong_arguments() {
+		assert!(call_function("lower", &[Box::new(BigInt::from(1))]).is_err());
+		assert!(call_function("sha256", &[]).is_err());
+		assert!(call_function("hex", &[Box::new(true)]).is_err());
+	}
+}
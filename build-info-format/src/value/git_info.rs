@@ -28,6 +28,21 @@ impl Value for GitInfo {
 		Type::GitInfo
 	}
 
+	fn fields(&self) -> &'static [&'static str] {
+		&[
+			"branch",
+			"commit_id",
+			"commit_short_id",
+			"commit_timestamp",
+			"dirty",
+			"tags",
+		]
+	}
+
+	fn methods(&self) -> &'static [&'static str] {
+		&["to_string"]
+	}
+
 	fn as_any(&self) -> &dyn Any {
 		self
 	}
@@ -0,0 +1,113 @@
+use std::any::Any;
+
+use anyhow::anyhow;
+use num_bigint::BigInt;
+use num_traits::{ToPrimitive, Zero};
+use proc_macro2::{Literal, TokenStream};
+use quote::quote;
+
+use super::{FormatSpecifier, Type, Value, as_arguments_0, as_simple_arguments_1};
+
+impl Value for BigInt {
+	fn call(&self, func: &str, args: &[Box<dyn Value>]) -> anyhow::Result<Box<dyn Value>> {
+		match func {
+			"add" => {
+				let (operand,) = as_simple_arguments_1::<BigInt>(args)?;
+				Ok(Box::new(self + operand))
+			}
+			"div" => {
+				let (operand,) = as_simple_arguments_1::<BigInt>(args)?;
+				if operand.is_zero() {
+					return Err(anyhow!("Attempt to divide {self} by zero"));
+				}
+				Ok(Box::new(self / operand))
+			}
+			"mul" => {
+				let (operand,) = as_simple_arguments_1::<BigInt>(args)?;
+				Ok(Box::new(self * operand))
+			}
+			"rem" => {
+				let (operand,) = as_simple_arguments_1::<BigInt>(args)?;
+				if operand.is_zero() {
+					return Err(anyhow!(
+						"Attempt to calculate the remainder of {self} with a divisor of zero"
+					));
+				}
+				Ok(Box::new(self % operand))
+			}
+			"sub" => {
+				let (operand,) = as_simple_arguments_1::<BigInt>(args)?;
+				Ok(Box::new(self - operand))
+			}
+			"to_f64" => {
+				as_arguments_0(args)?;
+				let value = self
+					.to_f64()
+					.ok_or_else(|| anyhow!("The integer {self} cannot be represented as f64"))?;
+				Ok(Box::new(value))
+			}
+			"to_string" => {
+				as_arguments_0(args)?;
+				Ok(Box::new(self.to_string()))
+			}
+			_ => self.call_base(func, args),
+		}
+	}
+
+	fn get_type(&self) -> Type {
+		Type::Integer
+	}
+
+	fn methods(&self) -> &'static [&'static str] {
+		&["add", "div", "mul", "rem", "sub", "to_f64", "to_string"]
+	}
+
+	fn as_any(&self) -> &dyn Any {
+		self
+	}
+
+	fn to_tokens(&self) -> Option<TokenStream> {
+		// Integers in the build information are `u64`s (e.g., version numbers), so that is the preferred type
+		let suffix = if self.to_u64().is_some() {
+			"u64"
+		} else if self.to_i64().is_some() {
+			"i64"
+		} else if self.to_u128().is_some() {
+			"u128"
+		} else if self.to_i128().is_some() {
+			"i128"
+		} else {
+			return None;
+		};
+		Some(int_literal(&self.to_string(), suffix))
+	}
+
+	fn format(&self, buffer: &mut String, spec: FormatSpecifier) {
+		use std::fmt::Write;
+
+		match spec {
+			FormatSpecifier::Default => write!(buffer, "{self}").unwrap(),
+			FormatSpecifier::Debug => write!(buffer, "{self:?}").unwrap(),
+			FormatSpecifier::DebugAlt => write!(buffer, "{self:#?}").unwrap(),
+		}
+	}
+
+	fn format_precision(&self, buffer: &mut String, _precision: usize) {
+		// Like in `std::format!`, the precision is ignored for integers
+		self.format(buffer, FormatSpecifier::Default);
+	}
+}
+
+/// Generates an integer literal with the given suffix, which is negated if necessary.
+pub(super) fn int_literal(digits: &str, suffix: &str) -> TokenStream {
+	match digits.strip_prefix('-') {
+		Some(digits) => {
+			let literal: Literal = format!("{digits}{suffix}").parse().unwrap();
+			quote!(-#literal)
+		}
+		None => {
+			let literal: Literal = format!("{digits}{suffix}").parse().unwrap();
+			quote!(#literal)
+		}
+	}
+}
@@ -0,0 +1,52 @@
+use std::any::Any;
+
+use num_bigint::BigInt;
+
+use super::{FormatSpecifier, OP_FIELD_ACCESS, Type, Value, as_field_name};
+
+/// The `loop` variable that is available inside of `{% for x in xs %}` loops.
+#[derive(Clone, PartialEq, Debug)]
+pub(crate) struct LoopInfo {
+	/// Zero-based index of the current iteration
+	pub(crate) index: usize,
+	/// Total number of iterations
+	pub(crate) length: usize,
+}
+
+impl Value for LoopInfo {
+	fn call(&self, func: &str, args: &[Box<dyn Value>]) -> anyhow::Result<Box<dyn Value>> {
+		match func {
+			OP_FIELD_ACCESS => match as_field_name(args) {
+				"index" => Ok(Box::new(BigInt::from(self.index))),
+				"index1" => Ok(Box::new(BigInt::from(self.index + 1))),
+				"first" => Ok(Box::new(self.index == 0)),
+				"last" => Ok(Box::new(self.index + 1 == self.length)),
+				"length" => Ok(Box::new(BigInt::from(self.length))),
+				_ => self.call_base(func, args),
+			},
+			_ => self.call_base(func, args),
+		}
+	}
+
+	fn get_type(&self) -> Type {
+		Type::LoopInfo
+	}
+
+	fn fields(&self) -> &'static [&'static str] {
+		&["first", "index", "index1", "last", "length"]
+	}
+
+	fn as_any(&self) -> &dyn Any {
+		self
+	}
+
+	fn format(&self, buffer: &mut String, spec: FormatSpecifier) {
+		use std::fmt::Write;
+
+		match spec {
+			FormatSpecifier::Default => write!(buffer, "{}/{}", self.index + 1, self.length).unwrap(),
+			FormatSpecifier::Debug => write!(buffer, "{self:?}").unwrap(),
+			FormatSpecifier::DebugAlt => write!(buffer, "{self:#?}").unwrap(),
+		}
+	}
+}
@@ -0,0 +1,212 @@
+use std::{cell::RefCell, collections::BTreeMap, path::Path};
+
+use build_info_common::{BuildInfo, Endianness};
+use proc_macro2::Span;
+
+use super::{
+	super::{error::Hint, syntax::CfgPredicate},
+	Value, as_named_arguments_0, as_named_arguments_1,
+};
+
+thread_local! {
+	/// Files read by `include_str!`, which have to be included in the generated code so that cargo rebuilds the crate
+	/// if they change
+	static INCLUDED_FILES: RefCell<Vec<String>> = const { RefCell::new(Vec::new()) };
+}
+
+/// Returns the absolute paths of all files read by `include_str!` since the last call.
+pub fn take_included_files() -> Vec<String> {
+	INCLUDED_FILES.with_borrow_mut(std::mem::take)
+}
+
+/// The location of the macro invocation, as used by `file!`, `line!` and `column!`.
+fn call_site() -> anyhow::Result<proc_macro::Span> {
+	if proc_macro::is_available() {
+		Ok(Span::call_site().unwrap())
+	} else {
+		Err(anyhow::anyhow!(
+			"Source locations are only available during macro expansion"
+		))
+	}
+}
+
+pub(crate) fn call_macro(name: &str, args: &[(Option<String>, Box<dyn Value>)]) -> anyhow::Result<Box<dyn Value>> {
+	match name {
+		"column" => {
+			as_named_arguments_0(args)?;
+			Ok(Box::new(call_site()?.column() as u32))
+		}
+		"compile_error" => {
+			let (message,) = as_named_arguments_1::<String>(args)?;
+			Err(anyhow::anyhow!("{message}"))
+		}
+		"concat" => {
+			let mut result = String::new();
+			for (i, (name, value)) in args.iter().enumerate() {
+				if name.is_some() {
+					anyhow::bail!("`concat!` Takes no named arguments");
+				}
+
+				if let Some(value) = value.as_any().downcast_ref::<String>() {
+					result += value;
+				} else {
+					return Err(
+						Hint {
+							message: format!("Argument {i} to `concat!` is no string."),
+							notes: vec![
+								"Try using `.to_string()`.".to_string(),
+								"Using `concat!` inside `build_info::format!` is special.".to_string(),
+							],
+						}
+						.into(),
+					);
+				}
+			}
+			Ok(Box::new(result))
+		}
+		"env" => {
+			let (name,) = as_named_arguments_1::<String>(args)?;
+			let value = std::env::var(name).map_err(|_| anyhow::anyhow!("Environment variable `{name}` not defined."))?;
+			Ok(Box::new(value))
+		}
+		"file" => {
+			as_named_arguments_0(args)?;
+			Ok(Box::new(call_site()?.file()))
+		}
+		"include_str" => {
+			let (path,) = as_named_arguments_1::<String>(args)?;
+			let manifest_dir = std::env::var_os("CARGO_MANIFEST_DIR")
+				.ok_or_else(|| anyhow::anyhow!("The environment variable CARGO_MANIFEST_DIR is not set"))?;
+			let path = Path::new(&manifest_dir).join(path);
+			let text = std::fs::read_to_string(&path).map_err(|err| Hint {
+				message: format!("Could not read file {}", path.display()),
+				notes: vec![
+					"Paths are relative to the directory containing your Cargo.toml".to_string(),
+					format!("Caused by: {err}"),
+				],
+			})?;
+			let path = path
+				.into_os_string()
+				.into_string()
+				.map_err(|path| anyhow::anyhow!("The path {} is not valid UTF-8", path.display()))?;
+			INCLUDED_FILES.with_borrow_mut(|files| files.push(path));
+			Ok(Box::new(text))
+		}
+		"line" => {
+			as_named_arguments_0(args)?;
+			Ok(Box::new(call_site()?.line() as u32))
+		}
+		"option_env" => {
+			let (name,) = as_named_arguments_1::<String>(args)?;
+			let value = std::env::var(name).ok();
+			Ok(Box::new(value))
+		}
+		_ => Err(
+			Hint {
+				message: format!("Macro `{name}!` cannot be called inside `build_info::format!`"),
+				notes: vec![
+					"Only `cfg!`, `column!`, `compile_error!`, `concat!`, `env!`, `file!`, `include_str!`, `line!`, \
+				 `option_env!` and `stringify!` are implemented for use in `build_info::format!`, as of now."
+						.to_string(),
+				],
+			}
+			.into(),
+		),
+	}
+}
+
+/// The configuration options of the crate being built, as forwarded by build-info-build in `BUILD_INFO_CFG`
+pub(crate) type Cfg = BTreeMap<String, Vec<String>>;
+
+/// Evaluates the predicate of `cfg!` for the crate being built.
+///
+/// Since proc macros do not see the configuration of the crate that invokes them, the predicate is evaluated against
+/// the collected build information (`feature`, `target_os`, `target_family`, `unix`, `windows`, `target_arch`,
+/// `target_pointer_width`, `target_endian` and `target_feature`). All other options, e.g., `debug_assertions` or
+/// `panic = "unwind"`, are looked up in the configuration that the build script saw. Options that are unknown to build
+/// scripts (`test`, `doc` and `doctest`) are an error, since they cannot be evaluated correctly.
+pub(crate) fn eval_cfg(predicate: &CfgPredicate, build_info: &BuildInfo) -> anyhow::Result<bool> {
+	let cfg = match std::env::var("BUILD_INFO_CFG") {
+		Ok(cfg) => Some(serde_json::from_str::<Cfg>(&cfg)?),
+		Err(_) => None,
+	};
+	eval_cfg_with(predicate, build_info, cfg.as_ref())
+}
+
+fn eval_cfg_with(predicate: &CfgPredicate, build_info: &BuildInfo, cfg: Option<&Cfg>) -> anyhow::Result<bool> {
+	let target = &build_info.target;
+	let eval_all = |predicates: &[CfgPredicate]| -> anyhow::Result<Vec<bool>> {
+		// All predicates are evaluated, so that options that cannot be evaluated are reported in any case
+		predicates
+			.iter()
+			.map(|predicate| eval_cfg_with(predicate, build_info, cfg))
+			.collect()
+	};
+	Ok(match predicate {
+		CfgPredicate::All(predicates) => eval_all(predicates)?.into_iter().all(|value| value),
+		CfgPredicate::Any(predicates) => eval_all(predicates)?.into_iter().any(|value| value),
+		CfgPredicate::Not(predicate) => !eval_cfg_with(predicate, build_info, cfg)?,
+		CfgPredicate::Name(name) => match name.as_str() {
+			"unix" | "windows" => target.family == *name,
+			_ => cfg_values(cfg, name)?.is_some(),
+		},
+		CfgPredicate::KeyValue(key, value) => match key.as_str() {
+			"feature" => build_info.crate_info.enabled_features.contains(value),
+			"target_os" => target.os == *value,
+			"target_family" => target.family == *value,
+			"target_arch" => target.cpu.arch == *value,
+			"target_pointer_width" => target.cpu.pointer_width.to_string() == *value,
+			"target_endian" => match target.cpu.endianness {
+				Endianness::Big => value == "big",
+				Endianness::Little => value == "little",
+			},
+			"target_feature" => target.cpu.has_feature(value),
+			_ => cfg_values(cfg, key)?.is_some_and(|values| values.contains(value)),
+		},
+	})
+}
+
+/// Looks up the values of the configuration option `name`, which is `None` if the option is not set.
+fn cfg_values<'a>(cfg: Option<&'a Cfg>, name: &str) -> anyhow::Result<Option<&'a Vec<String>>> {
+	if matches!(name, "test" | "doc" | "doctest") {
+		anyhow::bail!(
+			"`cfg!({name})` cannot be evaluated inside `build_info::format!`, since build scripts do not know it"
+		);
+	}
+	let cfg = cfg.ok_or_else(|| {
+		anyhow::anyhow!(
+			"`cfg!({name})` cannot be evaluated, since `BUILD_INFO_CFG` is not set; is build-info-build used in the build \
+			 script?"
+		)
+	})?;
+	Ok(cfg.get(name))
+}
+
+#[cfg(test)]
+mod test {
+	use build_info_fixtures::build_info;
+
+	use super::*;
+
+	fn eval(predicate: &str, cfg: Option<&Cfg>) -> anyhow::Result<bool> {
+		eval_cfg_with(&syn::parse_str(predicate).unwrap(), &build_info(), cfg)
+	}
+
+	#[test]
+	fn cfg() {
+		let cfg = Cfg::from([
+			("debug_assertions".to_string(), Vec::new()),
+			("panic".to_string(), vec!["unwind".to_string()]),
+		]);
+		assert!(eval("debug_assertions", Some(&cfg)).unwrap());
+		assert!(eval(r#"panic = "unwind""#, Some(&cfg)).unwrap());
+		assert!(!eval(r#"panic = "abort""#, Some(&cfg)).unwrap());
+		assert!(!eval("miri", Some(&cfg)).unwrap());
+		assert!(eval(r#"all(unix, target_arch = "x86_64")"#, None).unwrap());
+
+		assert!(eval("debug_assertions", None).is_err());
+		assert!(eval("test", Some(&cfg)).is_err());
+		assert!(eval("any(unix, test)", Some(&cfg)).is_err());
+		assert!(eval("not(doc)", Some(&cfg)).is_err());
+	}
+}
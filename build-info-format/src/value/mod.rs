@@ -0,0 +1,489 @@
+use std::{
+	any::{Any, TypeId, type_name},
+	fmt::Debug,
+};
+
+use anyhow::anyhow;
+use num_bigint::BigInt;
+use num_traits::ToPrimitive;
+use proc_macro2::TokenStream;
+
+use super::{
+	Type,
+	error::{Hint, similar_name},
+};
+
+mod bool;
+mod char;
+mod float;
+mod int;
+mod option;
+mod string;
+mod typed_int;
+pub(crate) use typed_int::typed_int;
+mod vec;
+pub(crate) use vec::join_human;
+
+mod chrono;
+mod semver;
+
+mod build_info;
+mod compiler_channel;
+mod compiler_info;
+mod cpu_info;
+mod crate_info;
+mod endianness;
+mod git_info;
+mod optimization_level;
+mod target_info;
+mod version_control;
+
+mod loop_info;
+pub(crate) use loop_info::LoopInfo;
+
+mod functions;
+pub(crate) use functions::call_function;
+
+mod macros;
+pub use macros::take_included_files;
+pub(crate) use macros::{call_macro, eval_cfg};
+
+/// Allows cloning values behind a `Box<dyn Value>`, e.g., to return a default value that was passed as an argument.
+pub trait CloneValue {
+	fn clone_value(&self) -> Box<dyn Value>;
+}
+
+impl<T: 'static + Value + Clone> CloneValue for T {
+	fn clone_value(&self) -> Box<dyn Value> {
+		Box::new(self.clone())
+	}
+}
+
+/// Compares values structurally, using the `PartialEq` implementation of their type
+pub trait EqValue {
+	fn eq_value(&self, other: &dyn Value) -> bool;
+}
+
+impl<T: 'static + Value + PartialEq> EqValue for T {
+	fn eq_value(&self, other: &dyn Value) -> bool {
+		other.as_any().downcast_ref::<T>().is_some_and(|other| self == other)
+	}
+}
+
+pub trait Value: Debug + CloneValue + EqValue {
+	fn call_base(&self, func: &str, args: &[Box<dyn Value>]) -> anyhow::Result<Box<dyn Value>> {
+		match func {
+			OP_FIELD_ACCESS => {
+				let field = as_field_name(args);
+				Err(
+					Hint {
+						message: format!(
+							"The field `{}` does not exist for objects of type {}",
+							field,
+							self.get_type()
+						),
+						notes: member_notes(field, self.fields(), "field", self.methods(), "method"),
+					}
+					.into(),
+				)
+			}
+			OP_TUPLE_INDEX => Err(anyhow!("Type {} cannot be tuple-indexed", self.get_type())),
+			OP_ARRAY_INDEX => Err(anyhow!("Type {} cannot be indexed", self.get_type())),
+			"fields" => {
+				as_arguments_0(args)?;
+				Ok(Box::new(names(self.fields())))
+			}
+			"methods" => {
+				as_arguments_0(args)?;
+				Ok(Box::new(names(self.methods())))
+			}
+			"?" => Err(
+				Hint {
+					message: format!("Objects of type {} cannot be unwrapped using `?`", self.get_type()),
+					notes: vec!["Only values of type `Option<_>` can be unwrapped".to_string()],
+				}
+				.into(),
+			),
+			_ => Err(
+				Hint {
+					message: format!(
+						"The method `{}` does not exist for objects of type {}",
+						func,
+						self.get_type()
+					),
+					notes: member_notes(func, self.methods(), "method", self.fields(), "field"),
+				}
+				.into(),
+			),
+		}
+	}
+
+	fn call(&self, func: &str, args: &[Box<dyn Value>]) -> anyhow::Result<Box<dyn Value>> {
+		self.call_base(func, args)
+	}
+
+	/// Calls a method with named arguments (e.g., `x.join_human("and", oxford = true)`), which only few methods accept
+	fn call_named(&self, func: &str, args: &[(Option<String>, Box<dyn Value>)]) -> anyhow::Result<Box<dyn Value>> {
+		let _ = args;
+		Err(anyhow!(
+			"The method `{}` of objects of type {} does not accept named arguments",
+			func,
+			self.get_type()
+		))
+	}
+
+	fn get_type(&self) -> Type;
+
+	/// Names of the fields that can be accessed on objects of this type, which is also available as `x.fields()`
+	fn fields(&self) -> &'static [&'static str] {
+		&[]
+	}
+
+	/// Names of the methods that can be called on objects of this type, which is also available as `x.methods()`
+	///
+	/// The reflection methods `fields` and `methods` themselves are available for all types and not listed here.
+	fn methods(&self) -> &'static [&'static str] {
+		&[]
+	}
+
+	/// The elements of a collection, which can be iterated over using `{% for x in xs %}`
+	fn elements(&self) -> Option<Vec<Box<dyn Value>>> {
+		None
+	}
+
+	fn as_any(&self) -> &dyn Any;
+
+	/// Converts the value into a Rust expression for `build_info::value!`, if it can be represented as a literal
+	fn to_tokens(&self) -> Option<TokenStream> {
+		None
+	}
+
+	fn format(&self, buffer: &mut String, spec: FormatSpecifier);
+
+	/// Formats the value with a precision, as in `{:.2}`. Like strings in `std::format!`, the default formatting is
+	/// truncated to `precision` characters.
+	fn format_precision(&self, buffer: &mut String, precision: usize) {
+		let mut formatted = String::new();
+		self.format(&mut formatted, FormatSpecifier::Default);
+		buffer.extend(formatted.chars().take(precision));
+	}
+}
+
+fn names(names: &[&str]) -> Vec<String> {
+	names.iter().map(|name| name.to_string()).collect()
+}
+
+/// Explains what to use instead of the unknown member `name`, which was used as a `kind` but may be an `other_kind`.
+fn member_notes(
+	name: &str,
+	candidates: &[&str],
+	kind: &str,
+	other_candidates: &[&str],
+	other_kind: &str,
+) -> Vec<String> {
+	if let Some(candidate) = similar_name(name, candidates) {
+		vec![format!("Did you mean `{candidate}`?")]
+	} else if other_candidates.contains(&name) {
+		vec![format!("`{name}` is a {other_kind}, not a {kind}")]
+	} else if candidates.is_empty() {
+		vec![format!("Objects of this type have no {kind}s")]
+	} else {
+		vec![format!("The available {kind}s are: {}", candidates.join(", "))]
+	}
+}
+
+#[derive(Copy, Clone, Eq, PartialEq, Ord, PartialOrd, Hash, Debug)]
+pub enum FormatSpecifier {
+	Default,
+	Debug,
+	DebugAlt,
+}
+
+pub(crate) const OP_FIELD_ACCESS: &str = "!field";
+pub(crate) const OP_TUPLE_INDEX: &str = "!tuple_index";
+pub(crate) const OP_ARRAY_INDEX: &str = "!array_index";
+
+fn as_field_name(args: &[Box<dyn Value>]) -> &str {
+	assert!(
+		args.len() == 1,
+		"Accessing a field must have exactly one operand (the field name)"
+	);
+
+	args[0]
+		.as_any()
+		.downcast_ref::<String>()
+		.expect("The field name must be a string when accessing a field.")
+}
+
+fn as_index(args: &[Box<dyn Value>]) -> anyhow::Result<usize> {
+	assert!(args.len() == 1, "Indexing must have exactly one operand (the index)");
+
+	args[0]
+		.as_any()
+		.downcast_ref::<BigInt>()
+		.ok_or_else(|| anyhow!("The index must be an integer, but has type {}", args[0].get_type()))?
+		.to_usize()
+		.ok_or_else(|| anyhow!("The index does not fit into the type usize"))
+}
+
+fn as_arguments_0(args: &[Box<dyn Value>]) -> anyhow::Result<()> {
+	if args.is_empty() {
+		Ok(())
+	} else {
+		Err(anyhow!("Wrong number of arguments (should be 0)"))
+	}
+}
+
+fn as_simple_arguments_1<T1: 'static>(args: &[Box<dyn Value>]) -> anyhow::Result<(&T1,)> {
+	if args.len() != 1 {
+		return Err(anyhow!("Wrong number of arguments (should be 1)"));
+	}
+
+	Ok((as_argument::<T1>(args, 0)?,))
+}
+
+fn as_simple_arguments_2<T1: 'static, T2: 'static>(args: &[Box<dyn Value>]) -> anyhow::Result<(&T1, &T2)> {
+	if args.len() != 2 {
+		return Err(anyhow!("Wrong number of arguments (should be 2)"));
+	}
+
+	Ok((as_argument::<T1>(args, 0)?, as_argument::<T2>(args, 1)?))
+}
+
+fn as_simple_arguments_3<T1: 'static, T2: 'static, T3: 'static>(
+	args: &[Box<dyn Value>],
+) -> anyhow::Result<(&T1, &T2, &T3)> {
+	if args.len() != 3 {
+		return Err(anyhow!("Wrong number of arguments (should be 3)"));
+	}
+
+	Ok((
+		as_argument::<T1>(args, 0)?,
+		as_argument::<T2>(args, 1)?,
+		as_argument::<T3>(args, 2)?,
+	))
+}
+
+fn as_argument<T: 'static>(args: &[Box<dyn Value>], index: usize) -> anyhow::Result<&T> {
+	args[index].as_any().downcast_ref::<T>().ok_or_else(|| {
+		anyhow!(
+			"Argument #{} should have type {}, but has type {}",
+			index + 1,
+			type_description::<T>(),
+			args[index].get_type()
+		)
+	})
+}
+
+/// Describes `T` the same way `Type` describes values, if possible.
+fn type_description<T: 'static>() -> String {
+	let id = TypeId::of::<T>();
+	if id == TypeId::of::<String>() {
+		Type::String.to_string()
+	} else if id == TypeId::of::<BigInt>() {
+		Type::Integer.to_string()
+	} else if id == TypeId::of::<bool>() {
+		Type::Bool.to_string()
+	} else if id == TypeId::of::<char>() {
+		Type::Char.to_string()
+	} else if id == TypeId::of::<f64>() {
+		Type::Float.to_string()
+	} else {
+		type_name::<T>().to_string()
+	}
+}
+
+fn as_named_arguments_0(args: &[(Option<String>, Box<dyn Value>)]) -> anyhow::Result<()> {
+	if args.is_empty() {
+		Ok(())
+	} else {
+		Err(anyhow!("Wrong number of arguments (should be 0)"))
+	}
+}
+
+fn as_named_arguments_1<T1: 'static>(args: &[(Option<String>, Box<dyn Value>)]) -> anyhow::Result<(&T1,)> {
+	if args.len() != 1 {
+		return Err(anyhow!("Wrong number of arguments (should be 1)"));
+	}
+
+	if args[0].0.is_some() {
+		return Err(anyhow!(
+			"Expected a single positional argument, found a named argument instead"
+		));
+	}
+
+	Ok((args[0].1.as_any().downcast_ref::<T1>().ok_or_else(|| {
+		anyhow!(
+			"Argument #1 should have type {}, but has type {}",
+			type_description::<T1>(),
+			args[0].1.get_type()
+		)
+	})?,))
+}
+
+#[cfg(test)]
+pub(crate) mod test {
+	use build_info_common::{VersionControl, semver::Version};
+	use pretty_assertions::assert_eq;
+
+	use super::{super::types::IntType, *};
+
+	/// Formats `value` like `{}` does
+	pub(crate) fn format(value: &dyn Value) -> String {
+		let mut buffer = String::new();
+		value.format(&mut buffer, FormatSpecifier::Default);
+		buffer
+	}
+
+	/// Calls the method `func` of `value` and formats the result like `{}` does
+	pub(crate) fn call(value: &dyn Value, func: &str, args: Vec<Box<dyn Value>>) -> String {
+		format(&*value.call(func, &args).unwrap())
+	}
+
+	/// Every listed field and method must actually exist.
+	fn check_reflection(value: &dyn Value) {
+		for field in value.fields() {
+			if let Err(err) = value.call(OP_FIELD_ACCESS, &[Box::new(field.to_string())]) {
+				panic!("{}.{} does not exist: {}", value.get_type(), field, err);
+			}
+		}
+		for method in value.methods() {
+			if let Err(err) = value.call(method, &[]) {
+				assert!(
+					err.downcast_ref::<Hint>().is_none(),
+					"{}.{}() does not exist: {}",
+					value.get_type(),
+					method,
+					err
+				);
+			}
+		}
+	}
+
+	/// A value of every type, so that adding a type requires checking its reflection
+	fn example(ty: Type) -> Box<dyn Value> {
+		let build_info = build_info_fixtures::build_info();
+		let Some(VersionControl::Git(git_info)) = build_info.version_control.clone() else {
+			unreachable!("The fixture is built from a git repository");
+		};
+		match ty {
+			Type::Bool => Box::new(true),
+			Type::Char => Box::new('x'),
+			Type::Integer => Box::new(BigInt::from(42)),
+			Type::Int(IntType::I8) => Box::new(42i8),
+			Type::Int(IntType::I16) => Box::new(42i16),
+			Type::Int(IntType::I32) => Box::new(42i32),
+			Type::Int(IntType::I64) => Box::new(42i64),
+			Type::Int(IntType::I128) => Box::new(42i128),
+			Type::Int(IntType::Isize) => Box::new(42isize),
+			Type::Int(IntType::U8) => Box::new(42u8),
+			Type::Int(IntType::U16) => Box::new(42u16),
+			Type::Int(IntType::U32) => Box::new(42u32),
+			Type::Int(IntType::U64) => Box::new(42u64),
+			Type::Int(IntType::U128) => Box::new(42u128),
+			Type::Int(IntType::Usize) => Box::new(42usize),
+			Type::Float => Box::new(2.5f64),
+			Type::String => Box::new("x".to_string()),
+
+			Type::BuildInfo => Box::new(build_info),
+			Type::OptimizationLevel => Box::new(build_info.optimization_level),
+			Type::CrateInfo => Box::new(build_info.crate_info),
+			Type::CompilerInfo => Box::new(build_info.compiler),
+			Type::CompilerChannel => Box::new(build_info.compiler.channel),
+			Type::VersionControl => Box::new(build_info.version_control.unwrap()),
+			Type::GitInfo => Box::new(git_info),
+			Type::TargetInfo => Box::new(build_info.target),
+			Type::CpuInfo => Box::new(build_info.target.cpu),
+			Type::Endianness => Box::new(build_info.target.cpu.endianness),
+
+			Type::Version => Box::new(build_info.crate_info.version),
+			Type::DateTimeUtc => Box::new(build_info.timestamp),
+			Type::DateTimeTz => Box::new(build_info.timestamp.with_timezone(&chrono_tz::Europe::Berlin)),
+			Type::NaiveDate => Box::new(build_info.timestamp.date_naive()),
+			Type::Duration => Box::new(build_info.timestamp - git_info.commit_timestamp),
+
+			Type::Option => Box::new(git_info.branch),
+			Type::Vec => Box::new(build_info.crate_info.authors),
+			Type::LoopInfo => Box::new(LoopInfo { index: 0, length: 1 }),
+		}
+	}
+
+	#[test]
+	fn reflection() {
+		let int_types = [
+			IntType::I8,
+			IntType::I16,
+			IntType::I32,
+			IntType::I64,
+			IntType::I128,
+			IntType::Isize,
+			IntType::U8,
+			IntType::U16,
+			IntType::U32,
+			IntType::U64,
+			IntType::U128,
+			IntType::Usize,
+		];
+		let types = [
+			Type::Bool,
+			Type::Char,
+			Type::Integer,
+			Type::Float,
+			Type::String,
+			Type::BuildInfo,
+			Type::OptimizationLevel,
+			Type::CrateInfo,
+			Type::CompilerInfo,
+			Type::CompilerChannel,
+			Type::VersionControl,
+			Type::GitInfo,
+			Type::TargetInfo,
+			Type::CpuInfo,
+			Type::Endianness,
+			Type::Version,
+			Type::DateTimeUtc,
+			Type::DateTimeTz,
+			Type::NaiveDate,
+			Type::Duration,
+			Type::Option,
+			Type::Vec,
+			Type::LoopInfo,
+		];
+		for ty in types.into_iter().chain(int_types.map(Type::Int)) {
+			let value = example(ty);
+			assert_eq!(value.get_type(), ty);
+			check_reflection(&*value);
+		}
+
+		assert_eq!(
+			format(
+				&*Version::new(1, 2, 3)
+					.call("fields", &[])
+					.unwrap()
+					.call("join", &[Box::new(" ".to_string())])
+					.unwrap()
+			),
+			"build major minor patch pre"
+		);
+		assert_eq!(format(&*true.call("methods", &[]).unwrap()), "to_string");
+		assert_eq!(format(&*BigInt::from(1).call("fields", &[]).unwrap()), "");
+	}
+
+	#[test]
+	fn to_tokens() {
+		let tokens = |value: &dyn Value| value.to_tokens().map(|tokens| tokens.to_string());
+		assert_eq!(tokens(&BigInt::from(3)), Some("3u64".to_string()));
+		assert_eq!(tokens(&BigInt::from(-3)), Some("- 3i64".to_string()));
+		assert_eq!(tokens(&BigInt::from(u128::MAX)), Some(format!("{}u128", u128::MAX)));
+		assert_eq!(tokens(&(BigInt::from(u128::MAX) + 1u8)), None);
+		assert_eq!(tokens(&200u8), Some("200u8".to_string()));
+		assert_eq!(tokens(&-1i64), Some("- 1i64".to_string()));
+		assert_eq!(tokens(&vec!['a', 'b']), Some("['a' , 'b']".to_string()));
+		assert_eq!(
+			tokens(&Some("x".to_string())),
+			Some(":: core :: option :: Option :: Some (\"x\")".to_string())
+		);
+		assert_eq!(tokens(&Version::new(1, 2, 3)), None);
+		assert_eq!(tokens(&vec![Version::new(1, 2, 3)]), None);
+	}
+}
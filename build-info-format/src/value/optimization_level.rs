@@ -19,6 +19,10 @@ impl Value for OptimizationLevel {
 		Type::OptimizationLevel
 	}
 
+	fn methods(&self) -> &'static [&'static str] {
+		&["to_string"]
+	}
+
 	fn as_any(&self) -> &dyn Any {
 		self
 	}
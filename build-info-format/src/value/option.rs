@@ -1,10 +1,15 @@
 use std::any::Any;
 
 use anyhow::anyhow;
+use proc_macro2::{Span, TokenStream};
+use quote::quote;
 
-use super::{FormatSpecifier, Type, Value, as_arguments_0, as_simple_arguments_1};
+use super::{
+	super::{error::UnwrapNone, interpolate},
+	FormatSpecifier, Type, Value, as_argument, as_arguments_0, as_simple_arguments_1,
+};
 
-impl<T: 'static + Value + Clone> Value for Option<T> {
+impl<T: 'static + Value + Clone + PartialEq> Value for Option<T> {
 	fn call(&self, func: &str, args: &[Box<dyn Value>]) -> anyhow::Result<Box<dyn Value>> {
 		match func {
 			"is_none" => {
@@ -31,12 +36,33 @@ impl<T: 'static + Value + Clone> Value for Option<T> {
 					.map(|value| Box::new(value) as Box<dyn Value>)
 					.ok_or_else(|| anyhow!("Could not unwrap Option (object does not contain a value)"))
 			}
+			"unwrap_or" => {
+				if args.len() != 1 {
+					return Err(anyhow!("Wrong number of arguments (should be 1)"));
+				}
+				match self {
+					Some(value) => Ok(Box::new(value.clone())),
+					None => Ok(args[0].clone_value()),
+				}
+			}
+			"map_or" => {
+				if args.len() != 2 {
+					return Err(anyhow!("Wrong number of arguments (should be 2)"));
+				}
+				let template = as_argument::<String>(args, 1)?;
+				match self {
+					Some(value) => Ok(Box::new(
+						interpolate(template, &[(None, Box::new(value.clone()))], Span::call_site()).map_err(|err| err.error)?,
+					)),
+					None => Ok(args[0].clone_value()),
+				}
+			}
 			"?" => {
 				as_arguments_0(args)?;
 				self
 					.clone()
 					.map(|value| Box::new(value) as Box<dyn Value>)
-					.ok_or_else(|| anyhow!("Could not unwrap Option (object does not contain a value)"))
+					.ok_or_else(|| UnwrapNone.into())
 			}
 			_ => self.call_base(func, args),
 		}
@@ -46,10 +72,24 @@ impl<T: 'static + Value + Clone> Value for Option<T> {
 		Type::Option
 	}
 
+	fn methods(&self) -> &'static [&'static str] {
+		&["expect", "is_none", "is_some", "map_or", "unwrap", "unwrap_or"]
+	}
+
 	fn as_any(&self) -> &dyn Any {
 		self
 	}
 
+	fn to_tokens(&self) -> Option<TokenStream> {
+		match self {
+			Some(value) => {
+				let value = value.to_tokens()?;
+				Some(quote!(::core::option::Option::Some(#value)))
+			}
+			None => Some(quote!(::core::option::Option::None)),
+		}
+	}
+
 	fn format(&self, buffer: &mut String, spec: FormatSpecifier) {
 		use std::fmt::Write;
 
@@ -69,6 +109,39 @@ mod test {
 	use pretty_assertions::assert_eq;
 
 	use super::*;
+	use crate::value::test::call;
+
+	#[test]
+	fn fallbacks() {
+		let main = || Some("main".to_string());
+		assert_eq!(
+			call(&main(), "unwrap_or", vec![Box::new("unknown".to_string())]),
+			"main"
+		);
+		assert_eq!(
+			call(&None::<String>, "unwrap_or", vec![Box::new("unknown".to_string())]),
+			"unknown"
+		);
+		assert_eq!(
+			call(
+				&main(),
+				"map_or",
+				vec![Box::new(String::new()), Box::new(" (branch {})".to_string())]
+			),
+			" (branch main)"
+		);
+		assert_eq!(
+			call(
+				&None::<String>,
+				"map_or",
+				vec![Box::new(String::new()), Box::new(" (branch {})".to_string())]
+			),
+			""
+		);
+
+		let error = (None as Option<String>).call("?", &[]).unwrap_err();
+		assert!(error.is::<UnwrapNone>());
+	}
 
 	#[test]
 	fn format_default() {
@@ -0,0 +1,257 @@
+use std::{any::Any, cmp::Ordering};
+
+use anyhow::anyhow;
+use build_info_common::semver::{BuildMetadata, Prerelease, Version, VersionReq};
+use num_bigint::BigInt;
+
+use super::super::{
+	FormatSpecifier, OP_FIELD_ACCESS, Type, Value, as_arguments_0, as_field_name, as_simple_arguments_1,
+};
+
+impl Value for Version {
+	fn call(&self, func: &str, args: &[Box<dyn Value>]) -> anyhow::Result<Box<dyn Value>> {
+		match func {
+			OP_FIELD_ACCESS => match as_field_name(args) {
+				"major" => Ok(Box::new(BigInt::from(self.major))),
+				"minor" => Ok(Box::new(BigInt::from(self.minor))),
+				"patch" => Ok(Box::new(BigInt::from(self.patch))),
+				"pre" => Ok(Box::new(self.pre.to_string())),
+				"build" => Ok(Box::new(self.build.to_string())),
+				_ => self.call_base(func, args),
+			},
+			// As for npm and cargo-release, bumping a pre-release yields the release it precedes, if there is one
+			// (e.g., `1.2.3-rc.1` becomes `1.2.3`, but `1.2.3-rc.1` becomes `1.3.0` for a minor version bump)
+			"bump_major" => {
+				as_arguments_0(args)?;
+				if !self.pre.is_empty() && self.minor == 0 && self.patch == 0 {
+					return Ok(Box::new(Version::new(self.major, 0, 0)));
+				}
+				let major = self.major.checked_add(1).ok_or_else(|| overflow(self, "major"))?;
+				Ok(Box::new(Version::new(major, 0, 0)))
+			}
+			"bump_minor" => {
+				as_arguments_0(args)?;
+				if !self.pre.is_empty() && self.patch == 0 {
+					return Ok(Box::new(Version::new(self.major, self.minor, 0)));
+				}
+				let minor = self.minor.checked_add(1).ok_or_else(|| overflow(self, "minor"))?;
+				Ok(Box::new(Version::new(self.major, minor, 0)))
+			}
+			"bump_patch" => {
+				as_arguments_0(args)?;
+				if !self.pre.is_empty() {
+					return Ok(Box::new(Version::new(self.major, self.minor, self.patch)));
+				}
+				let patch = self.patch.checked_add(1).ok_or_else(|| overflow(self, "patch"))?;
+				Ok(Box::new(Version::new(self.major, self.minor, patch)))
+			}
+			// Build metadata does not affect precedence, so `1.2.3+abc` and `1.2.3+def` compare as equal
+			"eq" => Ok(Box::new(
+				self.cmp_precedence(&as_version_argument(args)?) == Ordering::Equal,
+			)),
+			"ge" => Ok(Box::new(
+				self.cmp_precedence(&as_version_argument(args)?) != Ordering::Less,
+			)),
+			"gt" => Ok(Box::new(
+				self.cmp_precedence(&as_version_argument(args)?) == Ordering::Greater,
+			)),
+			"is_prerelease" => {
+				as_arguments_0(args)?;
+				Ok(Box::new(!self.pre.is_empty()))
+			}
+			"le" => Ok(Box::new(
+				self.cmp_precedence(&as_version_argument(args)?) != Ordering::Greater,
+			)),
+			"lt" => Ok(Box::new(
+				self.cmp_precedence(&as_version_argument(args)?) == Ordering::Less,
+			)),
+			"matches" => {
+				let (requirement,) = as_simple_arguments_1::<String>(args)?;
+				let requirement = VersionReq::parse(requirement)
+					.map_err(|err| anyhow!("Could not parse version requirement {requirement:?}: {err}"))?;
+				Ok(Box::new(requirement.matches(self)))
+			}
+			"ne" => Ok(Box::new(
+				self.cmp_precedence(&as_version_argument(args)?) != Ordering::Equal,
+			)),
+			"to_string" => {
+				as_arguments_0(args)?;
+				Ok(Box::new(self.to_string()))
+			}
+			"without_build" => {
+				as_arguments_0(args)?;
+				Ok(Box::new(Version {
+					build: BuildMetadata::EMPTY,
+					..self.clone()
+				}))
+			}
+			"without_pre" => {
+				as_arguments_0(args)?;
+				Ok(Box::new(Version {
+					pre: Prerelease::EMPTY,
+					..self.clone()
+				}))
+			}
+			_ => self.call_base(func, args),
+		}
+	}
+
+	fn get_type(&self) -> Type {
+		Type::Version
+	}
+
+	fn fields(&self) -> &'static [&'static str] {
+		&["build", "major", "minor", "patch", "pre"]
+	}
+
+	fn methods(&self) -> &'static [&'static str] {
+		&[
+			"bump_major",
+			"bump_minor",
+			"bump_patch",
+			"eq",
+			"ge",
+			"gt",
+			"is_prerelease",
+			"le",
+			"lt",
+			"matches",
+			"ne",
+			"to_string",
+			"without_build",
+			"without_pre",
+		]
+	}
+
+	fn as_any(&self) -> &dyn Any {
+		self
+	}
+
+	fn format(&self, buffer: &mut String, spec: FormatSpecifier) {
+		use std::fmt::Write;
+
+		match spec {
+			FormatSpecifier::Default => write!(buffer, "{self}").unwrap(),
+			FormatSpecifier::Debug => write!(buffer, "{self:?}").unwrap(),
+			FormatSpecifier::DebugAlt => write!(buffer, "{self:#?}").unwrap(),
+		}
+	}
+}
+
+fn overflow(version: &Version, part: &str) -> anyhow::Error {
+	anyhow!("Cannot bump the {part} version of {version}, since it would overflow")
+}
+
+/// Comparisons accept either another version or a string that can be parsed as one.
+fn as_version_argument(args: &[Box<dyn Value>]) -> anyhow::Result<Version> {
+	if args.len() != 1 {
+		return Err(anyhow!("Wrong number of arguments (should be 1)"));
+	}
+
+	if let Some(version) = args[0].as_any().downcast_ref::<Version>() {
+		Ok(version.clone())
+	} else if let Some(version) = args[0].as_any().downcast_ref::<String>() {
+		Version::parse(version).map_err(|err| anyhow!("Could not parse version {version:?}: {err}"))
+	} else {
+		Err(anyhow!(
+			"Argument #1 should be a version or a string, but has type {}",
+			args[0].get_type()
+		))
+	}
+}
+
+#[cfg(test)]
+mod test {
+	use pretty_assertions::assert_eq;
+
+	use super::*;
+	use crate::value::test::call;
+
+	fn version(version: &str) -> Version {
+		Version::parse(version).unwrap()
+	}
+
+	#[test]
+	fn bump() {
+		assert_eq!(call(&version("1.2.3-rc.1+abc"), "bump_major", vec![]), "2.0.0");
+		assert_eq!(call(&version("1.2.3-rc.1+abc"), "bump_minor", vec![]), "1.3.0");
+		assert_eq!(call(&version("1.2.3-rc.1+abc"), "bump_patch", vec![]), "1.2.3");
+		assert_eq!(call(&version("1.2.3+abc"), "bump_patch", vec![]), "1.2.4");
+		assert_eq!(call(&version("1.3.0-rc.1"), "bump_minor", vec![]), "1.3.0");
+		assert_eq!(call(&version("2.0.0-rc.1"), "bump_major", vec![]), "2.0.0");
+		assert_eq!(call(&version("2.0.1-rc.1"), "bump_major", vec![]), "3.0.0");
+		for func in ["bump_major", "bump_minor", "bump_patch"] {
+			let version = Version::new(u64::MAX, u64::MAX, u64::MAX);
+			assert!(version.call(func, &[]).is_err());
+		}
+		assert_eq!(call(&version("1.2.3-rc.1+abc"), "without_pre", vec![]), "1.2.3+abc");
+		assert_eq!(call(&version("1.2.3-rc.1+abc"), "without_build", vec![]), "1.2.3-rc.1");
+	}
+
+	#[test]
+	fn prerelease() {
+		assert_eq!(call(&version("1.2.3-rc.1"), "is_prerelease", vec![]), "true");
+		assert_eq!(call(&version("1.2.3+abc"), "is_prerelease", vec![]), "false");
+	}
+
+	#[test]
+	fn matches() {
+		assert_eq!(
+			call(&version("1.2.3"), "matches", vec![Box::new("^1.2".to_string())]),
+			"true"
+		);
+		assert_eq!(
+			call(&version("1.2.3"), "matches", vec![Box::new(">=1.3, <2".to_string())]),
+			"false"
+		);
+		assert!(
+			Version::new(1, 2, 3)
+				.call("matches", &[Box::new("not a requirement".to_string())])
+				.is_err()
+		);
+	}
+
+	#[test]
+	fn compare() {
+		assert_eq!(
+			call(&version("1.2.3"), "lt", vec![Box::new("1.10.0".to_string())]),
+			"true"
+		);
+		assert_eq!(
+			call(&version("1.2.3"), "gt", vec![Box::new("1.2.3-rc.1".to_string())]),
+			"true"
+		);
+		assert_eq!(
+			call(&version("1.2.3"), "eq", vec![Box::new(Version::new(1, 2, 3))]),
+			"true"
+		);
+		assert_eq!(
+			call(&version("1.2.3"), "ne", vec![Box::new(Version::new(1, 2, 3))]),
+			"false"
+		);
+		assert_eq!(
+			call(&version("1.2.3"), "le", vec![Box::new("1.2.3".to_string())]),
+			"true"
+		);
+		assert_eq!(
+			call(&version("1.2.3"), "ge", vec![Box::new("1.2.4".to_string())]),
+			"false"
+		);
+		assert_eq!(
+			call(&version("1.2.3+abc"), "eq", vec![Box::new("1.2.3+def".to_string())]),
+			"true"
+		);
+		assert_eq!(
+			call(&version("1.2.3+abc"), "ne", vec![Box::new("1.2.3".to_string())]),
+			"false"
+		);
+		assert_eq!(
+			call(&version("1.2.3+abc"), "gt", vec![Box::new("1.2.3".to_string())]),
+			"false"
+		);
+		assert_eq!(
+			call(&version("1.2.3+abc"), "le", vec![Box::new("1.2.3".to_string())]),
+			"true"
+		);
+	}
+}
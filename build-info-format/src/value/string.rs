@@ -1,6 +1,8 @@
 use std::any::Any;
 
 use num_bigint::BigInt;
+use proc_macro2::TokenStream;
+use quote::quote;
 
 use super::{FormatSpecifier, Type, Value, as_arguments_0};
 
@@ -47,10 +49,27 @@ impl Value for String {
 		Type::String
 	}
 
+	fn methods(&self) -> &'static [&'static str] {
+		&[
+			"is_empty",
+			"len",
+			"to_lowercase",
+			"to_string",
+			"to_uppercase",
+			"trim",
+			"trim_end",
+			"trim_start",
+		]
+	}
+
 	fn as_any(&self) -> &dyn Any {
 		self
 	}
 
+	fn to_tokens(&self) -> Option<TokenStream> {
+		Some(quote!(#self))
+	}
+
 	fn format(&self, buffer: &mut String, spec: FormatSpecifier) {
 		use std::fmt::Write;
 
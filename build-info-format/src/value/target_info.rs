@@ -23,7 +23,15 @@ impl Value for TargetInfo {
 	}
 
 	fn get_type(&self) -> Type {
-		Type::CompilerInfo
+		Type::TargetInfo
+	}
+
+	fn fields(&self) -> &'static [&'static str] {
+		&["cpu", "family", "os", "triple"]
+	}
+
+	fn methods(&self) -> &'static [&'static str] {
+		&["to_string"]
 	}
 
 	fn as_any(&self) -> &dyn Any {
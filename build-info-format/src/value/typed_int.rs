@@ -0,0 +1,192 @@
+use std::any::Any;
+
+use anyhow::anyhow;
+use num_bigint::BigInt;
+use proc_macro2::TokenStream;
+
+use super::{super::types::IntType, FormatSpecifier, Type, Value, as_arguments_0, int::int_literal};
+
+/// Converts an integer literal with the suffix `int_type` (e.g., `42u8`) into a value of that type.
+pub(crate) fn typed_int(value: &BigInt, int_type: IntType) -> anyhow::Result<Box<dyn Value>> {
+	fn convert<T: 'static + Value + TryFrom<BigInt>>(
+		value: &BigInt,
+		int_type: IntType,
+	) -> anyhow::Result<Box<dyn Value>> {
+		T::try_from(value.clone())
+			.map(|value| Box::new(value) as Box<dyn Value>)
+			.map_err(|_err| anyhow!("The literal {value}{int_type} does not fit into the type {int_type}"))
+	}
+
+	match int_type {
+		IntType::I8 => convert::<i8>(value, int_type),
+		IntType::I16 => convert::<i16>(value, int_type),
+		IntType::I32 => convert::<i32>(value, int_type),
+		IntType::I64 => convert::<i64>(value, int_type),
+		IntType::I128 => convert::<i128>(value, int_type),
+		IntType::Isize => convert::<isize>(value, int_type),
+		IntType::U8 => convert::<u8>(value, int_type),
+		IntType::U16 => convert::<u16>(value, int_type),
+		IntType::U32 => convert::<u32>(value, int_type),
+		IntType::U64 => convert::<u64>(value, int_type),
+		IntType::U128 => convert::<u128>(value, int_type),
+		IntType::Usize => convert::<usize>(value, int_type),
+	}
+}
+
+/// Arithmetic accepts another value of the same type, or an untyped integer that fits into it.
+fn as_operand<T: 'static + Copy + TryFrom<BigInt>>(args: &[Box<dyn Value>], int_type: IntType) -> anyhow::Result<T> {
+	if args.len() != 1 {
+		return Err(anyhow!("Wrong number of arguments (should be 1)"));
+	}
+
+	if let Some(value) = args[0].as_any().downcast_ref::<T>() {
+		Ok(*value)
+	} else if let Some(value) = args[0].as_any().downcast_ref::<BigInt>() {
+		T::try_from(value.clone()).map_err(|_err| anyhow!("The integer {value} does not fit into the type {int_type}"))
+	} else {
+		Err(anyhow!(
+			"Argument #1 should have type {} or integer, but has type {}",
+			int_type,
+			args[0].get_type()
+		))
+	}
+}
+
+macro_rules! impl_typed_int {
+	($($ty:ident => $int_type:ident),* $(,)?) => {$(
+		impl Value for $ty {
+			fn call(&self, func: &str, args: &[Box<dyn Value>]) -> anyhow::Result<Box<dyn Value>> {
+				let int_type = IntType::$int_type;
+				let checked = |result: Option<$ty>, operand: $ty| {
+					result
+						.map(|value| Box::new(value) as Box<dyn Value>)
+						.ok_or_else(|| {
+							anyhow!(
+								"The result of {}{}.{}({}) cannot be represented as {}",
+								self,
+								int_type,
+								func,
+								operand,
+								int_type
+							)
+						})
+				};
+
+				match func {
+					"add" => {
+						let operand = as_operand::<$ty>(args, int_type)?;
+						checked(self.checked_add(operand), operand)
+					}
+					"div" => {
+						let operand = as_operand::<$ty>(args, int_type)?;
+						checked(self.checked_div(operand), operand)
+					}
+					"mul" => {
+						let operand = as_operand::<$ty>(args, int_type)?;
+						checked(self.checked_mul(operand), operand)
+					}
+					"rem" => {
+						let operand = as_operand::<$ty>(args, int_type)?;
+						checked(self.checked_rem(operand), operand)
+					}
+					"sub" => {
+						let operand = as_operand::<$ty>(args, int_type)?;
+						checked(self.checked_sub(operand), operand)
+					}
+					"to_f64" => {
+						as_arguments_0(args)?;
+						Ok(Box::new(*self as f64))
+					}
+					"to_integer" => {
+						as_arguments_0(args)?;
+						Ok(Box::new(BigInt::from(*self)))
+					}
+					"to_string" => {
+						as_arguments_0(args)?;
+						Ok(Box::new(self.to_string()))
+					}
+					_ => self.call_base(func, args),
+				}
+			}
+
+			fn get_type(&self) -> Type {
+				Type::Int(IntType::$int_type)
+			}
+
+			fn methods(&self) -> &'static [&'static str] {
+				&["add", "div", "mul", "rem", "sub", "to_f64", "to_integer", "to_string"]
+			}
+
+			fn as_any(&self) -> &dyn Any {
+				self
+			}
+
+			fn to_tokens(&self) -> Option<TokenStream> {
+				Some(int_literal(&self.to_string(), stringify!($ty)))
+			}
+
+			fn format(&self, buffer: &mut String, spec: FormatSpecifier) {
+				use std::fmt::Write;
+
+				match spec {
+					FormatSpecifier::Default => write!(buffer, "{self}").unwrap(),
+					FormatSpecifier::Debug => write!(buffer, "{self:?}").unwrap(),
+					FormatSpecifier::DebugAlt => write!(buffer, "{self:#?}").unwrap(),
+				}
+			}
+
+			fn format_precision(&self, buffer: &mut String, _precision: usize) {
+				// Like in `std::format!`, the precision is ignored for integers
+				self.format(buffer, FormatSpecifier::Default);
+			}
+		}
+	)*};
+}
+
+impl_typed_int!(
+	i8 => I8,
+	i16 => I16,
+	i32 => I32,
+	i64 => I64,
+	i128 => I128,
+	isize => Isize,
+	u8 => U8,
+	u16 => U16,
+	u32 => U32,
+	u64 => U64,
+	u128 => U128,
+	usize => Usize,
+);
+
+#[cfg(test)]
+mod test {
+	use pretty_assertions::assert_eq;
+
+	use super::*;
+	use crate::value::test::format;
+
+	#[test]
+	fn literals() {
+		assert_eq!(
+			typed_int(&BigInt::from(255), IntType::U8).unwrap().get_type(),
+			Type::Int(IntType::U8)
+		);
+		assert!(typed_int(&BigInt::from(256), IntType::U8).is_err());
+		assert!(typed_int(&BigInt::from(-1), IntType::Usize).is_err());
+		assert_eq!(format(&*typed_int(&BigInt::from(-128), IntType::I8).unwrap()), "-128");
+	}
+
+	#[test]
+	fn overflow() {
+		assert_eq!(format(&*200u8.call("add", &[Box::new(55u8)]).unwrap()), "255");
+		assert_eq!(
+			format(&*200u8.call("add", &[Box::new(BigInt::from(55))]).unwrap()),
+			"255"
+		);
+		assert!(200u8.call("add", &[Box::new(56u8)]).is_err());
+		assert!(200u8.call("add", &[Box::new(BigInt::from(256))]).is_err());
+		assert!(0u32.call("sub", &[Box::new(1u32)]).is_err());
+		assert!(1i64.call("div", &[Box::new(0i64)]).is_err());
+		assert!(1i64.call("add", &[Box::new(1u64)]).is_err());
+	}
+}
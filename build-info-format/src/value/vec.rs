@@ -0,0 +1,314 @@
+use std::any::Any;
+
+use anyhow::anyhow;
+use num_bigint::BigInt;
+use num_traits::cast::ToPrimitive;
+use proc_macro2::TokenStream;
+use quote::quote;
+
+use super::{
+	FormatSpecifier, OP_ARRAY_INDEX, Type, Value, as_argument, as_arguments_0, as_index, as_simple_arguments_1,
+};
+
+impl<T: 'static + Value + Clone + PartialEq> Value for Vec<T> {
+	fn call(&self, func: &str, args: &[Box<dyn Value>]) -> anyhow::Result<Box<dyn Value>> {
+		match func {
+			"get" => {
+				let (index,) = as_simple_arguments_1::<BigInt>(args)?;
+				Ok(Box::new(index.to_usize().and_then(|index| self.get(index).cloned())))
+			}
+			"join" => {
+				let (separator,) = as_simple_arguments_1::<String>(args)?;
+				Ok(Box::new(self.formatted().join(separator)))
+			}
+			"join_human" => {
+				let (conjunction, oxford) = match args.len() {
+					1 => (as_argument::<String>(args, 0)?, false),
+					2 => (as_argument::<String>(args, 0)?, *as_argument::<bool>(args, 1)?),
+					_ => return Err(anyhow!("Wrong number of arguments (should be 1 or 2)")),
+				};
+				Ok(Box::new(join_human(&self.formatted(), conjunction, oxford)))
+			}
+			"join_locale" => {
+				let (locale,) = as_simple_arguments_1::<String>(args)?;
+				let (conjunction, oxford) = locale_conjunction(locale)?;
+				Ok(Box::new(join_human(&self.formatted(), conjunction, oxford)))
+			}
+			"is_empty" => {
+				as_arguments_0(args)?;
+				Ok(Box::new(self.is_empty()))
+			}
+			"len" => {
+				as_arguments_0(args)?;
+				Ok(Box::new(BigInt::from(self.len())))
+			}
+			OP_ARRAY_INDEX => {
+				let index = as_index(args)?;
+				let value = self
+					.get(index)
+					.ok_or_else(|| {
+						anyhow!(
+							"Index out of bounds: the len is {} but the index is {}",
+							self.len(),
+							index
+						)
+					})?
+					.clone();
+				Ok(Box::new(value))
+			}
+			_ => self.call_base(func, args),
+		}
+	}
+
+	fn call_named(&self, func: &str, args: &[(Option<String>, Box<dyn Value>)]) -> anyhow::Result<Box<dyn Value>> {
+		match func {
+			"join_human" => {
+				let (conjunction, oxford) = match args {
+					[(None, conjunction), (Some(name), oxford)] if name == "oxford" => (
+						conjunction.as_any().downcast_ref::<String>().ok_or_else(|| {
+							anyhow!(
+								"Argument #1 should have type string, but has type {}",
+								conjunction.get_type()
+							)
+						})?,
+						*oxford.as_any().downcast_ref::<bool>().ok_or_else(|| {
+							anyhow!(
+								"Argument `oxford` should have type bool, but has type {}",
+								oxford.get_type()
+							)
+						})?,
+					),
+					_ => {
+						return Err(anyhow!(
+							"Expected a conjunction and the named argument `oxford`, e.g., `join_human(\"and\", oxford = true)`"
+						));
+					}
+				};
+				Ok(Box::new(join_human(&self.formatted(), conjunction, oxford)))
+			}
+			_ => Err(anyhow!(
+				"The method `{}` of objects of type {} does not accept named arguments",
+				func,
+				self.get_type()
+			)),
+		}
+	}
+
+	fn get_type(&self) -> Type {
+		Type::Vec
+	}
+
+	fn methods(&self) -> &'static [&'static str] {
+		&["get", "is_empty", "join", "join_human", "join_locale", "len"]
+	}
+
+	fn elements(&self) -> Option<Vec<Box<dyn Value>>> {
+		Some(
+			self
+				.iter()
+				.map(|value| Box::new(value.clone()) as Box<dyn Value>)
+				.collect(),
+		)
+	}
+
+	fn as_any(&self) -> &dyn Any {
+		self
+	}
+
+	fn to_tokens(&self) -> Option<TokenStream> {
+		let elements = self.iter().map(Value::to_tokens).collect::<Option<Vec<_>>>()?;
+		Some(quote!([#(#elements),*]))
+	}
+
+	fn format(&self, buffer: &mut String, spec: FormatSpecifier) {
+		use std::fmt::Write;
+
+		match spec {
+			FormatSpecifier::Default => {
+				*buffer += &join_human(&self.formatted(), "and", cfg!(feature = "oxford-comma"));
+			}
+			FormatSpecifier::Debug => write!(buffer, "{self:?}").unwrap(),
+			FormatSpecifier::DebugAlt => write!(buffer, "{self:#?}").unwrap(),
+		}
+	}
+}
+
+trait Formatted {
+	/// Formats every element using its default formatting
+	fn formatted(&self) -> Vec<String>;
+}
+
+impl<T: Value> Formatted for Vec<T> {
+	fn formatted(&self) -> Vec<String> {
+		self
+			.iter()
+			.map(|value| {
+				let mut buffer = String::new();
+				value.format(&mut buffer, FormatSpecifier::Default);
+				buffer
+			})
+			.collect()
+	}
+}
+
+/// Joins `items` as in "a, b and c", optionally with an Oxford comma as in "a, b, and c".
+pub(crate) fn join_human(items: &[String], conjunction: &str, oxford: bool) -> String {
+	let mut result = String::new();
+	for (i, item) in items.iter().enumerate() {
+		if i > 0 {
+			if i < items.len() - 1 {
+				result += ", ";
+			} else {
+				if oxford && i > 1 {
+					result += ",";
+				}
+				result += " ";
+				result += conjunction;
+				result += " ";
+			}
+		}
+		result += item;
+	}
+	result
+}
+
+/// Locales whose lists are joined with a word for "and", along with whether they use a serial comma.
+const LOCALE_CONJUNCTIONS: &[(&str, &str, bool)] = &[
+	("da", "og", false),
+	("de", "und", false),
+	("en", "and", false),
+	("en-US", "and", true),
+	("es", "y", false),
+	("fr", "et", false),
+	("it", "e", false),
+	("nl", "en", false),
+	("no", "og", false),
+	("pl", "i", false),
+	("pt", "e", false),
+	("sv", "och", false),
+];
+
+fn locale_conjunction(locale: &str) -> anyhow::Result<(&'static str, bool)> {
+	let locale = locale.replace('_', "-");
+	LOCALE_CONJUNCTIONS
+		.iter()
+		.find(|(name, _conjunction, _oxford)| name.eq_ignore_ascii_case(&locale))
+		.or_else(|| {
+			let language = locale.split('-').next().unwrap_or_default();
+			LOCALE_CONJUNCTIONS
+				.iter()
+				.find(|(name, _conjunction, _oxford)| name.eq_ignore_ascii_case(language))
+		})
+		.map(|(_name, conjunction, oxford)| (*conjunction, *oxford))
+		.ok_or_else(|| {
+			let locales: Vec<_> = LOCALE_CONJUNCTIONS.iter().map(|(name, ..)| *name).collect();
+			anyhow!(
+				"Unsupported locale {locale:?} (supported locales are: {})",
+				locales.join(", ")
+			)
+		})
+}
+
+#[cfg(test)]
+mod test {
+	use pretty_assertions::assert_eq;
+
+	use super::*;
+	use crate::value::test::{call, format};
+
+	#[test]
+	fn format_vec0() {
+		let value: &dyn Value = &vec![] as &Vec<String>;
+		let mut result = String::new();
+		value.format(&mut result, FormatSpecifier::Default);
+		assert_eq!(&result, "");
+	}
+
+	#[test]
+	fn format_vec1() {
+		let value: &dyn Value = &vec!["abc".to_string()];
+		let mut result = String::new();
+		value.format(&mut result, FormatSpecifier::Default);
+		assert_eq!(&result, "abc");
+	}
+
+	#[test]
+	fn format_vec2() {
+		let value: &dyn Value = &vec!["ab".to_string(), "cd".to_string()];
+		let mut result = String::new();
+		value.format(&mut result, FormatSpecifier::Default);
+		assert_eq!(&result, "ab and cd");
+	}
+
+	#[test]
+	fn format_vec3() {
+		let value: &dyn Value = &vec!["ab".to_string(), "cd".to_string(), "ef".to_string()];
+		let mut result = String::new();
+		value.format(&mut result, FormatSpecifier::Default);
+
+		#[cfg(feature = "oxford-comma")]
+		assert_eq!(&result, "ab, cd, and ef");
+
+		#[cfg(not(feature = "oxford-comma"))]
+		assert_eq!(&result, "ab, cd and ef");
+	}
+
+	fn abc() -> Vec<String> {
+		vec!["ab".to_string(), "cd".to_string(), "ef".to_string()]
+	}
+
+	#[test]
+	fn join() {
+		assert_eq!(call(&abc(), "join", vec![Box::new("/".to_string())]), "ab/cd/ef");
+		assert_eq!(
+			call(&abc(), "join_human", vec![Box::new("or".to_string())]),
+			"ab, cd or ef"
+		);
+		assert_eq!(
+			call(&abc(), "join_human", vec![Box::new("and".to_string()), Box::new(true)]),
+			"ab, cd, and ef"
+		);
+		assert_eq!(
+			call(&abc(), "join_locale", vec![Box::new("de_DE".to_string())]),
+			"ab, cd und ef"
+		);
+		assert_eq!(
+			call(&abc(), "join_locale", vec![Box::new("en-US".to_string())]),
+			"ab, cd, and ef"
+		);
+		assert_eq!(
+			call(&abc(), "join_locale", vec![Box::new("en-GB".to_string())]),
+			"ab, cd and ef"
+		);
+
+		let value = vec!["ab".to_string(), "cd".to_string()];
+		assert!(value.call("join_locale", &[Box::new("tlh".to_string())]).is_err());
+
+		let result = value
+			.call_named(
+				"join_human",
+				&[
+					(None, Box::new("and".to_string())),
+					(Some("oxford".to_string()), Box::new(true)),
+				],
+			)
+			.unwrap();
+		assert_eq!(format(&*result), "ab and cd");
+
+		// The named argument takes precedence over the `oxford-comma` feature
+		let join_human = |oxford: bool| {
+			let result = abc()
+				.call_named(
+					"join_human",
+					&[
+						(None, Box::new("and".to_string())),
+						(Some("oxford".to_string()), Box::new(oxford)),
+					],
+				)
+				.unwrap();
+			format(&*result)
+		};
+		assert_eq!(join_human(true), "ab, cd, and ef");
+		assert_eq!(join_human(false), "ab, cd and ef");
+	}
+}
@@ -23,6 +23,10 @@ impl Value for VersionControl {
 		Type::VersionControl
 	}
 
+	fn methods(&self) -> &'static [&'static str] {
+		&["git", "to_string"]
+	}
+
 	fn as_any(&self) -> &dyn Any {
 		self
 	}
@@ -0,0 +1,34 @@
+use build_info_common::{
+	BuildInfo,
+	embedded::{ELF_SECTION, MACHO_SECTION, PE_SECTION, encode},
+};
+use proc_macro::TokenStream;
+use proc_macro_error2::abort;
+use proc_macro2::Literal;
+use quote::quote_spanned;
+
+pub fn embed(input: TokenStream, build_info: BuildInfo) -> TokenStream {
+	if let Some(token) = proc_macro2::TokenStream::from(input).into_iter().next() {
+		abort!(token, "`embed!` does not take any arguments");
+	}
+
+	let record = encode(&build_info);
+	let len = record.len();
+	let record = Literal::byte_string(&record);
+
+	// `#[used]` keeps the otherwise unreferenced static alive, so that it can be found in the binary
+	let output = quote_spanned! {
+		proc_macro::Span::mixed_site().into() =>
+		const _: () = {
+			#[used]
+			#[allow(unsafe_code)]
+			#[cfg_attr(target_vendor = "apple", unsafe(link_section = #MACHO_SECTION))]
+			#[cfg_attr(windows, unsafe(link_section = #PE_SECTION))]
+			#[cfg_attr(not(any(target_vendor = "apple", windows)), unsafe(link_section = #ELF_SECTION))]
+			static BUILD_INFO: [u8; #len] = *#record;
+		};
+	};
+
+	// println!("{}", output.to_string());
+	output.into()
+}
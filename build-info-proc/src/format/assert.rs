@@ -0,0 +1,82 @@
+use build_info_common::BuildInfo;
+use build_info_format::{Eval, syntax, with_build_info};
+use proc_macro::TokenStream;
+use proc_macro_error2::{abort, abort_call_site};
+use proc_macro2::{Span, TokenTree};
+use quote::{quote, quote_spanned};
+
+use super::format_to_string;
+
+pub fn assert(input: TokenStream, build_info: BuildInfo) -> TokenStream {
+	let message = with_build_info(build_info, || check(input, "assert"));
+	let files = included_files();
+	match message {
+		Some(message) => quote_spanned!(Span::call_site()=> #files ::core::compile_error!(#message);),
+		None => files,
+	}
+	.into()
+}
+
+pub fn warn(input: TokenStream, build_info: BuildInfo) -> TokenStream {
+	let message = with_build_info(build_info, || check(input, "warn"));
+	let files = included_files();
+	match message {
+		// Custom warnings are not available on stable, so a deprecated item is used to emit one
+		Some(message) => quote_spanned!(Span::call_site()=>
+			#files
+			const _: () = {
+				#[deprecated(note = #message)]
+				struct BuildInfoWarning;
+				let _ = BuildInfoWarning;
+			};
+		),
+		None => files,
+	}
+	.into()
+}
+
+/// Evaluates the condition of `assert!` or `warn!` and returns the message to emit if it does not hold.
+fn check(input: TokenStream, macro_name: &str) -> Option<String> {
+	let condition_text = proc_macro2::TokenStream::from(input.clone())
+		.into_iter()
+		.take_while(|tree| !matches!(tree, TokenTree::Punct(punct) if punct.as_char() == ','))
+		.collect::<proc_macro2::TokenStream>()
+		.to_string();
+	let syntax: syntax::Syntax = syn::parse(input).unwrap_or_else(|err| abort!(err));
+
+	let Some((name, condition)) = syntax.args.first() else {
+		abort_call_site!(
+			"Expected a condition";
+			note = "Usage: `build_info::{}!(condition, \"message {{}}\", args...)`", macro_name;
+		)
+	};
+	let span = condition.meta().span;
+	if name.is_some() {
+		abort!(span, "The condition of `build_info::{}!` cannot be named", macro_name)
+	}
+
+	let value = condition.eval().unwrap_or_else(|err| err.abort());
+	let holds = *value.as_any().downcast_ref::<bool>().unwrap_or_else(|| {
+		abort!(span,
+			"The condition of `build_info::{}!` must be a bool", macro_name;
+			note = "It has type {}", value.get_type();
+		)
+	});
+
+	if holds {
+		None
+	} else if syntax.args.len() == 1 {
+		Some(format!("Build info condition failed: {condition_text}"))
+	} else {
+		let message = syntax::Syntax {
+			args: syntax.args[1..].to_vec(),
+		};
+		Some(format_to_string(&message))
+	}
+}
+
+/// Files read by `include_str!` are tracked using items, as both macros may be used in item position.
+fn included_files() -> proc_macro2::TokenStream {
+	let files = build_info_format::take_included_files();
+	quote!(#(const _: &[u8] = ::core::include_bytes!(#files);)*)
+}
@@ -1,219 +1,157 @@
-use std::str::Chars;
+use std::{ffi::CString, path::Path};
 
-use anyhow::Result;
 use build_info_common::BuildInfo;
+use build_info_format::{Arguments, Template, Value, interpolate_template, syntax, with_build_info};
 use proc_macro::TokenStream;
-use proc_macro_error2::{abort, abort_call_site, emit_error};
-use proc_macro2::Span;
+use proc_macro_error2::{abort, abort_call_site};
+use proc_macro2::{Literal, Span};
 use quote::quote;
-use syn::parse_macro_input;
+use syn::{LitByteStr, parse_macro_input};
 
-mod eval;
-use eval::Eval;
+mod assert;
+pub use assert::{assert, warn};
 
-mod syntax;
+pub fn format(input: TokenStream, build_info: BuildInfo) -> TokenStream {
+	let syntax = parse_macro_input!(input as syntax::Syntax);
+	let str = with_build_info(build_info, || format_to_string(&syntax));
+	let output = track_included_files(quote!(#str));
 
-mod types;
-use types::Type;
+	// println!("{}", output.to_string());
+	output.into()
+}
 
-mod value;
-use value::{FormatSpecifier, OP_ARRAY_INDEX, OP_FIELD_ACCESS, OP_TUPLE_INDEX, Value};
+pub fn format_const(input: TokenStream, build_info: BuildInfo) -> TokenStream {
+	let item = parse_macro_input!(input as syntax::ConstItem);
+	let str = with_build_info(build_info, || format_to_string(&item.syntax));
+	let value = track_included_files(quote!(#str));
 
-pub fn format(input: TokenStream, _build_info: BuildInfo) -> TokenStream {
-	let syntax = parse_macro_input!(input as syntax::Syntax);
-	let values: Result<Vec<_>> = syntax
-		.args
-		.iter()
-		.map(|(name, expr)| Ok((name.as_ref().map(|id| id.to_string()), expr.eval()?)))
-		.collect();
-	let values = values.unwrap_or_else(|err| abort_call_site!(err.to_string()));
-
-	let str = if values.is_empty() {
-		super::deserialize_build_info().to_string()
+	let attrs = &item.attrs;
+	let vis = &item.vis;
+	let ident = &item.ident;
+	let doc = if attrs.iter().any(|attr| attr.path().is_ident("doc")) {
+		quote!()
 	} else {
-		if values[0].0.is_some() {
-			abort_call_site!("The first argument cannot be named (it should be a positional argument containing a string)")
-		}
-		let format = values[0].1.as_any().downcast_ref::<String>().unwrap_or_else(
-			|| abort_call_site!("Could not interpret first argument as a string"; note = "It is {:#?}", &*values[0].1;),
-		);
-		interpolate(format, &values[1..], Span::call_site())
+		let doc = format!("The value of this constant is:\n\n```text\n{str}\n```");
+		quote!(#[doc = #doc])
 	};
-	let output = quote!(#str);
 
-	// println!("{}", output.to_string());
-	output.into()
+	quote!(
+		#(#attrs)*
+		#doc
+		#vis const #ident: &str = #value;
+	)
+	.into()
 }
 
-const CLOSING_BRACE_EXPECTED: &str = "Invalid format string: unmatched `{` found";
-const CLOSING_BRACE_NOTE: &str = "If you intended to print `{`, you can escape it using `{{`.";
-
-fn interpolate(format: &str, args: &[(Option<String>, Box<dyn Value>)], span: Span) -> String {
-	let mut res = String::with_capacity(format.len());
-	let mut implicit_position = 0usize;
-	let mut argument_used = Vec::new();
-	argument_used.resize(args.len(), false);
-
-	let mut chars = format.chars();
-	while let Some(c) = chars.next() {
-		if c == '{' {
-			let n = chars
-				.next()
-				.unwrap_or_else(|| abort!(span, CLOSING_BRACE_EXPECTED; note = CLOSING_BRACE_NOTE;));
-			if n == '{' {
-				res.push(c);
-			} else {
-				interpolate_once(
-					&mut res,
-					n,
-					&mut chars,
-					args,
-					&mut argument_used,
-					&mut implicit_position,
-					span,
-				);
-			}
-		} else if c == '}' {
-			let n = chars.next();
-			if n == Some('}') {
-				res.push(c);
-			} else {
-				abort!(
-					span, "Invalid format string: unmatched `}` found";
-					note = "If you intended to print `}`, you can escape it using `}}`.";
-				)
-			}
-		} else {
-			res.push(c);
-		}
-	}
+pub fn format_bytes(input: TokenStream, build_info: BuildInfo) -> TokenStream {
+	let syntax = parse_macro_input!(input as syntax::Syntax);
+	let str = with_build_info(build_info, || format_to_string(&syntax));
+	let bytes = LitByteStr::new(str.as_bytes(), Span::call_site());
+	track_included_files(quote!(#bytes)).into()
+}
 
-	for (i, used) in argument_used.iter().enumerate() {
-		if !used {
-			if let Some(ref name) = args[i].0 {
-				emit_error!(span,
-					"Parameter `{}` is not used in format string.", name;
-					note = "Positional arguments are zero-based";
-				);
-			} else {
-				emit_error!(span,
-					"Parameter {} is not used in format string.", i;
-					note = "Positional arguments are zero-based";
-				);
-			}
-		}
-	}
+pub fn format_cstr(input: TokenStream, build_info: BuildInfo) -> TokenStream {
+	let syntax = parse_macro_input!(input as syntax::Syntax);
+	let str = with_build_info(build_info, || format_to_string(&syntax));
+	let str = CString::new(str).unwrap_or_else(|err| {
+		abort_call_site!(
+			"The formatted string contains a NUL byte at position {}", err.nul_position();
+			note = "C strings are terminated by the first NUL byte, so they cannot contain any others";
+		)
+	});
+	let literal = Literal::c_string(&str);
+	track_included_files(quote!(#literal)).into()
+}
 
-	res
+pub fn include_template(input: TokenStream, build_info: BuildInfo) -> TokenStream {
+	let syntax = parse_macro_input!(input as syntax::Syntax);
+	with_build_info(build_info, || render_template(&syntax))
 }
 
-fn interpolate_once(
-	buffer: &mut String,
-	mut c: char,
-	chars: &mut Chars,
-	args: &[(Option<String>, Box<dyn Value>)],
-	argument_used: &mut [bool],
-	implicit_position: &mut usize,
-	span: Span,
-) {
-	let mut explicit_position = None;
-	let mut named = None;
-	if c.is_ascii_digit() {
-		let mut acc = 0;
-		#[allow(clippy::blocks_in_conditions)]
-		while {
-			acc = acc * 10 + c.to_digit(10).unwrap() as usize;
-			c = chars
-				.next()
-				.unwrap_or_else(|| abort!(span, CLOSING_BRACE_EXPECTED; note = CLOSING_BRACE_NOTE;));
-			c.is_ascii_digit()
-		} {}
-		explicit_position = Some(acc);
-	} else if c.is_alphabetic() {
-		let mut acc = String::new();
-		#[allow(clippy::blocks_in_conditions)]
-		while {
-			acc.push(c);
-			c = chars
-				.next()
-				.unwrap_or_else(|| abort!(span, CLOSING_BRACE_EXPECTED; note = CLOSING_BRACE_NOTE;));
-			c.is_alphanumeric() || c == '_'
-		} {}
-		named = Some(acc);
+fn render_template(syntax: &syntax::Syntax) -> TokenStream {
+	let values = eval_arguments(syntax);
+	if values.is_empty() {
+		abort_call_site!("Expected the path of a template file as the first argument")
 	}
+	let span = syntax.args[0].1.meta().span;
+	let path = first_argument_as_string(syntax, &values);
 
-	let arg = if let Some(pos) = explicit_position {
-		let arg = &args
-			.get(pos)
-			.unwrap_or_else(|| {
-				abort!(span,
-					"Invalid reference to positional argument {} ({} arguments were given)", pos, args.len();
-					note = "Positional arguments are zero-based";
-				)
-			})
-			.1;
-		argument_used[pos] = true;
-		arg
-	} else if named.is_some() {
-		let (pos, (_name, arg)) = args
-			.iter()
-			.enumerate()
-			.find(|(_i, (name, _value))| *name == named)
-			.unwrap_or_else(|| abort!(span, "Invalid reference to named argument {}", named.unwrap()));
-		argument_used[pos] = true;
-		arg
-	} else {
-		let arg = &args
-			.get(*implicit_position)
-			.unwrap_or_else(|| {
-				abort!(span,
-					"Invalid implicit reference to positional argument {} ({} arguments were given)",
-					*implicit_position,
-					args.len();
-					note = "Positional arguments are zero-based";
-				)
-			})
-			.1;
-		argument_used[*implicit_position] = true;
-		*implicit_position += 1;
-		arg
+	let manifest_dir = std::env::var_os("CARGO_MANIFEST_DIR")
+		.unwrap_or_else(|| abort_call_site!("The environment variable CARGO_MANIFEST_DIR is not set"));
+	let path = Path::new(&manifest_dir).join(path);
+	let text = std::fs::read_to_string(&path).unwrap_or_else(|err| {
+		abort!(span,
+			"Could not read template file {}", path.display();
+			note = "Template paths are relative to the directory containing your Cargo.toml";
+			note = "Caused by: {}", err;
+		)
+	});
+
+	let template = Template {
+		text: &text,
+		path: Some(&path),
+		span,
 	};
+	let str = interpolate_template(&template, &values[1..]).unwrap_or_else(|err| err.abort());
+
+	// Including the template as bytes ensures that cargo rebuilds the crate if the template changes
+	let path = path.to_str().unwrap_or_else(|| {
+		abort!(
+			span,
+			"The path of the template file is not valid UTF-8: {}",
+			path.display()
+		)
+	});
+	track_included_files(quote!({
+		const _: &[u8] = ::core::include_bytes!(#path);
+		#str
+	}))
+	.into()
+}
 
-	let mut debug = false;
-	let mut alternate = false;
-	if c == ':' {
-		c = chars
-			.next()
-			.unwrap_or_else(|| abort!(span, CLOSING_BRACE_EXPECTED; note = CLOSING_BRACE_NOTE;));
-		if c == '#' {
-			alternate = true;
-			c = chars
-				.next()
-				.unwrap_or_else(|| abort!(span, CLOSING_BRACE_EXPECTED; note = CLOSING_BRACE_NOTE;));
-		}
-		if c == '?' {
-			debug = true;
-			c = chars
-				.next()
-				.unwrap_or_else(|| abort!(span, CLOSING_BRACE_EXPECTED; note = CLOSING_BRACE_NOTE;));
-		}
+pub fn value(input: TokenStream, build_info: BuildInfo) -> TokenStream {
+	let syntax = parse_macro_input!(input as syntax::Syntax);
+	let values = with_build_info(build_info, || eval_arguments(&syntax));
+	if values.len() != 1 || values[0].0.is_some() {
+		abort_call_site!(
+			"Expected a single expression";
+			note = "Usage: `build_info::value!($.crate_info.version.major)`";
+		)
 	}
 
-	if c == '}' {
-		if debug {
-			if alternate {
-				arg.format(buffer, FormatSpecifier::DebugAlt);
-			} else {
-				arg.format(buffer, FormatSpecifier::Debug);
-			}
-		} else {
-			debug_assert!(!alternate);
-			arg.format(buffer, FormatSpecifier::Default);
-		}
+	let value = &values[0].1;
+	let tokens = value.to_tokens().unwrap_or_else(|| {
+		abort!(syntax.args[0].1.meta().span,
+			"Objects of type {} cannot be converted into a literal", value.get_type();
+			note = "Only bools, chars, strings, integers, floats, and `Option`s and `Vec`s thereof are supported";
+			note = "Try using `.to_string()`";
+		)
+	});
+	track_included_files(tokens).into()
+}
+
+/// Adds the files read by `include_str!` to `output`, so that cargo rebuilds the crate if they change.
+fn track_included_files(output: proc_macro2::TokenStream) -> proc_macro2::TokenStream {
+	let files = build_info_format::take_included_files();
+	if files.is_empty() {
+		output
 	} else {
-		abort!(span,
-			"Unexpected character {:?} in format specifier.", c;
-			note = CLOSING_BRACE_NOTE;
-		);
+		quote!({
+			#(const _: &[u8] = ::core::include_bytes!(#files);)*
+			#output
+		})
 	}
 }
+
+/// Evaluates the arguments and performs the string interpolation, which is shared by all `format!`-like macros.
+fn format_to_string(syntax: &syntax::Syntax) -> String {
+	build_info_format::format_to_string(syntax).unwrap_or_else(|err| err.abort())
+}
+
+fn eval_arguments(syntax: &syntax::Syntax) -> Arguments {
+	build_info_format::eval_arguments(syntax).unwrap_or_else(|err| err.abort())
+}
+
+fn first_argument_as_string<'a>(syntax: &syntax::Syntax, values: &'a [(Option<String>, Box<dyn Value>)]) -> &'a String {
+	build_info_format::first_argument_as_string(syntax, values).unwrap_or_else(|err| err.abort())
+}
@@ -1,2 +0,0 @@
-mod date_time;
-mod naive_date;
@@ -1,252 +1,311 @@
-//! Not currently enabled.
+//! Generates the initializers of the borrowed build information types (e.g., `BuildInfoRef<'static>`), which are
+//! constant expressions so that `build_info!` can place the build information in a `static`.
 
 use build_info_common::{
-	chrono::{DateTime, Datelike, NaiveDate, Utc},
-	semver::Version,
 	BuildInfo, CompilerChannel, CompilerInfo, CpuInfo, CrateInfo, Endianness, GitInfo, OptimizationLevel, TargetInfo,
 	VersionControl,
+	chrono::{DateTime, Datelike, NaiveDate, Timelike, Utc},
+	semver::{BuildMetadata, Prerelease, Version},
 };
-use proc_macro2::{Delimiter, Group, Ident, TokenStream};
-use quote::{quote, quote_spanned, TokenStreamExt};
+use proc_macro2::{Ident, TokenStream};
+use quote::{TokenStreamExt, quote, quote_spanned};
 
-pub(crate) fn init_value<T: InitValue>(this: &T, tokens: &mut TokenStream, definition_crate: &Ident) {
+pub(crate) fn init_value<T: InitValue + ?Sized>(this: &T, tokens: &mut TokenStream, definition_crate: &Ident) {
 	this.init_value(tokens, definition_crate)
 }
 
-pub(crate) trait InitValue {
-	fn init_value(&self, tokens: &mut TokenStream, definition_crate: &Ident);
+/// Shorthand for generating the initializer of a value into a new token stream.
+fn init<T: InitValue + ?Sized>(this: &T, definition_crate: &Ident) -> TokenStream {
+	let mut tokens = TokenStream::new();
+	init_value(this, &mut tokens, definition_crate);
+	tokens
 }
 
-impl InitValue for BuildInfo {
-	fn init_value(&self, tokens: &mut TokenStream, definition_crate: &Ident) {
-		tokens.append_all(quote_spanned!(proc_macro::Span::mixed_site().into() => #definition_crate::BuildInfo));
-		let mut initializer = TokenStream::new();
-
-		initializer.append_all(quote!(timestamp:));
-		init_value(&self.timestamp, &mut initializer, definition_crate);
-		initializer.append_all(quote!(,));
+/// The type and initializer of a part of the build information, as selected by `build_info!(fn name: path, ...)`.
+pub(crate) type Selection = (TokenStream, TokenStream);
 
-		initializer.append_all(quote!(profile:));
-		init_value(&self.profile, &mut initializer, definition_crate);
-		initializer.append_all(quote!(,));
-
-		initializer.append_all(quote!(optimization_level:));
-		init_value(&self.optimization_level, &mut initializer, definition_crate);
-		initializer.append_all(quote!(,));
-
-		initializer.append_all(quote!(crate_info:));
-		init_value(&self.crate_info, &mut initializer, definition_crate);
-		initializer.append_all(quote!(,));
+pub(crate) trait InitValue {
+	fn init_value(&self, tokens: &mut TokenStream, definition_crate: &Ident);
 
-		initializer.append_all(quote!(target:));
-		init_value(&self.target, &mut initializer, definition_crate);
-		initializer.append_all(quote!(,));
+	/// The type of the expression generated by `init_value`
+	fn ref_type(definition_crate: &Ident) -> TokenStream
+	where
+		Self: Sized;
+
+	/// Selects the member at the end of `path`, which is empty to select the value itself. Paths can only traverse
+	/// structs: an `Option` (such as `version_control`) can be selected as a whole, but not its members, since they
+	/// might be missing.
+	fn select(&self, path: &[Ident], definition_crate: &Ident) -> syn::Result<Selection>
+	where
+		Self: Sized,
+	{
+		match path.first() {
+			None => Ok((Self::ref_type(definition_crate), init(self, definition_crate))),
+			Some(field) => Err(syn::Error::new(
+				field.span(),
+				format!("Cannot select `{field}`, because the selected value has no fields"),
+			)),
+		}
+	}
+}
 
-		initializer.append_all(quote!(compiler:));
-		init_value(&self.compiler, &mut initializer, definition_crate);
-		initializer.append_all(quote!(,));
+/// Generates the `select` method for a struct, which forwards to the selected field.
+macro_rules! select_fields {
+	($($field:ident),* $(,)?) => {
+		fn select(&self, path: &[Ident], definition_crate: &Ident) -> syn::Result<Selection> {
+			let Some((field, rest)) = path.split_first() else {
+				return Ok((Self::ref_type(definition_crate), init(self, definition_crate)));
+			};
+			match field.to_string().as_str() {
+				$(stringify!($field) => self.$field.select(rest, definition_crate),)*
+				_ => Err(syn::Error::new(
+					field.span(),
+					format!(
+						"Unknown field `{}` (expected one of: {})",
+						field,
+						[$(stringify!($field)),*].join(", ")
+					),
+				)),
+			}
+		}
+	};
+}
 
-		initializer.append_all(quote!(version_control: ));
-		init_value(&self.version_control, &mut initializer, definition_crate);
-		initializer.append_all(quote!(,));
+impl InitValue for BuildInfo {
+	fn init_value(&self, tokens: &mut TokenStream, definition_crate: &Ident) {
+		let timestamp = init(&self.timestamp, definition_crate);
+		let profile = init(&self.profile, definition_crate);
+		let optimization_level = init(&self.optimization_level, definition_crate);
+		let crate_info = init(&self.crate_info, definition_crate);
+		let target = init(&self.target, definition_crate);
+		let compiler = init(&self.compiler, definition_crate);
+		let version_control = init(&self.version_control, definition_crate);
+		tokens.append_all(quote_spanned!(proc_macro::Span::mixed_site().into() =>
+			#definition_crate::BuildInfoRef {
+				timestamp: #timestamp,
+				profile: #profile,
+				optimization_level: #optimization_level,
+				crate_info: #crate_info,
+				target: #target,
+				compiler: #compiler,
+				version_control: #version_control,
+			}
+		));
+	}
 
-		tokens.append(Group::new(Delimiter::Brace, initializer));
+	fn ref_type(definition_crate: &Ident) -> TokenStream {
+		quote_spanned!(proc_macro::Span::mixed_site().into() => #definition_crate::BuildInfoRef<'static>)
 	}
+
+	select_fields!(
+		timestamp,
+		profile,
+		optimization_level,
+		crate_info,
+		target,
+		compiler,
+		version_control
+	);
 }
 
 impl InitValue for OptimizationLevel {
 	fn init_value(&self, tokens: &mut TokenStream, definition_crate: &Ident) {
-		match self {
-			OptimizationLevel::O0 => tokens
-				.append_all(quote_spanned!(proc_macro::Span::mixed_site().into() => #definition_crate::OptimizationLevel::O0)),
-			OptimizationLevel::O1 => tokens
-				.append_all(quote_spanned!(proc_macro::Span::mixed_site().into() => #definition_crate::OptimizationLevel::O1)),
-			OptimizationLevel::O2 => tokens
-				.append_all(quote_spanned!(proc_macro::Span::mixed_site().into() => #definition_crate::OptimizationLevel::O2)),
-			OptimizationLevel::O3 => tokens
-				.append_all(quote_spanned!(proc_macro::Span::mixed_site().into() => #definition_crate::OptimizationLevel::O3)),
-			OptimizationLevel::Os => tokens
-				.append_all(quote_spanned!(proc_macro::Span::mixed_site().into() => #definition_crate::OptimizationLevel::Os)),
-			OptimizationLevel::Oz => tokens
-				.append_all(quote_spanned!(proc_macro::Span::mixed_site().into() => #definition_crate::OptimizationLevel::Oz)),
+		let variant = match self {
+			OptimizationLevel::O0 => quote!(O0),
+			OptimizationLevel::O1 => quote!(O1),
+			OptimizationLevel::O2 => quote!(O2),
+			OptimizationLevel::O3 => quote!(O3),
+			OptimizationLevel::Os => quote!(Os),
+			OptimizationLevel::Oz => quote!(Oz),
 		};
+		tokens.append_all(
+			quote_spanned!(proc_macro::Span::mixed_site().into() => #definition_crate::OptimizationLevel::#variant),
+		);
+	}
+
+	fn ref_type(definition_crate: &Ident) -> TokenStream {
+		quote_spanned!(proc_macro::Span::mixed_site().into() => #definition_crate::OptimizationLevel)
 	}
 }
 
 impl InitValue for CrateInfo {
 	fn init_value(&self, tokens: &mut TokenStream, definition_crate: &Ident) {
-		tokens.append_all(quote_spanned!(proc_macro::Span::mixed_site().into() => #definition_crate::CrateInfo));
-		let mut initializer = TokenStream::new();
-
-		initializer.append_all(quote!(name:));
-		init_value(&self.name, &mut initializer, definition_crate);
-		initializer.append_all(quote!(,));
-
-		initializer.append_all(quote!(version:));
-		init_value(&self.version, &mut initializer, definition_crate);
-		initializer.append_all(quote!(,));
-
-		initializer.append_all(quote!(authors:));
-		init_value(&self.authors, &mut initializer, definition_crate);
-		initializer.append_all(quote!(,));
-
-		initializer.append_all(quote!(license:));
-		init_value(&self.license, &mut initializer, definition_crate);
-		initializer.append_all(quote!(,));
-
-		initializer.append_all(quote!(enabled_features:));
-		init_value(&self.enabled_features, &mut initializer, definition_crate);
-		initializer.append_all(quote!(,));
-
-		initializer.append_all(quote!(available_features:));
-		init_value(&self.available_features, &mut initializer, definition_crate);
-		initializer.append_all(quote!(,));
-
-		initializer.append_all(quote!(dependencies:));
-		init_value(&self.dependencies, &mut initializer, definition_crate);
-		initializer.append_all(quote!(,));
+		let name = init(&self.name, definition_crate);
+		let version = init(&self.version, definition_crate);
+		let authors = init(&self.authors, definition_crate);
+		let license = init(&self.license, definition_crate);
+		let source = init(&self.source, definition_crate);
+		let enabled_features = init(&self.enabled_features, definition_crate);
+		let available_features = init(&self.available_features, definition_crate);
+		let dependencies = init(&self.dependencies, definition_crate);
+		tokens.append_all(quote_spanned!(proc_macro::Span::mixed_site().into() =>
+			#definition_crate::CrateInfoRef {
+				name: #name,
+				version: #version,
+				authors: #authors,
+				license: #license,
+				source: #source,
+				enabled_features: #enabled_features,
+				available_features: #available_features,
+				dependencies: #dependencies,
+			}
+		));
+	}
 
-		tokens.append(Group::new(Delimiter::Brace, initializer));
+	fn ref_type(definition_crate: &Ident) -> TokenStream {
+		quote_spanned!(proc_macro::Span::mixed_site().into() => #definition_crate::CrateInfoRef<'static>)
 	}
+
+	select_fields!(
+		name,
+		version,
+		authors,
+		license,
+		source,
+		enabled_features,
+		available_features,
+		dependencies
+	);
 }
 
 impl InitValue for TargetInfo {
 	fn init_value(&self, tokens: &mut TokenStream, definition_crate: &Ident) {
-		tokens.append_all(quote_spanned!(proc_macro::Span::mixed_site().into() => #definition_crate::TargetInfo));
-		let mut initializer = TokenStream::new();
-
-		initializer.append_all(quote!(triple:));
-		init_value(&self.triple, &mut initializer, definition_crate);
-		initializer.append_all(quote!(,));
-
-		initializer.append_all(quote!(family:));
-		init_value(&self.family, &mut initializer, definition_crate);
-		initializer.append_all(quote!(,));
-
-		initializer.append_all(quote!(os:));
-		init_value(&self.os, &mut initializer, definition_crate);
-		initializer.append_all(quote!(,));
-
-		initializer.append_all(quote!(cpu:));
-		init_value(&self.cpu, &mut initializer, definition_crate);
-		initializer.append_all(quote!(,));
+		let triple = init(&self.triple, definition_crate);
+		let family = init(&self.family, definition_crate);
+		let os = init(&self.os, definition_crate);
+		let cpu = init(&self.cpu, definition_crate);
+		tokens.append_all(quote_spanned!(proc_macro::Span::mixed_site().into() =>
+			#definition_crate::TargetInfoRef {
+				triple: #triple,
+				family: #family,
+				os: #os,
+				cpu: #cpu,
+			}
+		));
+	}
 
-		tokens.append(Group::new(Delimiter::Brace, initializer));
+	fn ref_type(definition_crate: &Ident) -> TokenStream {
+		quote_spanned!(proc_macro::Span::mixed_site().into() => #definition_crate::TargetInfoRef<'static>)
 	}
+
+	select_fields!(triple, family, os, cpu);
 }
 
 impl InitValue for CpuInfo {
 	fn init_value(&self, tokens: &mut TokenStream, definition_crate: &Ident) {
-		tokens.append_all(quote_spanned!(proc_macro::Span::mixed_site().into() => #definition_crate::CpuInfo));
-		let mut initializer = TokenStream::new();
-
-		initializer.append_all(quote!(arch:));
-		init_value(&self.arch, &mut initializer, definition_crate);
-		initializer.append_all(quote!(,));
-
-		initializer.append_all(quote!(pointer_width:));
-		init_value(&self.pointer_width, &mut initializer, definition_crate);
-		initializer.append_all(quote!(,));
-
-		initializer.append_all(quote!(endianness:));
-		init_value(&self.endianness, &mut initializer, definition_crate);
-		initializer.append_all(quote!(,));
-
-		initializer.append_all(quote!(features:));
-		init_value(&self.features, &mut initializer, definition_crate);
-		initializer.append_all(quote!(,));
+		let arch = init(&self.arch, definition_crate);
+		let pointer_width = init(&self.pointer_width, definition_crate);
+		let endianness = init(&self.endianness, definition_crate);
+		let features = init(&self.features, definition_crate);
+		tokens.append_all(quote_spanned!(proc_macro::Span::mixed_site().into() =>
+			#definition_crate::CpuInfoRef {
+				arch: #arch,
+				pointer_width: #pointer_width,
+				endianness: #endianness,
+				features: #features,
+			}
+		));
+	}
 
-		tokens.append(Group::new(Delimiter::Brace, initializer));
+	fn ref_type(definition_crate: &Ident) -> TokenStream {
+		quote_spanned!(proc_macro::Span::mixed_site().into() => #definition_crate::CpuInfoRef<'static>)
 	}
+
+	select_fields!(arch, pointer_width, endianness, features);
 }
 
 impl InitValue for Endianness {
 	fn init_value(&self, tokens: &mut TokenStream, definition_crate: &Ident) {
-		match self {
-			Endianness::Little => tokens
-				.append_all(quote_spanned!(proc_macro::Span::mixed_site().into() => #definition_crate::Endianness::Little)),
-			Endianness::Big => {
-				tokens.append_all(quote_spanned!(proc_macro::Span::mixed_site().into() => #definition_crate::Endianness::Big))
-			}
+		let variant = match self {
+			Endianness::Little => quote!(Little),
+			Endianness::Big => quote!(Big),
 		};
+		tokens.append_all(quote_spanned!(proc_macro::Span::mixed_site().into() => #definition_crate::Endianness::#variant));
+	}
+
+	fn ref_type(definition_crate: &Ident) -> TokenStream {
+		quote_spanned!(proc_macro::Span::mixed_site().into() => #definition_crate::Endianness)
 	}
 }
 
 impl InitValue for CompilerInfo {
 	fn init_value(&self, tokens: &mut TokenStream, definition_crate: &Ident) {
-		tokens.append_all(quote_spanned!(proc_macro::Span::mixed_site().into() => #definition_crate::CompilerInfo));
-		let mut initializer = TokenStream::new();
-
-		initializer.append_all(quote!(version:));
-		init_value(&self.version, &mut initializer, definition_crate);
-		initializer.append_all(quote!(,));
-
-		initializer.append_all(quote!(commit_id:));
-		init_value(&self.commit_id, &mut initializer, definition_crate);
-		initializer.append_all(quote!(,));
-
-		initializer.append_all(quote!(commit_date:));
-		init_value(&self.commit_date, &mut initializer, definition_crate);
-		initializer.append_all(quote!(,));
-
-		initializer.append_all(quote!(channel:));
-		init_value(&self.channel, &mut initializer, definition_crate);
-		initializer.append_all(quote!(,));
-
-		initializer.append_all(quote!(host_triple:));
-		init_value(&self.host_triple, &mut initializer, definition_crate);
-		initializer.append_all(quote!(,));
+		let version = init(&self.version, definition_crate);
+		let commit_id = init(&self.commit_id, definition_crate);
+		let commit_date = init(&self.commit_date, definition_crate);
+		let channel = init(&self.channel, definition_crate);
+		let host_triple = init(&self.host_triple, definition_crate);
+		tokens.append_all(quote_spanned!(proc_macro::Span::mixed_site().into() =>
+			#definition_crate::CompilerInfoRef {
+				version: #version,
+				commit_id: #commit_id,
+				commit_date: #commit_date,
+				channel: #channel,
+				host_triple: #host_triple,
+			}
+		));
+	}
 
-		tokens.append(Group::new(Delimiter::Brace, initializer));
+	fn ref_type(definition_crate: &Ident) -> TokenStream {
+		quote_spanned!(proc_macro::Span::mixed_site().into() => #definition_crate::CompilerInfoRef<'static>)
 	}
+
+	select_fields!(version, commit_id, commit_date, channel, host_triple);
 }
 
 impl InitValue for CompilerChannel {
 	fn init_value(&self, tokens: &mut TokenStream, definition_crate: &Ident) {
-		match self {
-			CompilerChannel::Dev => tokens
-				.append_all(quote_spanned!(proc_macro::Span::mixed_site().into() => #definition_crate::CompilerChannel::Dev)),
-			CompilerChannel::Nightly => tokens.append_all(
-				quote_spanned!(proc_macro::Span::mixed_site().into() => #definition_crate::CompilerChannel::Nightly),
-			),
-			CompilerChannel::Beta => tokens
-				.append_all(quote_spanned!(proc_macro::Span::mixed_site().into() => #definition_crate::CompilerChannel::Beta)),
-			CompilerChannel::Stable => tokens.append_all(
-				quote_spanned!(proc_macro::Span::mixed_site().into() => #definition_crate::CompilerChannel::Stable),
-			),
+		let variant = match self {
+			CompilerChannel::Dev => quote!(Dev),
+			CompilerChannel::Nightly => quote!(Nightly),
+			CompilerChannel::Beta => quote!(Beta),
+			CompilerChannel::Stable => quote!(Stable),
 		};
+		tokens.append_all(
+			quote_spanned!(proc_macro::Span::mixed_site().into() => #definition_crate::CompilerChannel::#variant),
+		);
+	}
+
+	fn ref_type(definition_crate: &Ident) -> TokenStream {
+		quote_spanned!(proc_macro::Span::mixed_site().into() => #definition_crate::CompilerChannel)
 	}
 }
 
 impl<T: InitValue> InitValue for Option<T> {
 	fn init_value(&self, tokens: &mut TokenStream, definition_crate: &Ident) {
 		if let Some(value) = self {
-			tokens.append_all(quote_spanned!(proc_macro::Span::mixed_site().into() => Some));
-			let mut initializer = TokenStream::new();
-			init_value(value, &mut initializer, definition_crate);
-			tokens.append(Group::new(Delimiter::Parenthesis, initializer));
+			let value = init(value, definition_crate);
+			tokens.append_all(quote!(::core::option::Option::Some(#value)));
 		} else {
-			tokens.append_all(quote!(None));
+			tokens.append_all(quote!(::core::option::Option::None));
+		}
+	}
+
+	fn ref_type(definition_crate: &Ident) -> TokenStream {
+		let inner = T::ref_type(definition_crate);
+		quote!(::core::option::Option<#inner>)
+	}
+
+	fn select(&self, path: &[Ident], definition_crate: &Ident) -> syn::Result<Selection> {
+		match path.first() {
+			None => Ok((Self::ref_type(definition_crate), init(self, definition_crate))),
+			Some(field) => Err(syn::Error::new(
+				field.span(),
+				format!("Cannot select `{field}`, because the selected value is optional (select the `Option` itself instead)"),
+			)),
 		}
 	}
 }
 
 impl<T: InitValue> InitValue for Vec<T> {
 	fn init_value(&self, tokens: &mut TokenStream, definition_crate: &Ident) {
-		tokens.append_all(quote!(vec!));
-		let mut initializer = TokenStream::new();
-
-		let mut first = true;
-		for element in self {
-			if first {
-				first = false;
-			} else {
-				initializer.append_all(quote!(,));
-			}
-			init_value(element, &mut initializer, definition_crate);
-		}
+		let elements = self.iter().map(|element| init(element, definition_crate));
+		tokens.append_all(quote!(&[#(#elements),*]));
+	}
 
-		tokens.append(Group::new(Delimiter::Bracket, initializer));
+	fn ref_type(definition_crate: &Ident) -> TokenStream {
+		let inner = T::ref_type(definition_crate);
+		quote!(&'static [#inner])
 	}
 }
 
@@ -254,65 +313,83 @@ impl InitValue for VersionControl {
 	fn init_value(&self, tokens: &mut TokenStream, definition_crate: &Ident) {
 		match self {
 			VersionControl::Git(data) => {
-				tokens
-					.append_all(quote_spanned!(proc_macro::Span::mixed_site().into() => #definition_crate::VersionControl::Git));
-				let mut initializer = TokenStream::new();
-				init_value(data, &mut initializer, definition_crate);
-				tokens.append(Group::new(Delimiter::Parenthesis, initializer));
+				let data = init(data, definition_crate);
+				tokens.append_all(
+					quote_spanned!(proc_macro::Span::mixed_site().into() => #definition_crate::VersionControlRef::Git(#data)),
+				);
 			}
 		}
 	}
+
+	fn ref_type(definition_crate: &Ident) -> TokenStream {
+		quote_spanned!(proc_macro::Span::mixed_site().into() => #definition_crate::VersionControlRef<'static>)
+	}
 }
 
 impl InitValue for GitInfo {
 	fn init_value(&self, tokens: &mut TokenStream, definition_crate: &Ident) {
-		tokens.append_all(quote_spanned!(proc_macro::Span::mixed_site().into() => #definition_crate::GitInfo));
-		let mut initializer = TokenStream::new();
-
-		initializer.append_all(quote!(commit_id:));
-		init_value(&self.commit_id, &mut initializer, definition_crate);
-		initializer.append_all(quote!(,));
-
-		initializer.append_all(quote!(commit_short_id:));
-		init_value(&self.commit_short_id, &mut initializer, definition_crate);
-		initializer.append_all(quote!(,));
-
-		initializer.append_all(quote!(commit_timestamp:));
-		init_value(&self.commit_timestamp, &mut initializer, definition_crate);
-		initializer.append_all(quote!(,));
-
-		initializer.append_all(quote!(dirty:));
-		init_value(&self.dirty, &mut initializer, definition_crate);
-		initializer.append_all(quote!(,));
-
-		initializer.append_all(quote!(branch:));
-		init_value(&self.branch, &mut initializer, definition_crate);
-		initializer.append_all(quote!(,));
-
-		initializer.append_all(quote!(tags:));
-		init_value(&self.tags, &mut initializer, definition_crate);
-		initializer.append_all(quote!(,));
+		let commit_id = init(&self.commit_id, definition_crate);
+		let commit_short_id = init(&self.commit_short_id, definition_crate);
+		let commit_timestamp = init(&self.commit_timestamp, definition_crate);
+		let dirty = init(&self.dirty, definition_crate);
+		let branch = init(&self.branch, definition_crate);
+		let tags = init(&self.tags, definition_crate);
+		tokens.append_all(quote_spanned!(proc_macro::Span::mixed_site().into() =>
+			#definition_crate::GitInfoRef {
+				commit_id: #commit_id,
+				commit_short_id: #commit_short_id,
+				commit_timestamp: #commit_timestamp,
+				dirty: #dirty,
+				branch: #branch,
+				tags: #tags,
+			}
+		));
+	}
 
-		tokens.append(Group::new(Delimiter::Brace, initializer));
+	fn ref_type(definition_crate: &Ident) -> TokenStream {
+		quote_spanned!(proc_macro::Span::mixed_site().into() => #definition_crate::GitInfoRef<'static>)
 	}
+
+	select_fields!(commit_id, commit_short_id, commit_timestamp, dirty, branch, tags);
 }
 
 impl InitValue for Version {
 	fn init_value(&self, tokens: &mut TokenStream, definition_crate: &Ident) {
-		let version_string = self.to_string();
+		let major = self.major;
+		let minor = self.minor;
+		let patch = self.patch;
+		let pre = self.pre.as_str();
+		let build = self.build.as_str();
 		tokens.append_all(quote_spanned!(proc_macro::Span::mixed_site().into() =>
-			#definition_crate::semver::Version::parse(#version_string).unwrap()
+			#definition_crate::VersionRef {
+				major: #major,
+				minor: #minor,
+				patch: #patch,
+				pre: #pre,
+				build: #build,
+			}
 		));
 	}
+
+	fn ref_type(definition_crate: &Ident) -> TokenStream {
+		quote_spanned!(proc_macro::Span::mixed_site().into() => #definition_crate::VersionRef<'static>)
+	}
+
+	select_fields!(major, minor, patch, pre, build);
 }
 
 impl InitValue for DateTime<Utc> {
 	fn init_value(&self, tokens: &mut TokenStream, definition_crate: &Ident) {
-		let nanos = self.timestamp_nanos_opt().unwrap();
+		let secs = self.timestamp();
+		let nanos = self.nanosecond();
 		tokens.append_all(quote_spanned!(proc_macro::Span::mixed_site().into() =>
-			#definition_crate::chrono::TimeZone::timestamp_nanos(&#definition_crate::chrono::Utc, #nanos)
+			#definition_crate::chrono::DateTime::from_timestamp(#secs, #nanos).unwrap()
 		));
 	}
+
+	fn ref_type(definition_crate: &Ident) -> TokenStream {
+		quote_spanned!(proc_macro::Span::mixed_site().into() => #definition_crate::chrono::DateTime<#definition_crate::chrono::Utc>)
+	}
 }
 
 impl InitValue for NaiveDate {
@@ -324,17 +401,19 @@ impl InitValue for NaiveDate {
 			#definition_crate::chrono::NaiveDate::from_ymd_opt(#year, #month, #day).unwrap()
 		));
 	}
+
+	fn ref_type(definition_crate: &Ident) -> TokenStream {
+		quote_spanned!(proc_macro::Span::mixed_site().into() => #definition_crate::chrono::NaiveDate)
+	}
 }
 
 impl InitValue for bool {
 	fn init_value(&self, tokens: &mut TokenStream, _definition_crate: &Ident) {
 		tokens.append_all(quote!(#self));
 	}
-}
 
-impl InitValue for u8 {
-	fn init_value(&self, tokens: &mut TokenStream, _definition_crate: &Ident) {
-		tokens.append_all(quote!(#self));
+	fn ref_type(_definition_crate: &Ident) -> TokenStream {
+		quote!(bool)
 	}
 }
 
@@ -342,11 +421,39 @@ impl InitValue for u64 {
 	fn init_value(&self, tokens: &mut TokenStream, _definition_crate: &Ident) {
 		tokens.append_all(quote!(#self));
 	}
+
+	fn ref_type(_definition_crate: &Ident) -> TokenStream {
+		quote!(u64)
+	}
 }
 
 impl InitValue for String {
-	fn init_value(&self, tokens: &mut TokenStream, _definition_crate: &Ident) {
-		tokens.append_all(quote!(#self.to_string()));
+	fn init_value(&self, tokens: &mut TokenStream, definition_crate: &Ident) {
+		init_value(self.as_str(), tokens, definition_crate);
+	}
+
+	fn ref_type(_definition_crate: &Ident) -> TokenStream {
+		quote!(&'static str)
+	}
+}
+
+impl InitValue for Prerelease {
+	fn init_value(&self, tokens: &mut TokenStream, definition_crate: &Ident) {
+		init_value(self.as_str(), tokens, definition_crate);
+	}
+
+	fn ref_type(_definition_crate: &Ident) -> TokenStream {
+		quote!(&'static str)
+	}
+}
+
+impl InitValue for BuildMetadata {
+	fn init_value(&self, tokens: &mut TokenStream, definition_crate: &Ident) {
+		init_value(self.as_str(), tokens, definition_crate);
+	}
+
+	fn ref_type(_definition_crate: &Ident) -> TokenStream {
+		quote!(&'static str)
 	}
 }
 
@@ -355,3 +462,70 @@ impl InitValue for str {
 		tokens.append_all(quote!(#self));
 	}
 }
+
+#[cfg(test)]
+mod test {
+	use pretty_assertions::assert_eq;
+	use syn::{Token, parse::Parser, punctuated::Punctuated};
+
+	use super::*;
+
+	fn path(path: &str) -> Vec<Ident> {
+		let path = Punctuated::<Ident, Token![.]>::parse_separated_nonempty
+			.parse_str(path)
+			.unwrap();
+		path.into_iter().collect()
+	}
+
+	fn select(path: &[Ident]) -> syn::Result<(String, String)> {
+		let definition_crate = Ident::new("krate", proc_macro2::Span::call_site());
+		let (ty, init) = build_info_fixtures::build_info().select(path, &definition_crate)?;
+		Ok((ty.to_string(), init.to_string()))
+	}
+
+	#[test]
+	fn select_members() {
+		assert_eq!(
+			select(&path("crate_info.version.major")).unwrap(),
+			("u64".to_string(), "1u64".to_string())
+		);
+		assert_eq!(
+			select(&path("compiler.version.pre")).unwrap(),
+			("& 'static str".to_string(), "\"\"".to_string())
+		);
+	}
+
+	#[test]
+	fn unknown_field() {
+		let err = select(&path("crate_info.nmae")).unwrap_err();
+		assert!(
+			err
+				.to_string()
+				.starts_with("Unknown field `nmae` (expected one of: name, "),
+			"{err}"
+		);
+		assert_eq!(err.span().start().column, "crate_info.".len());
+
+		let err = select(&path("profile.name")).unwrap_err();
+		assert_eq!(
+			err.to_string(),
+			"Cannot select `name`, because the selected value has no fields"
+		);
+		assert_eq!(err.span().start().column, "profile.".len());
+	}
+
+	#[test]
+	fn optional_members() {
+		let err = select(&path("version_control.git.commit_id")).unwrap_err();
+		assert!(
+			err
+				.to_string()
+				.starts_with("Cannot select `git`, because the selected value is optional"),
+			"{err}"
+		);
+		assert_eq!(err.span().start().column, "version_control.".len());
+
+		let err = select(&path("compiler.commit_id.len")).unwrap_err();
+		assert!(err.to_string().starts_with("Cannot select `len`"), "{err}");
+	}
+}
@@ -1,13 +1,60 @@
 use build_info_common::BuildInfo;
 use proc_macro::TokenStream;
-use quote::{quote, quote_spanned};
-use syn::{Attribute, Ident, Token, Visibility, parse, parse_macro_input};
+use proc_macro_error2::abort;
+use quote::{format_ident, quote, quote_spanned};
+use syn::{Attribute, Ident, Token, Visibility, parse, parse_macro_input, punctuated::Punctuated};
+
+mod init_value;
+use init_value::InitValue;
 
 struct FunctionSyntax {
 	attrs: Vec<Attribute>,
 	definition_crate: Ident,
 	visibility: Option<Visibility>,
 	id: Ident,
+	/// The name of the struct generated for a selection, as in `build_info!(fn version -> Version: ...)`
+	struct_name: Option<Ident>,
+	/// `None` if the entire build information should be returned
+	selection: Option<Vec<Selection>>,
+	/// A document that is returned instead of the build information, as in `build_info!(fn sbom_json = sbom)`
+	document: Option<Ident>,
+}
+
+/// A selected member of the build information, e.g., `crate_info.version` or `compiler_version = compiler.version`
+struct Selection {
+	name: Option<Ident>,
+	path: Vec<Ident>,
+}
+
+impl Selection {
+	/// The name of the generated field, which defaults to the path joined by underscores
+	fn field_name(&self) -> Ident {
+		match &self.name {
+			Some(name) => name.clone(),
+			None => {
+				let name = self.path.iter().map(Ident::to_string).collect::<Vec<_>>().join("_");
+				Ident::new(&name, self.path[0].span())
+			}
+		}
+	}
+}
+
+impl parse::Parse for Selection {
+	fn parse(input: parse::ParseStream) -> parse::Result<Self> {
+		let mut name = None;
+		let mut path = vec![input.parse::<Ident>()?];
+		if input.peek(Token![=]) {
+			input.parse::<Token![=]>()?;
+			name = path.pop();
+			path.push(input.parse::<Ident>()?);
+		}
+		while input.peek(Token![.]) {
+			input.parse::<Token![.]>()?;
+			path.push(input.parse::<Ident>()?);
+		}
+
+		Ok(Selection { name, path })
+	}
 }
 
 impl parse::Parse for FunctionSyntax {
@@ -19,11 +66,47 @@ impl parse::Parse for FunctionSyntax {
 		input.parse::<Token![fn]>()?;
 		let id = input.parse::<Ident>()?;
 
+		if input.peek(Token![=]) {
+			input.parse::<Token![=]>()?;
+			return Ok(FunctionSyntax {
+				attrs,
+				definition_crate,
+				visibility,
+				id,
+				struct_name: None,
+				selection: None,
+				document: Some(input.parse()?),
+			});
+		}
+
+		let struct_name = if input.peek(Token![->]) {
+			input.parse::<Token![->]>()?;
+			Some(input.parse::<Ident>()?)
+		} else {
+			None
+		};
+
+		let selection = if input.peek(Token![:]) {
+			input.parse::<Token![:]>()?;
+			let selection = Punctuated::<Selection, Token![,]>::parse_separated_nonempty(input)?;
+			Some(selection.into_iter().collect())
+		} else if let Some(struct_name) = &struct_name {
+			return Err(parse::Error::new(
+				struct_name.span(),
+				"A struct name can only be given together with a selection, e.g., `fn version -> Version: crate_info.version`",
+			));
+		} else {
+			None
+		};
+
 		Ok(FunctionSyntax {
 			attrs,
 			definition_crate,
 			visibility,
 			id,
+			struct_name,
+			selection,
+			document: None,
 		})
 	}
 }
@@ -34,26 +117,155 @@ pub fn build_info(input: TokenStream, build_info: BuildInfo) -> TokenStream {
 		definition_crate,
 		visibility,
 		id,
+		struct_name,
+		selection,
+		document,
 	} = parse_macro_input!(input as FunctionSyntax);
 	let visibility = visibility.map_or(quote!(), |vis| quote!(#vis));
 
-	let mut bytes = Vec::new();
-	ciborium::into_writer(&build_info, &mut bytes).unwrap();
-	let bytes = proc_macro2::Literal::byte_string(&bytes);
+	if let Some(document) = document {
+		let path = match document.to_string().as_str() {
+			"sbom" => std::env::var("BUILD_INFO_SBOM").unwrap_or_else(|_| {
+				abort!(document, "No SBOM has been generated for this crate";
+					note = "Call `.embed_sbom(true)` on `build_info_build::build_script()` in your build.rs";
+					note = "This requires the `sbom` feature of `build-info-build`";
+				)
+			}),
+			_ => abort!(document, "Unknown document `{}` (expected `sbom`)", document),
+		};
 
+		let output = quote_spanned! {
+			proc_macro::Span::mixed_site().into() =>
+			#(#attrs)*
+			#visibility fn #id() -> &'static str {
+				::core::include_str!(#path)
+			}
+		};
+		return output.into();
+	}
+
+	let Some(selection) = selection else {
+		let mut init = proc_macro2::TokenStream::new();
+		init_value::init_value(&build_info, &mut init, &definition_crate);
+
+		#[allow(clippy::let_and_return)]
+		let output = quote_spanned! {
+			proc_macro::Span::mixed_site().into() =>
+			#(#attrs)*
+			#visibility fn #id() -> &'static #definition_crate::BuildInfoRef<'static> {
+				static BUILD_INFO: #definition_crate::BuildInfoRef<'static> = #init;
+				&BUILD_INFO
+			}
+		};
+
+		// println!("{}", output.to_string());
+		return output.into();
+	};
+
+	// Only the selected members are embedded into the binary, in a struct that is generated for this function
+	let struct_name = struct_name.unwrap_or_else(|| format_ident!("{}BuildInfo", to_camel_case(&id.to_string())));
+	let names = field_names(&selection).unwrap_or_else(|name| {
+		abort!(name,
+			"The field `{}` is selected more than once", name;
+			note = "Use `name = path` to choose a different name for the field";
+		)
+	});
+	let mut types = Vec::new();
+	let mut inits = Vec::new();
+	for selected in &selection {
+		let (ty, init) = build_info
+			.select(&selected.path, &definition_crate)
+			.unwrap_or_else(|err| abort!(err.span(), "{}", err));
+		types.push(ty);
+		inits.push(init);
+	}
+
+	let doc = format!("Build information selected by `{id}`");
 	#[allow(clippy::let_and_return)]
 	let output = quote_spanned! {
 		proc_macro::Span::mixed_site().into() =>
+		#[doc = #doc]
+		#[derive(Clone, Copy, Debug)]
+		#visibility struct #struct_name {
+			#(pub #names: #types,)*
+		}
+
 		#(#attrs)*
-		#visibility fn #id() -> &'static #definition_crate::BuildInfo {
-			static VERSION: ::std::sync::OnceLock<#definition_crate::BuildInfo> = ::std::sync::OnceLock::new();
-			const BYTES: &[u8] = #bytes;
-			VERSION.get_or_init(|| {
-				#definition_crate::ciborium::from_reader(BYTES).unwrap()
-			})
+		#visibility fn #id() -> &'static #struct_name {
+			static BUILD_INFO: #struct_name = #struct_name {
+				#(#names: #inits,)*
+			};
+			&BUILD_INFO
 		}
 	};
 
 	// println!("{}", output.to_string());
 	output.into()
 }
+
+/// The names of the fields generated for a selection, or the first name that is used more than once
+fn field_names(selection: &[Selection]) -> Result<Vec<Ident>, Ident> {
+	let mut names: Vec<Ident> = Vec::new();
+	for selected in selection {
+		let name = selected.field_name();
+		if names.contains(&name) {
+			return Err(name);
+		}
+		names.push(name);
+	}
+	Ok(names)
+}
+
+fn to_camel_case(name: &str) -> String {
+	name
+		.split('_')
+		.flat_map(|word| {
+			let mut chars = word.chars();
+			chars.next().map(|first| first.to_uppercase().chain(chars))
+		})
+		.flatten()
+		.collect()
+}
+
+#[cfg(test)]
+mod test {
+	use pretty_assertions::assert_eq;
+	use quote::quote;
+
+	use super::*;
+
+	#[test]
+	fn selection() -> anyhow::Result<()> {
+		let syntax =
+			syn::parse2::<FunctionSyntax>(quote!(krate pub fn version: crate_info.version, rustc = compiler.version))?;
+		let selection = syntax.selection.unwrap();
+		let names: Vec<String> = selection
+			.iter()
+			.map(|selected| selected.field_name().to_string())
+			.collect();
+		assert_eq!(names, ["crate_info_version", "rustc"]);
+		assert_eq!(selection[1].path.len(), 2);
+		assert!(syntax.struct_name.is_none());
+
+		assert!(syn::parse2::<FunctionSyntax>(quote!(krate fn version -> Version)).is_err());
+		let syntax = syn::parse2::<FunctionSyntax>(quote!(krate fn sbom_json = sbom))?;
+		assert_eq!(
+			syntax.document.map(|document| document.to_string()).as_deref(),
+			Some("sbom")
+		);
+		assert_eq!(to_camel_case("my_build_info"), "MyBuildInfo");
+
+		Ok(())
+	}
+
+	#[test]
+	fn duplicate_names() -> anyhow::Result<()> {
+		let syntax = syn::parse2::<FunctionSyntax>(
+			quote!(krate fn version: compiler.version, rustc = crate_info.version, compiler_version = compiler.version),
+		)?;
+		let duplicate = field_names(&syntax.selection.unwrap()).unwrap_err();
+		assert_eq!(duplicate.to_string(), "compiler_version");
+
+		Ok(())
+	}
+}
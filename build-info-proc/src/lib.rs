@@ -6,13 +6,14 @@ use build_info_common::{BuildInfo, VersionedString};
 use proc_macro::TokenStream;
 use proc_macro_error2::{abort_call_site, emit_call_site_error, proc_macro_error};
 
+mod embed;
 mod format;
 #[cfg(feature = "runtime")]
 mod function;
 
 /**
-Call as `build_info!(fn name)` to create a function called `name` that returns a reference to a lazily created
-and cached `BuildInfo` object.
+Call as `build_info!(fn name)` to create a function called `name` that returns a reference to a `BuildInfoRef`
+object, which is stored in a `static`.
 
 This macro also accepts a visibility specifier for the generated function, such as `build_info!(pub fn version)`.
 */
@@ -23,12 +24,60 @@ pub fn build_info(input: TokenStream) -> TokenStream {
 	function::build_info(input, deserialize_build_info())
 }
 
+#[proc_macro_error]
+#[proc_macro]
+pub fn embed(input: TokenStream) -> TokenStream {
+	embed::embed(input, deserialize_build_info())
+}
+
 #[proc_macro_error]
 #[proc_macro]
 pub fn format(input: TokenStream) -> TokenStream {
 	format::format(input, deserialize_build_info())
 }
 
+#[proc_macro_error]
+#[proc_macro]
+pub fn format_const(input: TokenStream) -> TokenStream {
+	format::format_const(input, deserialize_build_info())
+}
+
+#[proc_macro_error]
+#[proc_macro]
+pub fn format_bytes(input: TokenStream) -> TokenStream {
+	format::format_bytes(input, deserialize_build_info())
+}
+
+#[proc_macro_error]
+#[proc_macro]
+pub fn format_cstr(input: TokenStream) -> TokenStream {
+	format::format_cstr(input, deserialize_build_info())
+}
+
+#[proc_macro_error]
+#[proc_macro]
+pub fn include_template(input: TokenStream) -> TokenStream {
+	format::include_template(input, deserialize_build_info())
+}
+
+#[proc_macro_error]
+#[proc_macro]
+pub fn assert(input: TokenStream) -> TokenStream {
+	format::assert(input, deserialize_build_info())
+}
+
+#[proc_macro_error]
+#[proc_macro]
+pub fn value(input: TokenStream) -> TokenStream {
+	format::value(input, deserialize_build_info())
+}
+
+#[proc_macro_error]
+#[proc_macro]
+pub fn warn(input: TokenStream) -> TokenStream {
+	format::warn(input, deserialize_build_info())
+}
+
 fn deserialize_build_info() -> BuildInfo {
 	// explicitly pull std::format into this namespace, as `abort_call_site` seems to use the macro without properly
 	// qualifying it.
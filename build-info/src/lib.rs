@@ -1,11 +1,14 @@
 #![doc = include_str!("../README.md")]
 #![forbid(unsafe_code)]
-#![cfg_attr(not(feature = "runtime"), no_std)]
+#![no_std]
 
+#[cfg(feature = "embedded")]
+pub use build_info_common::embedded;
 #[cfg(feature = "runtime")]
 pub use build_info_common::{
-	BuildInfo, CompilerChannel, CompilerInfo, CpuInfo, CrateInfo, Endianness, GitInfo, OptimizationLevel, TargetInfo,
-	VersionControl, chrono, semver,
+	BuildInfo, BuildInfoRef, CompilerChannel, CompilerInfo, CompilerInfoRef, CpuInfo, CpuInfoRef, CrateInfo,
+	CrateInfoRef, Endianness, GitInfo, GitInfoRef, OptimizationLevel, TargetInfo, TargetInfoRef, VersionControl,
+	VersionControlRef, VersionRef, chrono, semver,
 };
 /// This crate defines macro_rules that pass `$crate` (i.e., this crate) to the proc-macros doing the actual work
 /// The proc-macro crate that contains said proc-macros is reexported here, to be found in the macro_rules.
@@ -13,9 +16,28 @@ pub use build_info_common::{
 pub use build_info_proc as proc;
 
 /**
-Generates a function that returns a reference to the `BuildInfo` structure for the crate.
+Generates a function that returns a reference to the build information for the crate.
 
 Usage: `build_info!(fn build_info_function);`
+
+The generated function returns a `&'static BuildInfoRef<'static>`, which is stored in a `static` and thus available
+without any allocation or deserialization at runtime. The owned [`BuildInfo`] can be obtained using
+`BuildInfo::try_from(build_info_function())` if needed.
+
+To avoid embedding all of the build information (including the possibly large dependency tree) into the binary, the
+members that are actually needed can be selected:
+`build_info!(fn version: crate_info.version, version_control, rustc = compiler.version);` generates a struct
+`VersionBuildInfo` with the fields `crate_info_version`, `version_control` and `rustc`, and a function `version` that
+returns a reference to it. The name of the struct can be chosen using `build_info!(fn version -> Version: ...)`. In the
+example above, the fields `crate_info_version` and `rustc` are `VersionRef<'static>`s and `version_control` is an
+`Option<VersionControlRef<'static>>`, so that `version().rustc.major` is the major version of the compiler.
+
+Paths can only traverse structs and stop at an `Option`: `version_control.git.commit_id` is rejected, because the
+version control information might be missing, whereas `version_control` can be selected as a whole.
+
+If the build script calls `embed_sbom(true)` (which requires the `sbom` feature of `build-info-build`),
+`build_info!(fn sbom_json = sbom);` generates a function that returns the CycloneDX SBOM of the crate as a
+`&'static str`.
 */
 #[cfg(feature = "runtime")]
 #[macro_export]
@@ -23,6 +45,28 @@ macro_rules! build_info {
 	($($tokens:tt)*) => { $crate::proc::build_info!{$crate $($tokens)*} };
 }
 
+/**
+Fails compilation if a condition on the build information does not hold.
+
+Usage: `build_info::assert!(any(eq($.profile, "debug"), not($.version_control?.git()?.dirty ?? false)), "Release
+builds of {} must come from a clean tree", $.crate_info.name);`. The condition is an expression as in [`format!`](crate::format!) and must
+evaluate to a `bool`; the optional message and its arguments are formatted like the arguments of
+[`format!`](crate::format!). This macro can be used wherever items or statements are allowed.
+*/
+pub use build_info_proc::assert;
+/**
+Embeds the build information into a dedicated section of the binary.
+
+Usage: `build_info::embed!();`
+
+The build information is stored as a self-describing record in the `.build_info` section (`__DATA,__build_info` on
+Apple platforms and `.bldinfo` on Windows, since PE/COFF section names are limited to 8 bytes), where it can be found by
+external tools without executing the binary, even if it has been stripped. With the `embedded` feature, the record can
+be extracted from an ELF file (or core dump) using `build_info::embedded::read_elf`; Mach-O and PE files are searched
+for the record using `build_info::embedded::scan` instead. Placing data into a specific section is an `unsafe` attribute, so this macro cannot be
+used in crates that `forbid(unsafe_code)`.
+*/
+pub use build_info_proc::embed;
 /**
 Generates a string at compile-time that includes build information.
 
@@ -31,12 +75,108 @@ the current build information. To do so, you can use a subset of the normal form
 "variable" `$` that denotes the `BuildInfo` object. For example, `build_info::format!("Built at {}", $.timestamp)`
 might return "Built at 2020-05-28 20:09:40Z".`
 
-You can use `?` to unwrap `Option`s and some additional types can be formatted this way (e.g., `Vec<T>`).
+You can use `?` to unwrap `Option`s and some additional types can be formatted this way (e.g., `Vec<T>`). If an
+expression may not have a value, e.g., because the crate is built from a tarball without version control, `??` provides
+a fallback: `$.version_control?.git()?.branch ?? "unknown"` uses "unknown" if any `?` in it fails or the branch itself
+is `None`. Similarly, `Option`s offer `.unwrap_or("unknown")` and `.map_or("", " (on {})")`, where the latter formats
+the contained value using the second argument.
+
+A small set of free functions is available as well: `max`, `min`, `len`, `lower`, `upper`, `replace`, `pad_left`,
+`pad_right`, `hex`, `sha256`, `base64`, `url_encode`, `json_escape`, `typeof` (or `type_name`), `eq`, `ne`, `not`,
+`all` and `any`. For example, `build_info::format!("{}", pad_left($.crate_info.name, 12, '.'))` right-aligns the crate
+name. `eq` and `ne` compare two values of the same type like the `eq` method of that type, if it has one, so versions
+that only differ in their build metadata are equal.
+
+Integer literals may carry a type suffix (`42u8`, `-1i64`, `7usize`), which makes them behave like the corresponding
+Rust type: literals that do not fit are rejected, and the arithmetic methods `add`, `sub`, `mul`, `div` and `rem` fail
+at compile-time instead of overflowing, e.g., `200u8.add(56)`. Float literals (`0.5`, `2f64`) have type `f64` and
+support the same arithmetic as well as `round()`, `floor()`, `ceil()` and `abs()`. `max` and `min` accept integers,
+floats and strings, as long as all arguments have the same type, e.g., `max(1u8, 2u8)` or `max(1.5, 2.0)`.
+
+Placeholders accept the width, fill, alignment, sign and precision flags of `std::fmt`: `{:>10}` right-aligns in a
+field of ten characters, `{:*^9}` centers using `*` as fill, `{:+05}` yields "+0042" and `{:.2}` rounds floats to two
+digits (or truncates strings to two characters). Numbers are right-aligned by default, everything else left-aligned.
+
+Lists are formatted as in "a, b and c" by default (or "a, b, and c" if the `oxford-comma` feature is enabled). To
+choose per call, use `{authors:list}` or `{authors:#list}` (with an Oxford comma) in the format string, or the methods
+`$.crate_info.authors.join(", ")`, `$.crate_info.authors.join_human("or", oxford = true)` and
+`$.crate_info.authors.join_locale("de")`.
+
+To explore the available data, every value offers `fields()` and `methods()`, e.g.,
+`build_info::format!("{}", $.crate_info.fields())` yields the names of all fields of `CrateInfo`.
+
+Versions support queries such as `$.crate_info.version.is_prerelease()`, `$.crate_info.version.matches("^1.2")` or
+`$.compiler.version.ge("1.80.0")`.
+
+Timestamps can be converted (`$.timestamp.to_rfc3339()`, `$.timestamp.with_timezone("Europe/Berlin")`), taken apart
+(`$.timestamp.year()`) and subtracted from each other, e.g.,
+`$.timestamp.signed_duration_since($.version_control?.git()?.commit_timestamp).humanize()` might yield "3 hours".
+
+Parts of the format string can be repeated for each element of a list using `{% for x in xs %}...{% endfor %}`, where
+`xs` is either an expression such as `$.crate_info.dependencies`, or a named argument or loop variable followed by a
+path such as `dep.dependencies`. Inside the loop, `{x.name}` accesses fields of the current element and `loop`
+provides `loop.index` (zero-based), `loop.index1`, `loop.first`, `loop.last` and `loop.length`. For example,
+`build_info::format!("{% for dep in $.crate_info.dependencies %}{dep.name}{% endfor %}")` lists the names of all direct
+dependencies. Implicit positional arguments (`{}`) cannot be used inside of loops.
+
+A few macros can be used inside `format!` as well: `concat!`, `env!`, `option_env!`, `stringify!`, `compile_error!`,
+`file!`, `line!` and `column!` (which refer to the invocation of `format!`) and `include_str!` (with a path relative to
+the directory containing your `Cargo.toml`; the crate is rebuilt if the file changes). `cfg!` is evaluated against the
+collected build information, so `cfg!(feature = "serde")`, `cfg!(unix)` or `cfg!(target_pointer_width = "64")` refer
+to the crate being built, while other options (e.g., `cfg!(debug_assertions)`) are forwarded by the build script.
+`cfg!(test)`, `cfg!(doc)` and `cfg!(doctest)` are not known to build scripts and therefore cannot be used.
 
 Literal curly braces can be printed by doubling them up: `build_info::format!("{{}}") // yields "{}"`.
 */
 pub use build_info_proc::format;
-/// Used by the function generated by `build_info!` to deserialize the build information
-#[cfg(feature = "runtime")]
-#[doc(hidden)]
-pub use ciborium;
+/**
+Generates a byte string literal (`&'static [u8; N]`) at compile-time that includes build information.
+
+This macro accepts the same arguments as [`format!`](crate::format!), e.g.,
+`const VERSION: &[u8] = build_info::format_bytes!("{}", $.crate_info.version);`.
+*/
+pub use build_info_proc::format_bytes;
+/**
+Generates a `const` item containing a string with build information.
+
+Usage: `build_info::format_const!(pub const VERSION = "{} v{}", $.crate_info.name, $.crate_info.version);` expands to
+`pub const VERSION: &str = "...";`. Everything after the `=` is interpreted exactly like the arguments of
+[`format!`](crate::format!). Attributes such as doc comments are passed through; if no doc comment is given, the
+generated constant documents its own value.
+*/
+pub use build_info_proc::format_const;
+/**
+Generates a C string literal (`&'static CStr`) at compile-time that includes build information.
+
+This macro accepts the same arguments as [`format!`](crate::format!). Compilation fails if the formatted string
+contains a NUL byte.
+*/
+pub use build_info_proc::format_cstr;
+/**
+Loads a template file at compile-time and interpolates it with build information.
+
+Usage: `build_info::include_template!("templates/version.txt", name = $.crate_info.name)`. The path is relative to the
+directory containing your `Cargo.toml`, and the file contents are used as the format string of
+[`format!`](crate::format!), with all remaining arguments working the same way. Errors in the template are reported
+with their position in the file, and the crate is rebuilt whenever the template changes.
+*/
+pub use build_info_proc::include_template;
+/**
+Generates a literal from an expression on the build information.
+
+Unlike [`format!`](crate::format!), the result is not converted to a string, so it can be used in `const` contexts and
+patterns: `const IS_DIRTY: bool = build_info::value!($.version_control?.git()?.dirty ?? false);`. Strings become
+`&'static str`s, while `bool`s, `char`s and floats keep their types. Integers become `u64`s (e.g.,
+`const MAJOR: u64 = build_info::value!($.crate_info.version.major);`), or `i64`s if they are negative, unless they carry
+a suffix already (`42u8`). `Option`s become `Some(...)` or `None` and `Vec`s become arrays, e.g.,
+`const AUTHORS: &[&str] = &build_info::value!($.crate_info.authors);`.
+*/
+pub use build_info_proc::value;
+/**
+Emits a warning if a condition on the build information does not hold.
+
+This macro accepts the same arguments as [`assert!`](crate::assert!), e.g.,
+`build_info::warn!(ne($.compiler.channel.to_string(), "Nightly"), "Built with a nightly compiler");`. Since custom warnings are not
+available on stable Rust, the warning is reported as the use of a deprecated item that carries the message.
+*/
+pub use build_info_proc::warn;
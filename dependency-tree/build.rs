@@ -1,9 +1,21 @@
-use build_info_build::DependencyDepth;
+use build_info_build::{Copyleft, DependencyDepth, LicensePolicy};
 
 fn main() {
 	// Calling `build_info_build::build_script` collects all data and makes it available to `build_info::build_info!`
 	// and `build_info::format!` in the main program.
 	//
-	// Dependency collection needs to be enabled specifically.
-	build_info_build::build_script().collect_dependencies(DependencyDepth::Full);
+	// Dependency collection needs to be enabled specifically. The collected dependencies are also listed in the CycloneDX
+	// and SPDX documents that are written to `OUT_DIR`. The license policy is checked against all dependencies,
+	// regardless of whether they are collected.
+	build_info_build::build_script()
+		.collect_dependencies(DependencyDepth::Full)
+		.write_sbom("dependency-tree.cdx.json")
+		.write_spdx_json("dependency-tree.spdx.json")
+		.write_spdx_tag_value("dependency-tree.spdx")
+		// Copyleft licenses would be fine for build-time tools, but not for anything that ends up in the binary
+		.license_policy(
+			LicensePolicy::new()
+				.copyleft(Copyleft::BuildOnly)
+				.fail_on_violation(true),
+		);
 }
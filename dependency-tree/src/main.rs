@@ -1,6 +1,6 @@
 build_info::build_info!(fn version);
 
-fn print_crate_info(ci: &build_info::CrateInfo, self_indent: &str, nest_indent: &str) {
+fn print_crate_info(ci: &build_info::CrateInfoRef, self_indent: &str, nest_indent: &str) {
 	println!(
 		"{}{} v{} [{}]",
 		self_indent,
@@ -24,4 +24,19 @@ fn print_crate_info(ci: &build_info::CrateInfo, self_indent: &str, nest_indent:
 
 fn main() {
 	print_crate_info(&version().crate_info, "", "");
+
+	// Templates can also loop over the dependencies at compile time
+	println!();
+	print!(
+		"{}",
+		build_info::include_template!("THIRD_PARTY_NOTICES.txt", name = $.crate_info.name)
+	);
+
+	// At runtime, the dependencies can be grouped by their license instead
+	println!();
+	println!("Open source notices:");
+	for (license, crates) in version().crate_info.licenses() {
+		let crates: Vec<String> = crates.iter().map(|ci| format!("{} v{}", ci.name, ci.version)).collect();
+		println!("  {}: {}", license.unwrap_or("(no license)"), crates.join(", "));
+	}
 }
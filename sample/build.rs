@@ -1,5 +1,11 @@
 fn main() {
 	// Calling `build_info_build::build_script` collects all data and makes it available to `build_info::build_info!`
 	// and `build_info::format!` in the main program.
-	build_info_build::build_script();
+	// Additionally, the build information and a CycloneDX SBOM are written next to the binary (e.g., to
+	// `target/debug/sample.build-info.json`) for consumption by other tools. The SBOM is also embedded into the program.
+	build_info_build::build_script()
+		.export_to_target_dir(true)
+		.write_json("sample.build-info.json")
+		.write_sbom("sample.cdx.json")
+		.embed_sbom(true);
 }
@@ -1,4 +1,5 @@
-#![forbid(unsafe_code)]
+// `build_info::embed!` needs to place data into a dedicated section, which is not possible with `forbid(unsafe_code)`
+#![deny(unsafe_code)]
 
 // Use the `build_info!` macro to generate a function `crate::build_info` that returns on object with the data that
 // is collected in the build script.
@@ -13,10 +14,55 @@ build_info::build_info! {
 	pub fn pub_build_info
 }
 
+// Build information can also be used as typed constants
+const MAJOR_VERSION: u64 = build_info::value!($.crate_info.version.major);
+const AUTHORS: &[&str] = &build_info::value!($.crate_info.authors);
+const IS_DIRTY: bool = build_info::value!($.version_control?.git()?.dirty ?? false);
+
+// Compilation can be aborted (or a warning can be emitted) if the build information does not meet expectations
+build_info::assert!(eq($.crate_info.name, "sample"), "This is not the sample crate, but {}", $.crate_info.name);
+build_info::warn!(ne($.crate_info.version.to_string(), "0.0.0"), "The crate version has not been set");
+
+// If only parts of the build information are needed, the generated function can return a selection of them
+build_info::build_info!(fn versions -> Versions: crate_info.version, version_control, rustc = compiler.version);
+
+// The SBOM that `build.rs` generated using `embed_sbom(true)` can be returned as a string
+build_info::build_info!(fn sbom_json = sbom);
+
+// The build information can be embedded into the `.build_info` section of the binary, where it can be read by external
+// tools without running the program
+build_info::embed!();
+
+// Interpolated strings can also be generated as `const` items, byte strings or C strings
+build_info::format_const!(
+	/// Name and version of this crate
+	const NAME_AND_VERSION = "{} v{}", $.crate_info.name, $.crate_info.version
+);
+const VERSION_BYTES: &[u8] = build_info::format_bytes!("{}", $.crate_info.version);
+const VERSION_CSTR: &std::ffi::CStr = build_info::format_cstr!("{}", $.crate_info.version);
+
 fn main() {
 	// We can now either use the `build_info` function to work with the collected data at runtime...
 	println!("{:#?}", build_info());
 
+	// The build information is stored in a `static`, but an owned `BuildInfo` can be created from it if needed
+	let owned = build_info::BuildInfo::try_from(build_info()).expect("build_info! generates valid versions");
+	println!("{owned}");
+	let versions = versions();
+	println!(
+		"{} (rustc {}, {:?})",
+		versions.crate_info_version, versions.rustc, versions.version_control
+	);
+	println!("The embedded SBOM is {} bytes long", sbom_json().len());
+
+	// Typed constants can be used like any other constant, including in patterns
+	let stability = match MAJOR_VERSION {
+		build_info::value!($.crate_info.version.major) if IS_DIRTY => "work in progress",
+		0 => "unstable",
+		_ => "stable",
+	};
+	println!("Version {MAJOR_VERSION}.x is {stability} (by {})", AUTHORS.join(", "));
+
 	// ... or format it directly to a single `&'static str` at compile time
 	println!(
 		"{}",
@@ -35,6 +81,75 @@ fn main() {
 		build_info::format!("Copyright 2020-{compilation_year}", compilation_year = $.timestamp.format("%Y"))
 	);
 
+	// There are also a few free functions that can be called inside `format!`
+	println!(
+		"{}",
+		build_info::format!("{} ({})", upper($.crate_info.name), sha256($.crate_info.version.to_string()))
+	);
+
+	// Typed literals, floats and `std::fmt`-like width and precision are supported as well
+	println!(
+		"{}",
+		build_info::format!("[{:>12}] [{:05}] [{:+.2}]", $.crate_info.name, 42u8.mul(3), 100f64.div(3))
+	);
+
+	// Some macros work inside `format!`, too
+	println!(
+		"{}",
+		build_info::format!(
+			"{}:{} (unix: {}, {}: {} bytes)",
+			file!(),
+			line!(),
+			cfg!(unix),
+			stringify!(version.txt),
+			len(include_str!("version.txt"))
+		)
+	);
+
+	// Lists can be joined in different ways
+	println!(
+		"{}",
+		build_info::format!("Authors: {:#list} ({})", $.crate_info.authors, $.crate_info.authors.join_human("or", oxford = true))
+	);
+
+	// The data model can be explored using `typeof`, `fields()` and `methods()`
+	println!(
+		"{}",
+		build_info::format!("{} has the fields {}", typeof($.target), $.target.fields())
+	);
+
+	// Target information can be queried as well
+	println!(
+		"{}",
+		build_info::format!("Built for {} {} (sse2: {})", $.target.cpu.bitness(), $.target.cpu.arch, $.target.cpu.has_feature("sse2"))
+	);
+
+	// Versions can be inspected and compared
+	println!(
+		"{}",
+		build_info::format!("Release channel: v{}.x (next patch: {}, compatible with ^0.0: {})", $.crate_info.version.major, $.crate_info.version.bump_patch(), $.crate_info.version.matches("^0.0"))
+	);
+
+	// Timestamps can be converted to other timezones and compared with each other
+	println!(
+		"{}",
+		build_info::format!("Built at {} ({} after the last commit)", $.timestamp.with_timezone("Europe/Berlin"), $.timestamp.signed_duration_since($.version_control?.git()?.commit_timestamp))
+	);
+
+	println!("{NAME_AND_VERSION} ({} bytes, {:?})", VERSION_BYTES.len(), VERSION_CSTR);
+
+	// Longer texts can be kept in template files
+	print!(
+		"{}",
+		build_info::include_template!("version.txt", name = $.crate_info.name, version = $.crate_info.version, profile = $.profile)
+	);
+
+	// `??` provides a fallback if an `Option` is empty, e.g., when building from a crates.io tarball without git
+	println!(
+		"{}",
+		build_info::format!("Branch: {} ({})", $.version_control?.git()?.branch ?? "unknown", $.version_control?.git()?.branch.map_or("detached", "on {}") ?? "no git")
+	);
+
 	// Some macros can also be called inside `format!`
 	println!(
 		"{}",
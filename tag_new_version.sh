@@ -11,7 +11,7 @@ cargo test
 cargo test --all-features
 git diff --exit-code  # check if unstaged changes exist
 git diff --cached --exit-code  # check if staged, uncommitted changes exist
-for x in build-info build-info-build build-info-common build-info-proc ; do
+for x in build-info build-info-build build-info-common build-info-format build-info-proc ; do
 	pushd $x
 	cargo msrv verify
 	popd